@@ -14,12 +14,16 @@ fn main() -> Result<()>
 
 	let years =
 	[
+		#[cfg(feature = "y2019")]
+		solution::y2019::parsers(),
 		#[cfg(feature = "y2020")]
 		solution::y2020::parsers(),
 		#[cfg(feature = "y2021")]
 		solution::y2021::parsers(),
 		#[cfg(feature = "y2023")]
 		solution::y2023::parsers(),
+		#[cfg(feature = "y2024")]
+		solution::y2024::parsers(),
 	];
 
 	let subcommands: std::collections::HashMap<_,_> = years.iter()
@@ -0,0 +1,195 @@
+//! Sparse cellular automata over arbitrary topologies.
+//!
+//! An [`Automaton`] only stores the cells differing from the background, so unbounded spaces of any
+//! dimension work as long as the pattern itself stays finite.
+//! The topology is given by a function listing the neighbours of a cell (which has to be symmetric,
+//! a cell is a neighbour of each of its neighbours) and the rule decides the next state of a cell
+//! from its own state and the states of its neighbours, in the order they were listed.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::automaton::{ life, moore, Automaton };
+//! // a glider in Conway's game of life moves diagonally by one cell every four steps
+//! let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].iter()
+//!     .map(|&(x, y)| vec![x, y])
+//!     .collect::<Vec<_>>();
+//! let mut game = Automaton::new(glider.clone(), 8, |cell: &Vec<isize>| moore(cell), life([3], [2, 3]));
+//! for _ in 0..4
+//! {
+//!     assert!(game.step());
+//! }
+//! let moved = glider.iter().map(|cell| vec![cell[0] + 1, cell[1] + 1]).collect();
+//! assert_eq!(game.cells(), &moved);
+//! assert_eq!(game.population(), Some(5));
+//!
+//! // a blinker on a bounded three by three board flips forever, a block never changes
+//! let board = (0..3).flat_map(|x| (0..3).map(move |y| vec![x, y])).collect::<Vec<_>>();
+//! let inside = |cell: &Vec<isize>| moore(cell).into_iter().filter(|cell| cell.iter().all(|&c| (0..3).contains(&c))).collect();
+//! let mut blinker = Automaton::bounded(vec![vec![0, 1], vec![1, 1], vec![2, 1]], board, inside, life([3], [2, 3]));
+//! blinker.step();
+//! assert!(blinker.is_alive(&vec![1, 0]) && !blinker.is_alive(&vec![0, 1]));
+//! let mut block = Automaton::new(vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]], 8, |cell: &Vec<isize>| moore(cell), life([3], [2, 3]));
+//! assert!(!block.step());
+//!
+//! // the background evolves with all of its neighbours, even without any living cell to look at
+//! let lonely = |_, neighbours: &[bool]| neighbours.iter().filter(|&&alive| !alive).count() == 8;
+//! let mut empty = Automaton::new(Vec::<Vec<isize>>::new(), 8, |cell: &Vec<isize>| moore(cell), lonely);
+//! assert!(empty.step());
+//! assert!(empty.background() && empty.cells().is_empty());
+//! ```
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use rayon::prelude::*;
+
+pub struct Automaton<C, N, R>
+{
+	cells: HashSet<C>,
+	background: bool,
+	degree: usize,
+	domain: Option<Vec<C>>,
+	neighbours: N,
+	rule: R,
+}
+
+impl<C, N, R> Automaton<C, N, R>
+	where
+		C: Clone + Eq + Hash + Send + Sync,
+		N: Fn(&C) -> Vec<C> + Sync,
+		R: Fn(bool, &[bool]) -> bool + Sync,
+{
+	/// An automaton on an unbounded space, with every cell not given starting out dead.
+	///
+	/// All cells must have the same number of neighbours, `degree`, the background evolves like a
+	/// cell surrounded by background.
+	pub fn new<I: IntoIterator<Item=C>>(alive: I, degree: usize, neighbours: N, rule: R) -> Self
+	{
+		let cells = alive.into_iter().collect::<HashSet<_>>();
+		Self { cells, background: false, degree, domain: None, neighbours, rule, }
+	}
+
+	/// An automaton where only the cells of the domain exist, every one of them is updated on each
+	/// step and the neighbours must not leave the domain.
+	pub fn bounded<I, D>(alive: I, domain: D, neighbours: N, rule: R) -> Self
+		where
+			I: IntoIterator<Item=C>,
+			D: IntoIterator<Item=C>,
+	{
+		let cells = alive.into_iter().collect();
+		Self { cells, background: false, degree: 0, domain: Some(domain.into_iter().collect()), neighbours, rule, }
+	}
+
+	/// The state of every cell not in [`cells`](Self::cells).
+	pub fn background(&self) -> bool
+	{
+		self.background
+	}
+
+	/// The cells differing from the background, which are the living cells unless the background is
+	/// alive.
+	pub fn cells(&self) -> &HashSet<C>
+	{
+		&self.cells
+	}
+
+	pub fn is_alive(&self, cell: &C) -> bool
+	{
+		self.cells.contains(cell) != self.background
+	}
+
+	/// The number of living cells, [`None`] if infinitely many are alive.
+	pub fn population(&self) -> Option<usize>
+	{
+		if self.background
+		{
+			None
+		}
+		else
+		{
+			Some(self.cells.len())
+		}
+	}
+
+	fn evolve(&self, cell: &C) -> bool
+	{
+		let neighbours = (self.neighbours)(cell).iter()
+			.map(|cell| self.is_alive(cell))
+			.collect::<Vec<_>>();
+		(self.rule)(self.is_alive(cell), &neighbours)
+	}
+
+	/// Advances all cells at once, returns whether any cell changed.
+	pub fn step(&mut self) -> bool
+	{
+		let (cells, background) = match self.domain
+		{
+			Some(ref domain) =>
+			{
+				let cells = domain.par_iter()
+					.filter(|cell| self.evolve(cell))
+					.cloned()
+					.collect::<HashSet<_>>();
+				(cells, false)
+			},
+			None =>
+			{
+				let background = (self.rule)(self.background, &vec![self.background; self.degree]);
+				let candidates = self.cells.par_iter()
+					.flat_map_iter(|cell| std::iter::once(cell.clone()).chain((self.neighbours)(cell)))
+					.collect::<HashSet<_>>();
+				let cells = candidates.into_par_iter()
+					.filter(|cell| self.evolve(cell) != background)
+					.collect::<HashSet<_>>();
+				(cells, background)
+			},
+		};
+
+		let changed = background != self.background || cells != self.cells;
+		self.cells = cells;
+		self.background = background;
+		changed
+	}
+}
+
+/// The rule of outer totalistic automata like Conway's game of life (which is `life([3], [2, 3])`):
+/// dead cells come alive with one of the `born` counts of living neighbours, living cells stay alive
+/// with one of the `survive` counts.
+pub fn life<B, S>(born: B, survive: S) -> impl Fn(bool, &[bool]) -> bool + Sync
+	where
+		B: IntoIterator<Item=usize>,
+		S: IntoIterator<Item=usize>,
+{
+	let born = born.into_iter().collect::<Vec<_>>();
+	let survive = survive.into_iter().collect::<Vec<_>>();
+	move |alive, neighbours|
+	{
+		let count = neighbours.iter().filter(|&&alive| alive).count();
+		if alive { survive.contains(&count) } else { born.contains(&count) }
+	}
+}
+
+/// All cells differing by at most one in every coordinate, in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::automaton::moore;
+/// assert_eq!(moore(&[0]), vec![vec![-1], vec![1]]);
+/// assert_eq!(moore(&[0, 0]).len(), 8);
+/// assert_eq!(moore(&[0, 0, 0, 0, 0]).len(), 242);
+/// ```
+pub fn moore(cell: &[isize]) -> Vec<Vec<isize>>
+{
+	cell.iter()
+		.fold(vec![Vec::with_capacity(cell.len())], |prefixes, &c|
+		{
+			prefixes.into_iter()
+				.flat_map(|prefix| (-1..=1).map(move |d| prefix.iter().copied().chain(std::iter::once(c + d)).collect()))
+				.collect()
+		})
+		.into_iter()
+		.filter(|neighbour: &Vec<isize>| neighbour.as_slice() != cell)
+		.collect()
+}
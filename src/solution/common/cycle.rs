@@ -0,0 +1,205 @@
+//! Running deterministic simulations until their state repeats.
+//!
+//! Every function takes a start state and a step function computing the next state.
+//! Once a state repeats, the simulation is known to loop forever, so the state after any number of
+//! steps can be looked up instead of simulated.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::cycle::{ brent, find_cycle, fixpoint, nth, Period };
+//! // 2, 4, 16, 256 % 100 = 56, 36, 96, 16, 56, ...
+//! let square = |n: &u64| n * n % 100;
+//!
+//! let cycle = find_cycle(2, square);
+//! assert_eq!(cycle.period(), Period { start: 2, length: 4 });
+//! assert_eq!(cycle.states(), &[2, 4, 16, 56, 36, 96]);
+//! assert_eq!(*cycle.nth(1_000_000_000_000), 36);
+//! assert_eq!(nth(2, square, 1_000_000_000_000), 36);
+//! assert_eq!(nth(2, square, 1), 4);
+//!
+//! assert_eq!(brent(2, square), Period { start: 2, length: 4 });
+//!
+//! // halving stops changing at zero
+//! assert_eq!(fixpoint(100u64, |n| n / 2), (7, 0));
+//!
+//! // compared with naive simulation for many start values
+//! for start in 0..100
+//! {
+//!     let cycle = find_cycle(start, square);
+//!     assert_eq!(brent(start, square), cycle.period());
+//!     let naive = std::iter::successors(Some(start), |n| Some(square(n))).take(50).collect::<Vec<_>>();
+//!     assert!((0..50).all(|n| naive[n] == *cycle.nth(n) && naive[n] == nth(start, square, n)));
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of a cycle: after `start` steps the states repeat every `length` steps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Period
+{
+	pub start: usize,
+	pub length: usize,
+}
+
+impl Period
+{
+	/// The smallest number of steps reaching the same state as `n` steps.
+	pub fn reduce(&self, n: usize) -> usize
+	{
+		if n < self.start
+		{
+			n
+		}
+		else
+		{
+			self.start + (n - self.start) % self.length
+		}
+	}
+
+	pub fn is_fixpoint(&self) -> bool
+	{
+		self.length == 1
+	}
+}
+
+/// All states of a simulation up to the first repetition.
+#[derive(Debug, Clone)]
+pub struct Cycle<S>
+{
+	states: Vec<S>,
+	period: Period,
+}
+
+impl<S> Cycle<S>
+{
+	pub fn period(&self) -> Period
+	{
+		self.period
+	}
+
+	/// Every distinct state in order, starting with the start state.
+	pub fn states(&self) -> &[S]
+	{
+		&self.states
+	}
+
+	/// The state after `n` steps.
+	pub fn nth(&self, n: usize) -> &S
+	{
+		&self.states[self.period.reduce(n)]
+	}
+}
+
+/// Simulates until a state repeats or `limit` steps are done, returning the states in order
+/// and where the cycle starts, if one was found.
+fn explore<S, F>(start: S, mut step: F, limit: usize) -> (Vec<S>, Option<usize>)
+	where
+		S: Clone + Eq + Hash,
+		F: FnMut(&S) -> S,
+{
+	let mut seen = HashMap::new();
+	let mut states = Vec::new();
+	let mut state = start;
+	loop
+	{
+		if let Some(&first) = seen.get(&state)
+		{
+			return (states, Some(first));
+		}
+		if states.len() > limit
+		{
+			return (states, None);
+		}
+		seen.insert(state.clone(), states.len());
+		let next = step(&state);
+		states.push(state);
+		state = next;
+	}
+}
+
+/// Finds the cycle by remembering every state seen, so the step function runs only once per state.
+pub fn find_cycle<S, F>(start: S, step: F) -> Cycle<S>
+	where
+		S: Clone + Eq + Hash,
+		F: FnMut(&S) -> S,
+{
+	let (states, first) = explore(start, step, usize::MAX);
+	let first = first.unwrap();
+	let period = Period { start: first, length: states.len() - first, };
+	Cycle { states, period, }
+}
+
+/// The state after `n` steps, simulating only until the first repetition.
+pub fn nth<S, F>(start: S, step: F, n: usize) -> S
+	where
+		S: Clone + Eq + Hash,
+		F: FnMut(&S) -> S,
+{
+	let (mut states, first) = explore(start, step, n);
+	let idx = match first
+	{
+		Some(first) => Period { start: first, length: states.len() - first, }.reduce(n),
+		None => n,
+	};
+	states.swap_remove(idx)
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only two states in memory at the cost of running
+/// the step function about three times as often.
+pub fn brent<S, F>(start: S, mut step: F) -> Period
+	where
+		S: Clone + Eq,
+		F: FnMut(&S) -> S,
+{
+	let mut power = 1;
+	let mut length = 1;
+	let mut tortoise = start.clone();
+	let mut hare = step(&start);
+	while tortoise != hare
+	{
+		if power == length
+		{
+			tortoise = hare.clone();
+			power *= 2;
+			length = 0;
+		}
+		hare = step(&hare);
+		length += 1;
+	}
+
+	let mut tortoise = start.clone();
+	let mut hare = (0..length).fold(start, |state, _| step(&state));
+	let mut first = 0;
+	while tortoise != hare
+	{
+		tortoise = step(&tortoise);
+		hare = step(&hare);
+		first += 1;
+	}
+
+	Period { start: first, length, }
+}
+
+/// Steps until the state stops changing, returning the number of steps which changed the state and
+/// the final state.
+pub fn fixpoint<S, F>(start: S, mut step: F) -> (usize, S)
+	where
+		S: PartialEq,
+		F: FnMut(&S) -> S,
+{
+	let mut state = start;
+	let mut steps = 0;
+	loop
+	{
+		let next = step(&state);
+		if next == state
+		{
+			return (steps, state);
+		}
+		state = next;
+		steps += 1;
+	}
+}
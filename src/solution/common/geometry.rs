@@ -0,0 +1,399 @@
+//! Integer geometry in three dimensions: vectors, the 24 rotations of the axis-aligned cube, rigid
+//! transforms built from them and axis-aligned boxes.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::geometry::{ Cuboid, Rotation, Transform, Vec3 };
+//! let a = Vec3::new(1, 2, 3);
+//! let b = Vec3::new(-4, 0, 5);
+//! assert_eq!(a + b, Vec3::new(-3, 2, 8));
+//! assert_eq!(a.cross(b), Vec3::new(10, -17, 8));
+//! assert_eq!(a.dot(a.cross(b)), 0);
+//! assert_eq!(a.manhattan(b), 9);
+//!
+//! // every rotation turns the vector into a different one
+//! let rotations = Rotation::all();
+//! assert_eq!(rotations.len(), 24);
+//! let images = rotations.iter().map(|r| r.apply(a)).collect::<std::collections::HashSet<_>>();
+//! assert_eq!(images.len(), 24);
+//!
+//! // the rotations form a group and keep lengths and orientation
+//! for r in rotations.iter()
+//! {
+//!     assert_eq!(r.compose(&r.inverse()), Rotation::identity());
+//!     assert_eq!(r.apply(a).dot(r.apply(b)), a.dot(b));
+//!     assert_eq!(r.apply(a).cross(r.apply(b)), r.apply(a.cross(b)));
+//!     for s in rotations.iter()
+//!     {
+//!         assert!(rotations.contains(&r.compose(s)));
+//!         assert_eq!(r.compose(s).apply(a), r.apply(s.apply(a)));
+//!     }
+//! }
+//!
+//! assert_eq!(Rotation::identity().to_string(), "(x, y, z)");
+//! assert_eq!(Rotation::new([1, 0, 2], [true, false, false]).unwrap().to_string(), "(-y, x, z)");
+//! assert_eq!(Rotation::new([1, 0, 2], [false, false, false]), None);
+//!
+//! // rigid transforms undo each other
+//! let t = Transform::new(rotations[7], Vec3::new(68, -1246, -43));
+//! let u = Transform::new(rotations[13], Vec3::new(-20, 5, 1));
+//! assert_eq!(t.inverse().apply(t.apply(b)), b);
+//! assert_eq!(t.compose(&u).apply(a), t.apply(u.apply(a)));
+//! assert_eq!(t.compose(&u).inverse(), u.inverse().compose(&t.inverse()));
+//!
+//! // boxes include both corners
+//! let brick = Cuboid::new(Vec3::new(2, 0, 5), Vec3::new(0, 0, 5));
+//! assert_eq!(brick.volume(), 3);
+//! assert!(brick.contains(Vec3::new(1, 0, 5)));
+//! let bounds = Cuboid::from_points(vec![a, b, Vec3::new(0, -7, 0)]).unwrap();
+//! assert_eq!(bounds, Cuboid::new(Vec3::new(-4, -7, 0), Vec3::new(1, 2, 5)));
+//! assert_eq!(bounds.intersection(&brick), Some(Cuboid::new(Vec3::new(0, 0, 5), Vec3::new(1, 0, 5))));
+//! assert!(!brick.intersects(&brick.translate(Vec3::new(0, 1, 0))));
+//! ```
+
+use std::ops::{ Add, AddAssign, Index, Mul, Neg, Sub, SubAssign };
+
+use num::Signed;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3<T>
+{
+	pub x: T,
+	pub y: T,
+	pub z: T,
+}
+
+impl<T: Copy + Signed> Vec3<T>
+{
+	pub fn new(x: T, y: T, z: T) -> Self
+	{
+		Self { x, y, z, }
+	}
+
+	pub fn dot(self, other: Self) -> T
+	{
+		self.x * other.x + self.y * other.y + self.z * other.z
+	}
+
+	pub fn cross(self, other: Self) -> Self
+	{
+		Self
+		{
+			x: self.y * other.z - self.z * other.y,
+			y: self.z * other.x - self.x * other.z,
+			z: self.x * other.y - self.y * other.x,
+		}
+	}
+
+	/// The sum of all coordinates.
+	pub fn sum(self) -> T
+	{
+		self.x + self.y + self.z
+	}
+
+	pub fn manhattan(self, other: Self) -> T
+	{
+		let d = self - other;
+		d.x.abs() + d.y.abs() + d.z.abs()
+	}
+
+	pub fn min(self, other: Self) -> Self
+		where
+			T: Ord,
+	{
+		Self::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+	}
+
+	pub fn max(self, other: Self) -> Self
+		where
+			T: Ord,
+	{
+		Self::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+	}
+}
+
+impl<T> From<[T; 3]> for Vec3<T>
+{
+	fn from([x, y, z]: [T; 3]) -> Self
+	{
+		Self { x, y, z, }
+	}
+}
+
+impl<T> Index<usize> for Vec3<T>
+{
+	type Output = T;
+
+	fn index(&self, idx: usize) -> &T
+	{
+		match idx
+		{
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			_ => panic!("index {} out of range for a vector in three dimensions", idx),
+		}
+	}
+}
+
+impl<T: Copy + Signed> Add for Vec3<T>
+{
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self
+	{
+		Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+	}
+}
+
+impl<T: Copy + Signed> Sub for Vec3<T>
+{
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self
+	{
+		Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+	}
+}
+
+impl<T: Copy + Signed> AddAssign for Vec3<T>
+{
+	fn add_assign(&mut self, other: Self)
+	{
+		*self = *self + other;
+	}
+}
+
+impl<T: Copy + Signed> SubAssign for Vec3<T>
+{
+	fn sub_assign(&mut self, other: Self)
+	{
+		*self = *self - other;
+	}
+}
+
+impl<T: Copy + Signed> Neg for Vec3<T>
+{
+	type Output = Self;
+
+	fn neg(self) -> Self
+	{
+		Self::new(-self.x, -self.y, -self.z)
+	}
+}
+
+impl<T: Copy + Signed> Mul<T> for Vec3<T>
+{
+	type Output = Self;
+
+	fn mul(self, factor: T) -> Self
+	{
+		Self::new(self.x * factor, self.y * factor, self.z * factor)
+	}
+}
+
+/// One of the 24 rotations mapping the coordinate axes onto each other.
+///
+/// Coordinate `i` of a rotated vector is coordinate `axes[i]` of the original one, negated if
+/// `negate[i]` is set.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rotation
+{
+	axes: [usize; 3],
+	negate: [bool; 3],
+}
+
+impl Rotation
+{
+	/// The rotation picking the given axes, [`None`] unless the axes are a permutation and the
+	/// orientation is kept, as anything else would be a reflection.
+	pub fn new(axes: [usize; 3], negate: [bool; 3]) -> Option<Self>
+	{
+		let mut sorted = axes;
+		sorted.sort_unstable();
+		if sorted != [0, 1, 2]
+		{
+			return None;
+		}
+		// the determinant is the sign of the permutation times the signs of the axes
+		let odd_permutation = (axes[0] > axes[1]) ^ (axes[0] > axes[2]) ^ (axes[1] > axes[2]);
+		let odd_negations = negate.iter().filter(|&&n| n).count() % 2 == 1;
+		(odd_permutation == odd_negations).then_some(Self { axes, negate, })
+	}
+
+	pub fn identity() -> Self
+	{
+		Self { axes: [0, 1, 2], negate: [false; 3], }
+	}
+
+	/// All 24 rotations, starting with the identity.
+	pub fn all() -> Vec<Self>
+	{
+		let permutations = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+		permutations.iter()
+			.flat_map(|&axes| (0..8).map(move |signs| (axes, [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0])))
+			.filter_map(|(axes, negate)| Self::new(axes, negate))
+			.collect()
+	}
+
+	pub fn apply<T: Copy + Signed>(&self, v: Vec3<T>) -> Vec3<T>
+	{
+		let coordinate = |i: usize| if self.negate[i] { -v[self.axes[i]] } else { v[self.axes[i]] };
+		Vec3::new(coordinate(0), coordinate(1), coordinate(2))
+	}
+
+	/// The rotation applying `other` first and `self` afterwards.
+	pub fn compose(&self, other: &Self) -> Self
+	{
+		let mut axes = [0; 3];
+		let mut negate = [false; 3];
+		for i in 0..3
+		{
+			axes[i] = other.axes[self.axes[i]];
+			negate[i] = self.negate[i] ^ other.negate[self.axes[i]];
+		}
+		Self { axes, negate, }
+	}
+
+	pub fn inverse(&self) -> Self
+	{
+		let mut axes = [0; 3];
+		let mut negate = [false; 3];
+		for i in 0..3
+		{
+			axes[self.axes[i]] = i;
+			negate[self.axes[i]] = self.negate[i];
+		}
+		Self { axes, negate, }
+	}
+}
+
+/// Shows where each coordinate comes from, so the identity is `(x, y, z)`.
+impl std::fmt::Display for Rotation
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		let coordinate = |i: usize| format!("{}{}", if self.negate[i] { "-" } else { "" }, ["x", "y", "z"][self.axes[i]]);
+		write!(f, "({}, {}, {})", coordinate(0), coordinate(1), coordinate(2))
+	}
+}
+
+impl Default for Rotation
+{
+	fn default() -> Self
+	{
+		Self::identity()
+	}
+}
+
+/// A rotation followed by a translation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transform<T>
+{
+	pub rotation: Rotation,
+	pub translation: Vec3<T>,
+}
+
+impl<T: Copy + Signed> Transform<T>
+{
+	pub fn new(rotation: Rotation, translation: Vec3<T>) -> Self
+	{
+		Self { rotation, translation, }
+	}
+
+	pub fn identity() -> Self
+	{
+		Self::new(Rotation::identity(), Vec3::new(T::zero(), T::zero(), T::zero()))
+	}
+
+	pub fn apply(&self, v: Vec3<T>) -> Vec3<T>
+	{
+		self.rotation.apply(v) + self.translation
+	}
+
+	/// The transform applying `other` first and `self` afterwards.
+	pub fn compose(&self, other: &Self) -> Self
+	{
+		Self::new(self.rotation.compose(&other.rotation), self.apply(other.translation))
+	}
+
+	pub fn inverse(&self) -> Self
+	{
+		let rotation = self.rotation.inverse();
+		Self::new(rotation, -rotation.apply(self.translation))
+	}
+}
+
+/// An axis-aligned box including both of its corners.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid<T>
+{
+	min: Vec3<T>,
+	max: Vec3<T>,
+}
+
+impl<T: Copy + Ord + Signed> Cuboid<T>
+{
+	/// The box spanned by two opposite corners in any order.
+	pub fn new(a: Vec3<T>, b: Vec3<T>) -> Self
+	{
+		Self { min: a.min(b), max: a.max(b), }
+	}
+
+	/// The smallest box containing all points, [`None`] without any.
+	pub fn from_points<I: IntoIterator<Item=Vec3<T>>>(points: I) -> Option<Self>
+	{
+		let mut points = points.into_iter();
+		let first = points.next()?;
+		Some(points.fold(Self::new(first, first), |cuboid, point| Self { min: cuboid.min.min(point), max: cuboid.max.max(point), }))
+	}
+
+	pub fn min(&self) -> Vec3<T>
+	{
+		self.min
+	}
+
+	pub fn max(&self) -> Vec3<T>
+	{
+		self.max
+	}
+
+	/// The number of points along each axis.
+	pub fn size(&self) -> Vec3<T>
+	{
+		self.max - self.min + Vec3::new(T::one(), T::one(), T::one())
+	}
+
+	pub fn volume(&self) -> T
+	{
+		let size = self.size();
+		size.x * size.y * size.z
+	}
+
+	pub fn contains(&self, point: Vec3<T>) -> bool
+	{
+		(0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+	}
+
+	pub fn intersects(&self, other: &Self) -> bool
+	{
+		(0..3).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+	}
+
+	pub fn intersection(&self, other: &Self) -> Option<Self>
+	{
+		self.intersects(other).then(|| Self { min: self.min.max(other.min), max: self.max.min(other.max), })
+	}
+
+	/// The smallest box containing both boxes.
+	pub fn hull(&self, other: &Self) -> Self
+	{
+		Self { min: self.min.min(other.min), max: self.max.max(other.max), }
+	}
+
+	pub fn translate(&self, offset: Vec3<T>) -> Self
+	{
+		Self { min: self.min + offset, max: self.max + offset, }
+	}
+}
@@ -0,0 +1,224 @@
+//! Undirected weighted graphs over arbitrary node types.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::graph::Graph;
+//! // two triangles joined by a single bridge
+//! let mut graph = Graph::new();
+//! for &(a, b) in [("a", "b"), ("b", "c"), ("c", "a"), ("x", "y"), ("y", "z"), ("z", "x"), ("c", "x")].iter()
+//! {
+//!     graph.add_edge(a, b, 1);
+//! }
+//! let cut = graph.min_cut().unwrap();
+//! assert_eq!(cut.weight, 1);
+//! assert_eq!(cut.edges.len(), 1);
+//! assert_eq!((cut.side.len(), cut.rest.len()), (3, 3));
+//!
+//! // the example from the Stoer-Wagner paper
+//! let mut graph = Graph::new();
+//! for &(a, b, weight) in [(1, 2, 2), (1, 5, 3), (2, 3, 3), (2, 5, 2), (2, 6, 2), (3, 4, 4), (3, 7, 2), (4, 7, 2), (4, 8, 2), (5, 6, 3), (6, 7, 1), (7, 8, 3)].iter()
+//! {
+//!     graph.add_edge(a, b, weight);
+//! }
+//! let cut = graph.min_cut().unwrap();
+//! assert_eq!(cut.weight, 4);
+//! let mut sides = [cut.side.clone(), cut.rest.clone()];
+//! sides.iter_mut().for_each(|side| side.sort_unstable());
+//! sides.sort();
+//! assert_eq!(sides, [vec![1, 2, 5, 6], vec![3, 4, 7, 8]]);
+//! assert_eq!(cut.edges.iter().map(|(a, b)| graph.weight(a, b)).sum::<usize>(), 4);
+//!
+//! // nothing has to be cut between separate components, and a single node cannot be split
+//! let mut graph = Graph::new();
+//! graph.add_edge('a', 'b', 5);
+//! graph.add_node('c');
+//! let cut = graph.min_cut().unwrap();
+//! assert_eq!((cut.weight, cut.edges.len()), (0, 0));
+//! let mut graph = Graph::new();
+//! graph.add_node('a');
+//! assert_eq!(graph.min_cut(), None);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{ BinaryHeap, HashMap };
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N>
+{
+	nodes: Vec<N>,
+	index: HashMap<N, usize>,
+	adjacency: Vec<HashMap<usize, usize>>,
+}
+
+/// A partition of the nodes into two non-empty sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut<N>
+{
+	/// The total weight of the edges between both sides.
+	pub weight: usize,
+	pub side: Vec<N>,
+	pub rest: Vec<N>,
+	pub edges: Vec<(N, N)>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N>
+{
+	fn default() -> Self
+	{
+		Self::new()
+	}
+}
+
+impl<N: Clone + Eq + Hash> Graph<N>
+{
+	pub fn new() -> Self
+	{
+		Self { nodes: Vec::new(), index: HashMap::new(), adjacency: Vec::new(), }
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.nodes.len()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.nodes.is_empty()
+	}
+
+	pub fn nodes(&self) -> &[N]
+	{
+		&self.nodes
+	}
+
+	/// Adds the node unless it already exists, returning its index.
+	pub fn add_node(&mut self, node: N) -> usize
+	{
+		if let Some(&idx) = self.index.get(&node)
+		{
+			return idx;
+		}
+		let idx = self.nodes.len();
+		self.index.insert(node.clone(), idx);
+		self.nodes.push(node);
+		self.adjacency.push(HashMap::new());
+		idx
+	}
+
+	/// Adds an edge between both nodes, parallel edges add up their weight.
+	pub fn add_edge(&mut self, a: N, b: N, weight: usize)
+	{
+		let (a, b) = (self.add_node(a), self.add_node(b));
+		*self.adjacency[a].entry(b).or_insert(0) += weight;
+		*self.adjacency[b].entry(a).or_insert(0) += weight;
+	}
+
+	/// The weight of the edge between both nodes, zero if they are not connected.
+	pub fn weight(&self, a: &N, b: &N) -> usize
+	{
+		self.index.get(a)
+			.zip(self.index.get(b))
+			.and_then(|(&a, b)| self.adjacency[a].get(b))
+			.copied()
+			.unwrap_or(0)
+	}
+
+	pub fn neighbours<'a>(&'a self, node: &N) -> impl Iterator<Item=(&'a N, usize)> + 'a
+	{
+		self.index.get(node)
+			.into_iter()
+			.flat_map(move |&idx| self.adjacency[idx].iter())
+			.map(move |(&other, &weight)| (&self.nodes[other], weight))
+	}
+
+	fn cut(&self, side: &[usize], weight: usize) -> Cut<N>
+	{
+		let mut in_side = vec![false; self.nodes.len()];
+		side.iter().for_each(|&idx| in_side[idx] = true);
+		let edges = side.iter()
+			.flat_map(|&a| self.adjacency[a].keys().filter(|&&b| !in_side[b]).map(move |&b| (a, b)))
+			.map(|(a, b)| (self.nodes[a].clone(), self.nodes[b].clone()))
+			.collect();
+		let (side, rest) = (0..self.nodes.len()).partition::<Vec<_>, _>(|&idx| in_side[idx]);
+		Cut
+		{
+			weight,
+			side: side.into_iter().map(|idx| self.nodes[idx].clone()).collect(),
+			rest: rest.into_iter().map(|idx| self.nodes[idx].clone()).collect(),
+			edges,
+		}
+	}
+
+	/// The global minimum cut using the Stoer-Wagner algorithm, [`None`] with less than two nodes.
+	pub fn min_cut(&self) -> Option<Cut<N>>
+	{
+		let n = self.nodes.len();
+		if n < 2
+		{
+			return None;
+		}
+
+		let mut adjacency = self.adjacency.clone();
+		let mut members = (0..n).map(|idx| vec![idx]).collect::<Vec<_>>();
+		let mut merged = vec![false; n];
+		let mut best: Option<(usize, Vec<usize>)> = None;
+
+		for phase in 0..(n - 1)
+		{
+			// maximum adjacency order: always add the node most tightly connected to the added ones
+			let start = (0..n).find(|&idx| !merged[idx]).unwrap();
+			let mut connection = vec![0; n];
+			let mut added = vec![false; n];
+			let mut queue = BinaryHeap::from(vec![(0, Reverse(start))]);
+			let mut order = Vec::with_capacity(n - phase);
+			while let Some((weight, Reverse(node))) = queue.pop()
+			{
+				if added[node] || weight != connection[node]
+				{
+					continue;
+				}
+				added[node] = true;
+				order.push(node);
+				for (&other, &weight) in adjacency[node].iter()
+				{
+					if !added[other]
+					{
+						connection[other] += weight;
+						queue.push((connection[other], Reverse(other)));
+					}
+				}
+			}
+
+			if order.len() < n - phase
+			{
+				// not connected, nothing needs to be cut
+				let side = order.iter().flat_map(|&node| members[node].iter().copied()).collect::<Vec<_>>();
+				return Some(self.cut(&side, 0));
+			}
+
+			let (last, previous) = (order[order.len() - 1], order[order.len() - 2]);
+			if best.as_ref().map(|(weight, _)| connection[last] < *weight).unwrap_or(true)
+			{
+				best = Some((connection[last], members[last].clone()));
+			}
+
+			let edges = std::mem::take(&mut adjacency[last]);
+			for (other, weight) in edges
+			{
+				adjacency[other].remove(&last);
+				if other != previous
+				{
+					*adjacency[previous].entry(other).or_insert(0) += weight;
+					*adjacency[other].entry(previous).or_insert(0) += weight;
+				}
+			}
+			let moved = std::mem::take(&mut members[last]);
+			members[previous].extend(moved);
+			merged[last] = true;
+		}
+
+		best.map(|(weight, side)| self.cut(&side, weight))
+	}
+}
@@ -0,0 +1,501 @@
+//! Rectangular grids and the directions used to walk them.
+//!
+//! A [`Position`] is an `(x, y)` tuple where `x` is the column and `y` the row, so `y` grows
+//! downwards just like the lines of the puzzle input.
+//! Stepping a [`Position`] in a [`Direction`] is checked and yields [`None`] when leaving the
+//! non-negative quadrant, stepping a [`Vector`] is unchecked.
+//!
+//! # Examples
+//!
+//! Parsing a map using any tile type implementing `TryFrom<char>`:
+//!
+//! ```
+//! # use adventofcode::solution::common::grid::{ Direction, Grid };
+//! let grid: Grid<char> = "\
+//!     abc\n\
+//!     def".parse().unwrap();
+//! assert_eq!((grid.width(), grid.height()), (3, 2));
+//! assert_eq!(grid.get((2, 1)), Some(&'f'));
+//! assert_eq!(grid.get((3, 1)), None);
+//! assert_eq!(grid[(1, 0)], 'b');
+//! assert_eq!(grid.find(|&ch| ch == 'e'), Some((1, 1)));
+//! assert_eq!(grid.column(1).collect::<String>(), "be");
+//! assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+//! assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+//! assert!("ab\nc".parse::<Grid<char>>().is_err());
+//! ```
+//!
+//! Walking around:
+//!
+//! ```
+//! # use adventofcode::solution::common::grid::{ Direction, Grid };
+//! let grid: Grid<char> = "abc\ndef".parse().unwrap();
+//! assert_eq!((1usize, 1) + Direction::Up, Some((1, 0)));
+//! assert_eq!((0usize, 0) + Direction::Left, None);
+//! assert_eq!((0isize, 0) + Direction::Left, (-1, 0));
+//! assert_eq!(Direction::Up.right(), Direction::Right);
+//! assert_eq!(Direction::Up.invert(), Direction::Down);
+//! assert_eq!(grid.step((2, 0), Direction::Right), None);
+//! assert_eq!(grid.neighbours((0, 0)).map(|(_, pos)| grid[pos]).collect::<String>(), "bd");
+//! assert_eq!(grid.neighbours8((1, 0)).map(|pos| grid[pos]).collect::<String>(), "acdef");
+//! assert_eq!(grid.step_wrapping((2, 0), Direction::Right), Some((0, 0)));
+//! assert_eq!(grid.get_wrapping((-1, 3)), Some(&'f'));
+//! assert_eq!(grid.get_signed((-1, 0)), None);
+//! let empty = Grid::<char>::new(0, 3, Vec::new()).unwrap();
+//! assert_eq!(empty.get_wrapping((1, 1)), None);
+//! assert_eq!(empty.step_wrapping((0, 0), Direction::Down), None);
+//! ```
+
+use crate::error::*;
+
+use std::convert::TryFrom;
+
+/// Unsigned `(x, y)` coordinates within a grid.
+pub type Position = (usize, usize);
+
+/// Signed `(x, y)` coordinates or offsets, used wherever a grid is unbounded.
+pub type Vector = (isize, isize);
+
+/// Offsets of all eight neighbours, row by row.
+pub const NEIGHBOURS8: [Vector; 8] =
+[
+	(-1, -1), (0, -1), (1, -1),
+	(-1, 0), (1, 0),
+	(-1, 1), (0, 1), (1, 1),
+];
+
+/// Adds a signed offset to a position, [`None`] if that leaves the non-negative quadrant.
+pub fn offset((x, y): Position, (dx, dy): Vector) -> Option<Position>
+{
+	Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+}
+
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum Orientation
+{
+	Horizontal,
+	Vertical,
+}
+
+impl Orientation
+{
+	pub fn invert(&self) -> Orientation
+	{
+		match self
+		{
+			Orientation::Horizontal => Orientation::Vertical,
+			Orientation::Vertical => Orientation::Horizontal,
+		}
+	}
+
+	pub fn slice() -> &'static [Orientation]
+	{
+		&[
+			Orientation::Vertical,
+			Orientation::Horizontal,
+		]
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum Direction
+{
+	Up,
+	Down,
+	Left,
+	Right,
+}
+
+impl Direction
+{
+	pub fn vector(&self) -> Vector
+	{
+		match self
+		{
+			Direction::Up => (0, -1),
+			Direction::Down => (0, 1),
+			Direction::Left => (-1, 0),
+			Direction::Right => (1, 0),
+		}
+	}
+
+	/// All directions in clockwise order, starting to the right.
+	pub fn slice() -> &'static [Direction]
+	{
+		&[
+			Direction::Right,
+			Direction::Down,
+			Direction::Left,
+			Direction::Up,
+		]
+	}
+
+	pub fn orientation(&self) -> Orientation
+	{
+		match self
+		{
+			Direction::Up | Direction::Down => Orientation::Vertical,
+			Direction::Left | Direction::Right => Orientation::Horizontal,
+		}
+	}
+
+	/// Turns counter-clockwise.
+	pub fn left(&self) -> Direction
+	{
+		Direction::slice()[(Direction::slice().iter().position(|dir| dir == self).unwrap() + 3) % 4]
+	}
+
+	/// Turns clockwise.
+	pub fn right(&self) -> Direction
+	{
+		Direction::slice()[(Direction::slice().iter().position(|dir| dir == self).unwrap() + 1) % 4]
+	}
+
+	pub fn invert(&self) -> Direction
+	{
+		Direction::slice()[(Direction::slice().iter().position(|dir| dir == self).unwrap() + 2) % 4]
+	}
+
+	pub fn symbol(&self) -> char
+	{
+		match self
+		{
+			Direction::Up => '^',
+			Direction::Down => 'v',
+			Direction::Left => '<',
+			Direction::Right => '>',
+		}
+	}
+}
+
+impl std::default::Default for Direction
+{
+	fn default() -> Self
+	{
+		Direction::slice()[0]
+	}
+}
+
+/// Accepts both the arrows of [`Direction::symbol`] and the letters `U`, `D`, `L` and `R`.
+impl TryFrom<char> for Direction
+{
+	type Error = Error;
+	fn try_from(ch: char) -> std::result::Result<Self, Error>
+	{
+		Ok(match ch
+		{
+			'^' | 'U' => Direction::Up,
+			'v' | 'D' => Direction::Down,
+			'<' | 'L' => Direction::Left,
+			'>' | 'R' => Direction::Right,
+			ch => Err(anyhow!("char {:?} does not correspond to a direction", ch))?,
+		})
+	}
+}
+
+impl std::ops::Add<Direction> for Position
+{
+	type Output = Option<Position>;
+
+	fn add(self, dir: Direction) -> Option<Position>
+	{
+		offset(self, dir.vector())
+	}
+}
+
+impl std::ops::Add<Direction> for Vector
+{
+	type Output = Vector;
+
+	fn add(self, dir: Direction) -> Vector
+	{
+		let (dx, dy) = dir.vector();
+		(self.0 + dx, self.1 + dy)
+	}
+}
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T>
+{
+	width: usize,
+	height: usize,
+	cells: Vec<T>,
+}
+
+impl<T> Grid<T>
+{
+	pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self>
+	{
+		anyhow::ensure!(width * height == cells.len(), "grid of size {}x{} cannot hold {} cells", width, height, cells.len());
+		Ok(Self { width, height, cells, })
+	}
+
+	/// Builds a grid from its rows, all of which must have the same length.
+	pub fn from_rows<I: IntoIterator<Item=Vec<T>>>(rows: I) -> Result<Self>
+	{
+		let mut width = None;
+		let mut height = 0;
+		let mut cells = Vec::new();
+		for row in rows
+		{
+			let expected = *width.get_or_insert(row.len());
+			anyhow::ensure!(row.len() == expected, "row {} has length {} instead of {}", height, row.len(), expected);
+			cells.extend(row);
+			height += 1;
+		}
+		Ok(Self { width: width.unwrap_or(0), height, cells, })
+	}
+
+	pub fn from_fn<F: FnMut(Position) -> T>(width: usize, height: usize, mut f: F) -> Self
+	{
+		let cells = (0..height)
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(&mut f)
+			.collect();
+		Self { width, height, cells, }
+	}
+
+	pub fn width(&self) -> usize
+	{
+		self.width
+	}
+
+	pub fn height(&self) -> usize
+	{
+		self.height
+	}
+
+	pub fn contains(&self, (x, y): Position) -> bool
+	{
+		x < self.width && y < self.height
+	}
+
+	pub fn get(&self, pos: Position) -> Option<&T>
+	{
+		if self.contains(pos)
+		{
+			self.cells.get(pos.1 * self.width + pos.0)
+		}
+		else
+		{
+			None
+		}
+	}
+
+	pub fn get_mut(&mut self, pos: Position) -> Option<&mut T>
+	{
+		if self.contains(pos)
+		{
+			self.cells.get_mut(pos.1 * self.width + pos.0)
+		}
+		else
+		{
+			None
+		}
+	}
+
+	pub fn get_signed(&self, (x, y): Vector) -> Option<&T>
+	{
+		self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+	}
+
+	/// Treats the grid as infinitely repeating in every direction, [`None`] if the grid is empty.
+	pub fn get_wrapping(&self, (x, y): Vector) -> Option<&T>
+	{
+		let pos = (x.checked_rem_euclid(self.width as isize)? as usize, y.checked_rem_euclid(self.height as isize)? as usize);
+		self.get(pos)
+	}
+
+	/// Treats the grid as surrounded by an infinite plane of `default`.
+	pub fn get_or<'a>(&'a self, pos: Vector, default: &'a T) -> &'a T
+	{
+		self.get_signed(pos).unwrap_or(default)
+	}
+
+	/// Steps into a direction, [`None`] if that leaves the grid.
+	pub fn step(&self, pos: Position, dir: Direction) -> Option<Position>
+	{
+		(pos + dir).filter(|&pos| self.contains(pos))
+	}
+
+	/// Steps into a direction, wrapping around at the edges, [`None`] if the grid is empty.
+	pub fn step_wrapping(&self, (x, y): Position, dir: Direction) -> Option<Position>
+	{
+		let (dx, dy) = dir.vector();
+		Some(((x as isize + dx).checked_rem_euclid(self.width as isize)? as usize, (y as isize + dy).checked_rem_euclid(self.height as isize)? as usize))
+	}
+
+	/// All positions row by row.
+	pub fn positions(&self) -> impl Iterator<Item=Position>
+	{
+		let width = self.width;
+		(0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item=(Position, &T)>
+	{
+		self.positions().zip(self.cells.iter())
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item=(Position, &mut T)>
+	{
+		self.positions().zip(self.cells.iter_mut())
+	}
+
+	/// The up to four orthogonal neighbours within the grid, in [`Direction::slice`] order.
+	pub fn neighbours(&self, pos: Position) -> impl Iterator<Item=(Direction, Position)> + '_
+	{
+		Direction::slice().iter()
+			.filter_map(move |&dir| Some((dir, self.step(pos, dir)?)))
+	}
+
+	/// The up to eight neighbours including diagonals within the grid, row by row.
+	pub fn neighbours8(&self, pos: Position) -> impl Iterator<Item=Position> + '_
+	{
+		NEIGHBOURS8.iter()
+			.filter_map(move |&vector| offset(pos, vector))
+			.filter(move |&pos| self.contains(pos))
+	}
+
+	pub fn row(&self, y: usize) -> Option<&[T]>
+	{
+		if y < self.height
+		{
+			Some(&self.cells[y * self.width..(y + 1) * self.width])
+		}
+		else
+		{
+			None
+		}
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item=&[T]>
+	{
+		(0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+	}
+
+	pub fn column(&self, x: usize) -> impl Iterator<Item=&T>
+	{
+		self.cells.iter()
+			.skip(x)
+			.step_by(self.width.max(1))
+			.take(if x < self.width { self.height } else { 0 })
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>>
+	{
+		(0..self.width).map(move |x| self.column(x))
+	}
+
+	pub fn find<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<Position>
+	{
+		self.iter()
+			.find(|(_, cell)| predicate(cell))
+			.map(|(pos, _)| pos)
+	}
+
+	pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U>
+	{
+		Grid
+		{
+			width: self.width,
+			height: self.height,
+			cells: self.cells.iter().map(f).collect(),
+		}
+	}
+}
+
+impl<T: Clone> Grid<T>
+{
+	pub fn filled(width: usize, height: usize, value: T) -> Self
+	{
+		Self { width, height, cells: vec![value; width * height], }
+	}
+
+	/// Mirrors the grid along its main diagonal, swapping rows and columns.
+	pub fn transpose(&self) -> Self
+	{
+		Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+	}
+
+	pub fn rotate_clockwise(&self) -> Self
+	{
+		Self::from_fn(self.height, self.width, |(x, y)| self[(y, self.height - 1 - x)].clone())
+	}
+
+	pub fn rotate_counterclockwise(&self) -> Self
+	{
+		Self::from_fn(self.height, self.width, |(x, y)| self[(self.width - 1 - y, x)].clone())
+	}
+
+	/// Mirrors the grid left to right.
+	pub fn flip_horizontal(&self) -> Self
+	{
+		Self::from_fn(self.width, self.height, |(x, y)| self[(self.width - 1 - x, y)].clone())
+	}
+
+	/// Mirrors the grid top to bottom.
+	pub fn flip_vertical(&self) -> Self
+	{
+		Self::from_fn(self.width, self.height, |(x, y)| self[(x, self.height - 1 - y)].clone())
+	}
+}
+
+impl<T> std::ops::Index<Position> for Grid<T>
+{
+	type Output = T;
+
+	fn index(&self, pos: Position) -> &T
+	{
+		self.get(pos).unwrap_or_else(|| panic!("position {:?} outside of {}x{} grid", pos, self.width, self.height))
+	}
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T>
+{
+	fn index_mut(&mut self, pos: Position) -> &mut T
+	{
+		let (width, height) = (self.width, self.height);
+		self.get_mut(pos).unwrap_or_else(|| panic!("position {:?} outside of {}x{} grid", pos, width, height))
+	}
+}
+
+impl<T> std::str::FromStr for Grid<T>
+	where
+		T: TryFrom<char>,
+		anyhow::Error: From<<T as TryFrom<char>>::Error>,
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let rows = input.lines()
+			.enumerate()
+			.map(|(y, line)|
+			{
+				line.chars()
+					.enumerate()
+					.map(|(x, ch)| T::try_from(ch).map_err(anyhow::Error::from).with_context(|| format!("invalid tile at {:?}", (x, y))))
+					.collect::<Result<Vec<T>>>()
+			})
+			.collect::<Result<Vec<Vec<T>>>>()
+			.context(Error::AocParsing)?;
+		Ok(Self::from_rows(rows).context(Error::AocParsing)?)
+	}
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T>
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		for row in self.rows()
+		{
+			for cell in row
+			{
+				write!(f, "{}", cell)?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
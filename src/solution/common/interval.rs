@@ -0,0 +1,403 @@
+//! Sets of integers stored as intervals and sets of points stored as axis-aligned boxes.
+//!
+//! All intervals are half-open [`Range`]s, inclusive bounds have to be converted by the caller.
+//!
+//! # Examples
+//!
+//! Both sets are checked against a naive implementation keeping every single member, using pseudo
+//! random intervals from a small linear congruential generator.
+//!
+//! ```
+//! # use adventofcode::solution::common::interval::{ BoxSet, IntervalSet };
+//! # use std::{ cell::Cell, collections::HashSet };
+//! let seed = Cell::new(42u64);
+//! let random = |max: u64|
+//! {
+//!     seed.set(seed.get().wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407));
+//!     (seed.get() >> 33) % max
+//! };
+//! let range = |max: u64| { let start = random(max); start..(start + random(12)) };
+//!
+//! for _ in 0..50
+//! {
+//!     let a = (0..6).map(|_| range(60)).collect::<Vec<_>>();
+//!     let b = (0..6).map(|_| range(60)).collect::<Vec<_>>();
+//!     let naive_a = a.iter().cloned().flatten().collect::<HashSet<_>>();
+//!     let naive_b = b.iter().cloned().flatten().collect::<HashSet<_>>();
+//!     let (a, b) = (a.into_iter().collect::<IntervalSet<_>>(), b.into_iter().collect::<IntervalSet<_>>());
+//!
+//!     let members = |set: &IntervalSet<u64>| set.iter().cloned().flatten().collect::<HashSet<_>>();
+//!     assert_eq!(members(&a), naive_a);
+//!     assert_eq!(a.len(), naive_a.len() as u64);
+//!     assert_eq!(members(&a.union(&b)), &naive_a | &naive_b);
+//!     assert_eq!(members(&a.intersection(&b)), &naive_a & &naive_b);
+//!     assert_eq!(members(&a.difference(&b)), &naive_a - &naive_b);
+//!     assert!((0..80).all(|n| a.contains(&n) == naive_a.contains(&n)));
+//!     // coalesced: sorted, disjoint and never touching
+//!     assert!(a.iter().all(|r| !r.is_empty()));
+//!     assert!(a.iter().zip(a.iter().skip(1)).all(|(r1, r2)| r1.end < r2.start));
+//!
+//!     let pieces = (0..3).map(|_| (range(60), random(100))).collect::<Vec<_>>();
+//!     let naive = naive_a.iter()
+//!         .map(|&n| pieces.iter()
+//!             .find(|(source, _)| source.contains(&n))
+//!             .map(|(source, destination)| n - source.start + destination)
+//!             .unwrap_or(n))
+//!         .collect::<HashSet<_>>();
+//!     assert_eq!(members(&a.map_piecewise(&pieces)), naive);
+//! }
+//!
+//! for _ in 0..20
+//! {
+//!     let mut boxes = BoxSet::new();
+//!     let mut naive = HashSet::new();
+//!     for _ in 0..8
+//!     {
+//!         let cuboid = [range(10), range(10), range(10)];
+//!         let points = cuboid[0].clone()
+//!             .flat_map(|x| cuboid[1].clone().map(move |y| (x, y)))
+//!             .flat_map(|(x, y)| cuboid[2].clone().map(move |z| [x, y, z]))
+//!             .collect::<Vec<_>>();
+//!         if random(3) == 0
+//!         {
+//!             boxes.remove(&cuboid);
+//!             points.iter().for_each(|point| { naive.remove(point); });
+//!         }
+//!         else
+//!         {
+//!             boxes.insert(cuboid);
+//!             naive.extend(points);
+//!         }
+//!     }
+//!     assert_eq!(boxes.volume(), naive.len() as u64);
+//!     assert!(naive.iter().all(|point| boxes.contains(point)));
+//!     let clip = [0..5, 2..7, 4..9];
+//!     let clipped = naive.iter().filter(|p| (0..3).all(|axis| clip[axis].contains(&p[axis]))).count();
+//!     assert_eq!(boxes.intersection(&clip).volume(), clipped as u64);
+//! }
+//! ```
+
+use std::iter::FromIterator;
+use std::ops::{ Add, Mul, Range, Sub };
+
+/// A set of values kept as sorted, disjoint and non-adjacent intervals.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::interval::IntervalSet;
+/// let mut set = IntervalSet::from(1..4);
+/// set.insert(4..6);
+/// set.insert(8..10);
+/// assert_eq!(set.ranges(), &[1..6, 8..10]);
+/// set.remove(2..9);
+/// assert_eq!(set.ranges(), &[1..2, 9..10]);
+/// assert!(set.contains(&9) && !set.contains(&5));
+/// assert_eq!(set.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T>
+{
+	ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T>
+{
+	pub fn new() -> Self
+	{
+		Self { ranges: Vec::new(), }
+	}
+
+	pub fn ranges(&self) -> &[Range<T>]
+	{
+		&self.ranges
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, Range<T>>
+	{
+		self.ranges.iter()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.ranges.is_empty()
+	}
+
+	pub fn min(&self) -> Option<T>
+	{
+		self.ranges.first().map(|range| range.start)
+	}
+
+	/// The smallest interval containing the whole set.
+	pub fn hull(&self) -> Option<Range<T>>
+	{
+		Some(self.ranges.first()?.start..self.ranges.last()?.end)
+	}
+
+	pub fn contains(&self, value: &T) -> bool
+	{
+		let idx = self.ranges.partition_point(|range| range.end <= *value);
+		self.ranges.get(idx).map(|range| range.start <= *value).unwrap_or(false)
+	}
+
+	pub fn insert(&mut self, range: Range<T>)
+	{
+		*self = self.union(&Self::from(range));
+	}
+
+	pub fn remove(&mut self, range: Range<T>)
+	{
+		*self = self.difference(&Self::from(range));
+	}
+
+	pub fn union(&self, other: &Self) -> Self
+	{
+		self.ranges.iter().chain(other.ranges.iter()).cloned().collect()
+	}
+
+	pub fn intersection(&self, other: &Self) -> Self
+	{
+		let mut ranges = Vec::new();
+		let (mut i, mut j) = (0, 0);
+		while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j))
+		{
+			let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+			if start < end
+			{
+				ranges.push(start..end);
+			}
+			if a.end < b.end
+			{
+				i += 1;
+			}
+			else
+			{
+				j += 1;
+			}
+		}
+		Self { ranges, }
+	}
+
+	pub fn difference(&self, other: &Self) -> Self
+	{
+		let mut ranges = Vec::new();
+		let mut j = 0;
+		for range in self.ranges.iter()
+		{
+			let mut start = range.start;
+			while other.ranges.get(j).map(|cut| cut.end <= start).unwrap_or(false)
+			{
+				j += 1;
+			}
+			for cut in other.ranges[j..].iter().take_while(|cut| cut.start < range.end)
+			{
+				if start < cut.start
+				{
+					ranges.push(start..cut.start);
+				}
+				start = start.max(cut.end);
+			}
+			if start < range.end
+			{
+				ranges.push(start..range.end);
+			}
+		}
+		Self { ranges, }
+	}
+}
+
+impl<T: Copy + Ord + Add<Output=T> + Sub<Output=T> + Default> IntervalSet<T>
+{
+	/// The number of values in the set.
+	pub fn len(&self) -> T
+	{
+		self.ranges.iter().fold(T::default(), |len, range| len + (range.end - range.start))
+	}
+
+	/// Maps the values of every `(source, destination)` piece from the source interval onto the
+	/// interval of the same length starting at the destination.
+	///
+	/// Values covered by several pieces are mapped by the first one, values not covered by any piece
+	/// are left as they are.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use adventofcode::solution::common::interval::IntervalSet;
+	/// let set = IntervalSet::from(0..10);
+	/// let mapped = set.map_piecewise(&[(2..4, 20), (3..6, 30)]);
+	/// assert_eq!(mapped.ranges(), &[0..2, 6..10, 20..22, 31..33]);
+	/// ```
+	pub fn map_piecewise(&self, pieces: &[(Range<T>, T)]) -> Self
+	{
+		let mut remaining = self.clone();
+		let mut mapped = Vec::new();
+		for (source, destination) in pieces
+		{
+			let source = Self::from(source.clone());
+			for range in remaining.intersection(&source).iter()
+			{
+				let offset = |value: T| value - source.ranges[0].start + *destination;
+				mapped.push(offset(range.start)..offset(range.end));
+			}
+			remaining = remaining.difference(&source);
+		}
+		remaining.union(&mapped.into_iter().collect())
+	}
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T>
+{
+	fn from(range: Range<T>) -> Self
+	{
+		std::iter::once(range).collect()
+	}
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T>
+{
+	fn from_iter<I: IntoIterator<Item=Range<T>>>(iter: I) -> Self
+	{
+		let mut sorted = iter.into_iter()
+			.filter(|range| range.start < range.end)
+			.collect::<Vec<_>>();
+		sorted.sort_unstable_by_key(|range| range.start);
+
+		let mut ranges: Vec<Range<T>> = Vec::with_capacity(sorted.len());
+		for range in sorted
+		{
+			match ranges.last_mut()
+			{
+				Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+				_ => ranges.push(range),
+			}
+		}
+		Self { ranges, }
+	}
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T>
+{
+	type Item = &'a Range<T>;
+	type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+	fn into_iter(self) -> Self::IntoIter
+	{
+		self.ranges.iter()
+	}
+}
+
+/// A set of points in `N` dimensions kept as disjoint axis-aligned boxes, every box given by one
+/// interval per axis.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::interval::BoxSet;
+/// let mut set = BoxSet::new();
+/// set.insert([0..3, 0..3]);
+/// set.insert([2..4, 2..4]);
+/// assert_eq!(set.volume(), 9 + 4 - 1);
+/// set.remove(&[1..2, 0..10]);
+/// assert_eq!(set.volume(), 12 - 3);
+/// assert!(set.contains(&[3, 3]) && !set.contains(&[1, 1]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BoxSet<T, const N: usize>
+{
+	boxes: Vec<[Range<T>; N]>,
+}
+
+impl<T: Copy + Ord, const N: usize> BoxSet<T, N>
+{
+	pub fn new() -> Self
+	{
+		Self { boxes: Vec::new(), }
+	}
+
+	/// The disjoint boxes making up the set, in no particular order.
+	pub fn boxes(&self) -> &[[Range<T>; N]]
+	{
+		&self.boxes
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.boxes.is_empty()
+	}
+
+	pub fn contains(&self, point: &[T; N]) -> bool
+	{
+		self.boxes.iter().any(|cuboid| cuboid.iter().zip(point.iter()).all(|(range, value)| range.contains(value)))
+	}
+
+	pub fn insert(&mut self, cuboid: [Range<T>; N])
+	{
+		self.remove(&cuboid);
+		if cuboid.iter().all(|range| range.start < range.end)
+		{
+			self.boxes.push(cuboid);
+		}
+	}
+
+	pub fn remove(&mut self, cuboid: &[Range<T>; N])
+	{
+		self.boxes = self.boxes.iter()
+			.flat_map(|other| subtract(other, cuboid))
+			.collect();
+	}
+
+	/// The part of the set inside the given box.
+	pub fn intersection(&self, cuboid: &[Range<T>; N]) -> Self
+	{
+		let boxes = self.boxes.iter()
+			.filter_map(|other|
+			{
+				let mut clipped = other.clone();
+				for (range, clip) in clipped.iter_mut().zip(cuboid.iter())
+				{
+					*range = range.start.max(clip.start)..range.end.min(clip.end);
+				}
+				if clipped.iter().all(|range| range.start < range.end) { Some(clipped) } else { None }
+			})
+			.collect();
+		Self { boxes, }
+	}
+}
+
+impl<T: Copy + Ord + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Default, const N: usize> BoxSet<T, N>
+{
+	/// The number of points in the set.
+	pub fn volume(&self) -> T
+	{
+		self.boxes.iter()
+			.map(|cuboid| cuboid.iter().map(|range| range.end - range.start).reduce(|a, b| a * b).unwrap_or_default())
+			.fold(T::default(), |volume, size| volume + size)
+	}
+}
+
+/// Splits the part of `cuboid` outside of `cut` into disjoint boxes, peeling off one axis after the
+/// other.
+fn subtract<T: Copy + Ord, const N: usize>(cuboid: &[Range<T>; N], cut: &[Range<T>; N]) -> Vec<[Range<T>; N]>
+{
+	if cuboid.iter().zip(cut.iter()).any(|(range, cut)| range.end.min(cut.end) <= range.start.max(cut.start))
+	{
+		return vec![cuboid.clone()];
+	}
+
+	let mut pieces = Vec::new();
+	let mut rest = cuboid.clone();
+	for axis in 0..N
+	{
+		let range = IntervalSet::from(rest[axis].clone());
+		let cut = IntervalSet::from(cut[axis].clone());
+		for outside in range.difference(&cut).iter()
+		{
+			let mut piece = rest.clone();
+			piece[axis] = outside.clone();
+			pieces.push(piece);
+		}
+		rest[axis] = range.intersection(&cut).ranges[0].clone();
+	}
+	pieces
+}
@@ -0,0 +1,186 @@
+//! Exact linear algebra over arbitrary precision rationals.
+//!
+//! Puzzle inputs easily produce coefficients around `10^15`, so even a small system overflows any
+//! fixed width integer during elimination and floating point loses the exact integer answer.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::linalg::Matrix;
+//! # use num::{ BigRational, ToPrimitive };
+//! //  2x +  y -  z =   8
+//! // -3x -  y + 2z = -11
+//! // -2x +  y + 2z =  -3
+//! let matrix = Matrix::from_integers(vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]]).unwrap();
+//! let rhs = [8, -11, -3].iter().map(|&i| BigRational::from_integer(i.into())).collect::<Vec<_>>();
+//! let solution = matrix.solve(&rhs).unwrap();
+//! assert_eq!(solution.iter().map(|r| r.to_i64().unwrap()).collect::<Vec<_>>(), vec![2, 3, -1]);
+//! assert_eq!(matrix.determinant().unwrap(), BigRational::from_integer((-1).into()));
+//! assert_eq!(matrix.rank(), 3);
+//!
+//! // the third row is the sum of the first two
+//! let singular = Matrix::from_integers(vec![vec![1, 2, 3], vec![4, 5, 6], vec![5, 7, 9]]).unwrap();
+//! assert_eq!(singular.rank(), 2);
+//! assert_eq!(singular.solve(&rhs), None);
+//! assert_eq!(singular.determinant().unwrap(), BigRational::from_integer(0.into()));
+//!
+//! // coefficients around 10^15 are solved exactly, whatever the products during elimination
+//! let rows = vec!
+//! [
+//!     vec![1_000_000_000_000_007, 999_999_999_999_989, -3],
+//!     vec![-999_999_999_999_937, 1, 1_000_000_000_000_037],
+//!     vec![7, -1_000_000_000_000_091, 1_000_000_000_000_003],
+//! ];
+//! let expected = [123_456_789, -987_654_321, 555];
+//! let rhs = rows.iter()
+//!     .map(|row| row.iter().zip(expected.iter()).map(|(&a, &x)| BigRational::from_integer((a * x).into())).sum())
+//!     .collect::<Vec<BigRational>>();
+//! let solution = Matrix::from_integers(rows).unwrap().solve(&rhs).unwrap();
+//! assert_eq!(solution, expected.iter().map(|&x| BigRational::from_integer(x.into())).collect::<Vec<_>>());
+//!
+//! // solutions need not be integers
+//! let halves = Matrix::from_integers(vec![vec![1, 1], vec![1, -1]]).unwrap();
+//! let rhs = [1, 0].iter().map(|&i| BigRational::from_integer(i.into())).collect::<Vec<_>>();
+//! assert_eq!(halves.solve(&rhs).unwrap(), vec![BigRational::new(1.into(), 2.into()); 2]);
+//! ```
+
+use crate::error::*;
+
+use num::{ BigRational, One, Zero };
+
+/// A dense matrix of rationals, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix
+{
+	rows: Vec<Vec<BigRational>>,
+	columns: usize,
+}
+
+impl Matrix
+{
+	pub fn new(rows: Vec<Vec<BigRational>>) -> Result<Self>
+	{
+		let columns = rows.first().map(Vec::len).unwrap_or(0);
+		if let Some(row) = rows.iter().find(|row| row.len() != columns)
+		{
+			bail!("matrix rows need {} columns, found a row with {}", columns, row.len());
+		}
+		Ok(Self { rows, columns, })
+	}
+
+	pub fn from_integers<I, R>(rows: I) -> Result<Self>
+		where
+			I: IntoIterator<Item=R>,
+			R: IntoIterator<Item=i128>,
+	{
+		Self::new(rows.into_iter()
+			.map(|row| row.into_iter().map(|i| BigRational::from_integer(i.into())).collect())
+			.collect())
+	}
+
+	pub fn rows(&self) -> usize
+	{
+		self.rows.len()
+	}
+
+	pub fn columns(&self) -> usize
+	{
+		self.columns
+	}
+
+	/// Brings the matrix into reduced row echelon form, returning the pivot columns.
+	fn reduce(&mut self) -> Vec<usize>
+	{
+		let mut pivots = Vec::new();
+		for column in 0..self.columns
+		{
+			let row = pivots.len();
+			let pivot = match (row..self.rows.len()).find(|&r| !self.rows[r][column].is_zero())
+			{
+				Some(pivot) => pivot,
+				None => continue,
+			};
+			self.rows.swap(pivot, row);
+
+			let factor = self.rows[row][column].clone();
+			self.rows[row].iter_mut().for_each(|value| *value /= &factor);
+			for other in 0..self.rows.len()
+			{
+				if other != row && !self.rows[other][column].is_zero()
+				{
+					let factor = self.rows[other][column].clone();
+					let pivot_row = self.rows[row].clone();
+					self.rows[other].iter_mut()
+						.zip(pivot_row.iter())
+						.for_each(|(value, pivot)| *value -= &factor * pivot);
+				}
+			}
+			pivots.push(column);
+		}
+		pivots
+	}
+
+	pub fn rank(&self) -> usize
+	{
+		self.clone().reduce().len()
+	}
+
+	/// The determinant, [`None`] unless the matrix is square.
+	pub fn determinant(&self) -> Option<BigRational>
+	{
+		if self.rows() != self.columns
+		{
+			return None;
+		}
+		// the determinant of the row echelon form is the product of its pivots
+		let mut matrix = self.clone();
+		let mut determinant = BigRational::one();
+		for column in 0..self.columns
+		{
+			let pivot = match (column..self.columns).find(|&r| !matrix.rows[r][column].is_zero())
+			{
+				Some(pivot) => pivot,
+				None => return Some(BigRational::zero()),
+			};
+			if pivot != column
+			{
+				matrix.rows.swap(pivot, column);
+				determinant = -determinant;
+			}
+			let pivot_row = matrix.rows[column].clone();
+			determinant *= &pivot_row[column];
+			for row in (column + 1)..self.columns
+			{
+				let factor = &matrix.rows[row][column] / &pivot_row[column];
+				matrix.rows[row].iter_mut()
+					.zip(pivot_row.iter())
+					.for_each(|(value, pivot)| *value -= &factor * pivot);
+			}
+		}
+		Some(determinant)
+	}
+
+	/// The unique solution `x` of `self * x = rhs`, [`None`] if there is no solution or infinitely
+	/// many.
+	pub fn solve(&self, rhs: &[BigRational]) -> Option<Vec<BigRational>>
+	{
+		if rhs.len() != self.rows()
+		{
+			return None;
+		}
+		let augmented = self.rows.iter()
+			.zip(rhs.iter())
+			.map(|(row, value)| row.iter().cloned().chain(std::iter::once(value.clone())).collect())
+			.collect();
+		let mut augmented = Self { rows: augmented, columns: self.columns + 1, };
+		let pivots = augmented.reduce();
+		if pivots.len() != self.columns || pivots.contains(&self.columns)
+		{
+			return None;
+		}
+		Some(augmented.rows.into_iter()
+			.take(self.columns)
+			.map(|mut row| row.pop().unwrap())
+			.collect())
+	}
+}
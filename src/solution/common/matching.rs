@@ -0,0 +1,164 @@
+//! Matching both sides of a bipartite graph, like puzzle fields to the columns they may occupy.
+//!
+//! Candidates map every left node to the right nodes it may be matched with.
+//! A matching pairs left and right nodes along these candidates so that no node is used twice.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::matching::{ maximum_matching, unique_matching };
+//! # use std::collections::{ BTreeMap, BTreeSet };
+//! let candidates = vec!
+//! [
+//!     ("row", vec![0, 1, 2]),
+//!     ("class", vec![1, 2]),
+//!     ("seat", vec![2]),
+//! ].into_iter()
+//!     .map(|(field, columns)| (field, columns.into_iter().collect::<BTreeSet<_>>()))
+//!     .collect::<BTreeMap<_, _>>();
+//! let matching = unique_matching(&candidates).unwrap();
+//! assert_eq!(matching, vec![("class", 1), ("row", 0), ("seat", 2)].into_iter().collect());
+//!
+//! // two fields fitting the same two columns either way round
+//! let mut ambiguous = candidates.clone();
+//! ambiguous.get_mut("row").unwrap().remove(&0);
+//! ambiguous.get_mut("row").unwrap().insert(3);
+//! ambiguous.get_mut("class").unwrap().insert(3);
+//! assert_eq!(maximum_matching(&ambiguous).len(), 3);
+//! assert!(unique_matching(&ambiguous).is_err());
+//!
+//! // a column nobody fits leaves a field without a match
+//! let mut impossible = candidates.clone();
+//! impossible.get_mut("row").unwrap().remove(&0);
+//! assert_eq!(maximum_matching(&impossible).len(), 2);
+//! assert!(unique_matching(&impossible).is_err());
+//!
+//! // an augmenting path moves an earlier match aside
+//! let chain = vec![('a', vec![0, 1]), ('b', vec![0]), ('c', vec![1, 2])].into_iter()
+//!     .map(|(field, columns)| (field, columns.into_iter().collect::<BTreeSet<_>>()))
+//!     .collect::<BTreeMap<_, _>>();
+//! assert_eq!(unique_matching(&chain).unwrap(), vec![('a', 1), ('b', 0), ('c', 2)].into_iter().collect());
+//!
+//! // a cycle of candidates can be matched in both directions
+//! let cycle = vec![('a', vec![0, 1]), ('b', vec![1, 2]), ('c', vec![2, 0])].into_iter()
+//!     .map(|(field, columns)| (field, columns.into_iter().collect::<BTreeSet<_>>()))
+//!     .collect::<BTreeMap<_, _>>();
+//! assert_eq!(maximum_matching(&cycle).len(), 3);
+//! assert!(unique_matching(&cycle).is_err());
+//!
+//! // three fields fighting over two columns
+//! let crowded = vec![('a', vec![0, 1]), ('b', vec![0, 1]), ('c', vec![0, 1])].into_iter()
+//!     .map(|(field, columns)| (field, columns.into_iter().collect::<BTreeSet<_>>()))
+//!     .collect::<BTreeMap<_, _>>();
+//! assert_eq!(maximum_matching(&crowded).len(), 2);
+//! assert!(unique_matching(&crowded).is_err());
+//! ```
+
+use crate::error::*;
+
+use std::collections::{ BTreeMap, BTreeSet };
+use std::fmt::Debug;
+
+/// The candidates as indices into sorted lists of both sides.
+struct Graph<'a, L, R>
+{
+	left: Vec<&'a L>,
+	right: Vec<&'a R>,
+	adjacency: Vec<Vec<usize>>,
+}
+
+impl<'a, L: Ord, R: Ord> Graph<'a, L, R>
+{
+	fn new(candidates: &'a BTreeMap<L, BTreeSet<R>>) -> Self
+	{
+		let right = candidates.values().flatten().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>();
+		let adjacency = candidates.values()
+			.map(|set| set.iter().map(|r| right.binary_search(&r).unwrap()).collect())
+			.collect();
+		Self { left: candidates.keys().collect(), right, adjacency, }
+	}
+
+	/// Finds an alternating path from the left node to a free right node and flips it, never
+	/// using the forbidden edge.
+	fn augment(&self, left: usize, matched: &mut [Option<usize>], visited: &mut [bool], forbidden: Option<(usize, usize)>) -> bool
+	{
+		for &right in self.adjacency[left].iter()
+		{
+			if visited[right] || forbidden == Some((left, right))
+			{
+				continue;
+			}
+			visited[right] = true;
+			if matched[right].map(|other| self.augment(other, matched, visited, forbidden)).unwrap_or(true)
+			{
+				matched[right] = Some(left);
+				return true;
+			}
+		}
+		false
+	}
+
+	/// A maximum matching by augmenting paths, as the left node matched to each right node.
+	fn matching(&self) -> Vec<Option<usize>>
+	{
+		let mut matched = vec![None; self.right.len()];
+		for left in 0..self.left.len()
+		{
+			self.augment(left, &mut matched, &mut vec![false; self.right.len()], None);
+		}
+		matched
+	}
+
+	fn pairs(&self, matched: &[Option<usize>]) -> BTreeMap<L, R>
+		where
+			L: Clone,
+			R: Clone,
+	{
+		matched.iter()
+			.enumerate()
+			.filter_map(|(right, left)| left.map(|left| (self.left[left].clone(), self.right[right].clone())))
+			.collect()
+	}
+}
+
+/// A largest possible matching, which is not necessarily the only one.
+pub fn maximum_matching<L, R>(candidates: &BTreeMap<L, BTreeSet<R>>) -> BTreeMap<L, R>
+	where
+		L: Clone + Ord,
+		R: Clone + Ord,
+{
+	let graph = Graph::new(candidates);
+	graph.pairs(&graph.matching())
+}
+
+/// The only matching using every left node, failing if there is none or more than one.
+///
+/// A perfect matching is unique iff no matched edge can be avoided, so every edge is forbidden once
+/// while trying to rematch its left node.
+pub fn unique_matching<L, R>(candidates: &BTreeMap<L, BTreeSet<R>>) -> Result<BTreeMap<L, R>>
+	where
+		L: Clone + Ord + Debug,
+		R: Clone + Ord + Debug,
+{
+	let graph = Graph::new(candidates);
+	let matched = graph.matching();
+	let pairs = matched.iter().enumerate().filter_map(|(right, left)| left.map(|left| (left, right))).collect::<Vec<_>>();
+	if pairs.len() < graph.left.len()
+	{
+		let unmatched = (0..graph.left.len()).find(|left| pairs.iter().all(|(l, _)| l != left)).unwrap();
+		bail!("no matching for all nodes, {:?} is left over", graph.left[unmatched]);
+	}
+
+	for &(left, right) in pairs.iter()
+	{
+		let mut alternative = matched.clone();
+		alternative[right] = None;
+		if graph.augment(left, &mut alternative, &mut vec![false; graph.right.len()], Some((left, right)))
+		{
+			let other = alternative.iter().position(|&l| l == Some(left)).unwrap();
+			bail!("ambiguous matching, {:?} fits both {:?} and {:?}", graph.left[left], graph.right[right], graph.right[other]);
+		}
+	}
+
+	Ok(graph.pairs(&matched))
+}
@@ -0,0 +1,13 @@
+//! Building blocks shared by solutions of different days and years.
+
+pub mod automaton;
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod linalg;
+pub mod matching;
+pub mod number;
+pub mod polygon;
+pub mod search;
@@ -0,0 +1,220 @@
+//! Number theory helpers: greatest common divisors, modular arithmetic and congruences.
+//!
+//! Congruences and cycles use `i128` so residues may be negative and products of two 64-bit moduli
+//! do not overflow, modular arithmetic works on `u64` with `u128` intermediates.
+
+use std::collections::HashMap;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::gcd;
+/// assert_eq!(gcd(12, 18), 6);
+/// assert_eq!(gcd(-12, 18), 6);
+/// assert_eq!(gcd(0, 7), 7);
+/// ```
+pub fn gcd(a: i128, b: i128) -> i128
+{
+	extended_gcd(a, b).0
+}
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::lcm;
+/// assert_eq!(lcm(4, 6), 12);
+/// assert_eq!(lcm(0, 6), 0);
+/// ```
+pub fn lcm(a: i128, b: i128) -> i128
+{
+	if a == 0 || b == 0
+	{
+		0
+	}
+	else
+	{
+		(a / gcd(a, b) * b).abs()
+	}
+}
+
+/// Returns `(g, x, y)` with `g` the non-negative greatest common divisor and `a * x + b * y = g`.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::extended_gcd;
+/// let (g, x, y) = extended_gcd(240, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128)
+{
+	let (mut old_r, mut r) = (a, b);
+	let (mut old_s, mut s) = (1, 0);
+	let (mut old_t, mut t) = (0, 1);
+	while r != 0
+	{
+		let quotient = old_r / r;
+		(old_r, r) = (r, old_r - quotient * r);
+		(old_s, s) = (s, old_s - quotient * s);
+		(old_t, t) = (t, old_t - quotient * t);
+	}
+	if old_r < 0
+	{
+		(-old_r, -old_s, -old_t)
+	}
+	else
+	{
+		(old_r, old_s, old_t)
+	}
+}
+
+fn mod_mul(a: u64, b: u64, modulus: u64) -> u64
+{
+	(a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Computes `base.pow(exp) % modulus` by repeated squaring.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::mod_pow;
+/// assert_eq!(mod_pow(4, 13, 497), 445);
+/// assert_eq!(mod_pow(7, 0, 13), 1);
+/// assert_eq!(mod_pow(3, 5, 1), 0);
+/// ```
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64
+{
+	let mut base = base % modulus;
+	let mut result = 1 % modulus;
+	while exp > 0
+	{
+		if exp & 1 == 1
+		{
+			result = mod_mul(result, base, modulus);
+		}
+		base = mod_mul(base, base, modulus);
+		exp >>= 1;
+	}
+	result
+}
+
+/// The multiplicative inverse, [`None`] unless `a` and `modulus` are coprime.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::mod_inverse;
+/// assert_eq!(mod_inverse(3, 11), Some(4));
+/// assert_eq!(mod_inverse(6, 9), None);
+/// ```
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64>
+{
+	let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+	if g != 1
+	{
+		return None;
+	}
+	Some(x.rem_euclid(modulus as i128) as u64)
+}
+
+/// Smallest `x` with `base.pow(x) % modulus == target % modulus` using baby-step giant-step.
+///
+/// Requires `base` and `modulus` to be coprime, otherwise [`None`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::{ discrete_log, mod_pow };
+/// assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+/// assert_eq!(discrete_log(2, 1, 11), Some(0));
+/// assert_eq!(discrete_log(3, mod_pow(3, 1234, 1000003), 1000003), Some(1234));
+/// // 2 only generates the quadratic residues modulo 7
+/// assert_eq!(discrete_log(2, 3, 7), None);
+/// ```
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64>
+{
+	if modulus == 1
+	{
+		return Some(0);
+	}
+	let steps = (modulus as f64).sqrt().ceil() as u64;
+
+	let mut baby = HashMap::new();
+	let mut value = 1;
+	for j in 0..steps
+	{
+		baby.entry(value).or_insert(j);
+		value = mod_mul(value, base, modulus);
+	}
+
+	let giant = mod_inverse(mod_pow(base, steps, modulus), modulus)?;
+	let mut value = target % modulus;
+	for i in 0..steps
+	{
+		if let Some(j) = baby.get(&value)
+		{
+			return Some(i * steps + j);
+		}
+		value = mod_mul(value, giant, modulus);
+	}
+	None
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` using the chinese remainder theorem.
+///
+/// The moduli need not be coprime.
+/// Returns the smallest non-negative solution together with the combined modulus (the least common
+/// multiple of all moduli), or [`None`] if the congruences contradict each other.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(-1, 3), (1, 4)]), Some((5, 12)));
+/// // non-coprime moduli
+/// assert_eq!(crt([(3, 6), (5, 8)]), Some((21, 24)));
+/// assert_eq!(crt([(1, 6), (2, 4)]), None);
+/// assert_eq!(crt(Vec::new()), Some((0, 1)));
+/// ```
+pub fn crt<I: IntoIterator<Item=(i128, i128)>>(congruences: I) -> Option<(i128, i128)>
+{
+	congruences.into_iter()
+		.try_fold((0, 1), |(r1, m1), (r2, m2)|
+		{
+			let r2 = r2.rem_euclid(m2);
+			let (g, p, _) = extended_gcd(m1, m2);
+			if (r2 - r1) % g != 0
+			{
+				return None;
+			}
+			let step = m2 / g;
+			let k = ((r2 - r1) / g).rem_euclid(step) * p.rem_euclid(step) % step;
+			let modulus = m1 * step;
+			Some(((r1 + m1 * k).rem_euclid(modulus), modulus))
+		})
+}
+
+/// The first time all cycles coincide, each cycle hitting at `offset + k * period` for all `k >= 0`.
+///
+/// With all offsets zero (or equal to their period) this is just the least common multiple.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::common::number::first_coincidence;
+/// assert_eq!(first_coincidence([(4, 4), (6, 6)]), Some(12));
+/// assert_eq!(first_coincidence([(2, 2), (3, 3)]), Some(6));
+/// // the second cycle only starts at 10
+/// assert_eq!(first_coincidence([(1, 3), (10, 5)]), Some(10));
+/// assert_eq!(first_coincidence([(0, 2), (1, 4)]), None);
+/// ```
+pub fn first_coincidence<I: IntoIterator<Item=(i128, i128)>>(cycles: I) -> Option<i128>
+{
+	let cycles = cycles.into_iter().collect::<Vec<_>>();
+	let (residue, modulus) = crt(cycles.iter().copied())?;
+	let earliest = cycles.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+	Some(earliest + (residue - earliest).rem_euclid(modulus))
+}
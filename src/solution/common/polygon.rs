@@ -0,0 +1,145 @@
+//! Simple polygons with integer vertices.
+//!
+//! The area follows from the shoelace formula, the number of lattice points on the boundary from the
+//! greatest common divisor of each edge, and Pick's theorem (`A = I + B / 2 - 1`) links both to the
+//! number of lattice points strictly inside, so none of them depend on the size of the polygon.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::polygon::Polygon;
+//! let triangle = Polygon::new(vec![(0, 0), (4, 0), (0, 3)]);
+//! assert_eq!(triangle.double_area(), 12);
+//! assert_eq!(triangle.boundary_points(), 8);
+//! assert_eq!(triangle.interior_points(), 3);
+//!
+//! // walking a square with side length 2 starting at the origin
+//! let square = Polygon::from_steps(vec![((1, 0), 2), ((0, 1), 2), ((-1, 0), 2), ((0, -1), 2)]);
+//! assert_eq!(square.vertices(), &[(0, 0), (2, 0), (2, 2), (0, 2)]);
+//! assert_eq!(square.lattice_points(), 9);
+//! assert!(square.contains((1, 1)) && square.contains((2, 1)) && !square.contains((3, 1)));
+//!
+//! // Pick's theorem agrees with testing every single point, also for concave shapes with collinear
+//! // and diagonal edges in either orientation
+//! let shapes = vec!
+//! [
+//!     vec![(0, 0), (4, 0), (0, 3)],
+//!     vec![(0, 0), (6, 0), (6, 2), (2, 2), (2, 5), (0, 5)],
+//!     vec![(0, 0), (0, 7), (3, 4), (6, 7), (8, 1), (5, 3), (4, 0), (2, 0)],
+//!     vec![(1, 1), (9, 3), (4, 4), (5, 9), (3, 5), (1, 8), (2, 3)],
+//! ];
+//! for vertices in shapes
+//! {
+//!     let polygon = Polygon::new(vertices);
+//!     let points = (-1..11).flat_map(|x| (-1..11).map(move |y| (x, y))).collect::<Vec<_>>();
+//!     let boundary = points.iter().filter(|&&p| polygon.on_boundary(p)).count();
+//!     let inside = points.iter().filter(|&&p| polygon.contains(p)).count();
+//!     assert_eq!(polygon.boundary_points(), boundary as i64);
+//!     assert_eq!(polygon.interior_points(), (inside - boundary) as i64);
+//! }
+//! ```
+
+use super::number::gcd;
+
+pub type Point = (i64, i64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon
+{
+	vertices: Vec<Point>,
+}
+
+impl Polygon
+{
+	/// The polygon through the vertices in order, closed by an edge from the last back to the first.
+	pub fn new(vertices: Vec<Point>) -> Self
+	{
+		Self { vertices, }
+	}
+
+	/// Traces the polygon starting at the origin, every step walking a number of times along a
+	/// vector.
+	pub fn from_steps<I: IntoIterator<Item=((isize, isize), i64)>>(steps: I) -> Self
+	{
+		let mut vertices = vec![(0, 0)];
+		for ((dx, dy), count) in steps
+		{
+			let (x, y) = *vertices.last().unwrap();
+			vertices.push((x + dx as i64 * count, y + dy as i64 * count));
+		}
+		if vertices.len() > 1 && vertices.first() == vertices.last()
+		{
+			vertices.pop();
+		}
+		Self { vertices, }
+	}
+
+	pub fn vertices(&self) -> &[Point]
+	{
+		&self.vertices
+	}
+
+	fn edges(&self) -> impl Iterator<Item=(Point, Point)> + '_
+	{
+		self.vertices.iter()
+			.copied()
+			.zip(self.vertices.iter().copied().cycle().skip(1))
+	}
+
+	/// Twice the enclosed area, which is always an integer.
+	pub fn double_area(&self) -> i64
+	{
+		self.edges()
+			.map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+			.sum::<i64>()
+			.abs()
+	}
+
+	/// The number of lattice points on the edges.
+	pub fn boundary_points(&self) -> i64
+	{
+		self.edges()
+			.map(|((x1, y1), (x2, y2))| gcd((x2 - x1) as i128, (y2 - y1) as i128) as i64)
+			.sum()
+	}
+
+	/// The number of lattice points strictly inside.
+	pub fn interior_points(&self) -> i64
+	{
+		(self.double_area() - self.boundary_points() + 2) / 2
+	}
+
+	/// The number of lattice points inside or on the boundary.
+	pub fn lattice_points(&self) -> i64
+	{
+		self.interior_points() + self.boundary_points()
+	}
+
+	pub fn on_boundary(&self, (px, py): Point) -> bool
+	{
+		self.edges()
+			.any(|((x1, y1), (x2, y2))|
+			{
+				(x2 - x1) * (py - y1) == (px - x1) * (y2 - y1)
+					&& x1.min(x2) <= px && px <= x1.max(x2)
+					&& y1.min(y2) <= py && py <= y1.max(y2)
+			})
+	}
+
+	/// Whether the point lies inside or on the boundary, by casting a ray towards positive x.
+	pub fn contains(&self, point: Point) -> bool
+	{
+		if self.on_boundary(point)
+		{
+			return true;
+		}
+		let (px, py) = point;
+		self.edges()
+			.filter(|&((x1, y1), (x2, y2))|
+			{
+				// the edge crosses the line of the ray right of the point
+				(y1 > py) != (y2 > py) && ((x2 - x1) * (py - y1) - (px - x1) * (y2 - y1) > 0) == (y2 > y1)
+			})
+			.count() % 2 == 1
+	}
+}
@@ -0,0 +1,280 @@
+//! Shortest path searches over arbitrary state types.
+//!
+//! All searches take the start states, a function yielding the neighbours of a state (with the cost
+//! of the step for the weighted searches) and, unless the whole graph is explored, a predicate
+//! recognising the goal.
+//! Every state remembers the state it was reached from, so the path to any state can be
+//! reconstructed afterwards, which comes in handy when debugging a solution.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::common::search::{ astar, bfs, dijkstra, dijkstra_all };
+//! // walking the number line, either one step to the right for 2 or doubling for 3
+//! let neighbours = |&n: &usize| [(n + 1, 2), (n * 2, 3)];
+//!
+//! let path = dijkstra([1], neighbours, |&n| n == 10).unwrap();
+//! assert_eq!(path.cost, 10);
+//! assert_eq!(path.states, vec![1, 2, 4, 5, 10]);
+//!
+//! // any state but the goal is at least one step away
+//! let heuristic = |&n: &usize| if n == 10 { 0 } else { 2 };
+//! assert_eq!(astar([1], neighbours, heuristic, |&n| n == 10).unwrap().cost, 10);
+//!
+//! let path = bfs([1], |&n: &usize| [n + 1, n * 2], |&n| n == 10).unwrap();
+//! assert_eq!(path.cost, 4);
+//!
+//! let explored = dijkstra_all([1], |&n: &usize| neighbours(&n).iter().copied().filter(|&(n, _)| n <= 10).collect::<Vec<_>>());
+//! assert_eq!(explored.cost(&7), Some(9));
+//! assert_eq!(explored.path_to(&7).unwrap().states, vec![1, 2, 3, 6, 7]);
+//! assert_eq!(explored.len(), 10);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{ BinaryHeap, HashMap, VecDeque };
+use std::hash::Hash;
+
+/// A path found by one of the searches, including both the start and the end state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S>
+{
+	pub cost: usize,
+	pub states: Vec<S>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<S>
+{
+	state: S,
+	cost: usize,
+	parent: Option<usize>,
+	settled: bool,
+}
+
+/// All states reached by a search together with their cheapest cost.
+#[derive(Debug, Clone)]
+pub struct Explored<S>
+{
+	nodes: Vec<Node<S>>,
+	index: HashMap<S, usize>,
+	goal: Option<usize>,
+}
+
+impl<S: Clone + Eq + Hash> Explored<S>
+{
+	fn new() -> Self
+	{
+		Self { nodes: Vec::new(), index: HashMap::new(), goal: None, }
+	}
+
+	fn insert(&mut self, state: S, cost: usize, parent: Option<usize>) -> usize
+	{
+		let idx = self.nodes.len();
+		self.index.insert(state.clone(), idx);
+		self.nodes.push(Node { state, cost, parent, settled: false, });
+		idx
+	}
+
+	fn reconstruct(&self, idx: usize) -> Path<S>
+	{
+		let mut states = std::iter::successors(Some(idx), |&idx| self.nodes[idx].parent)
+			.map(|idx| self.nodes[idx].state.clone())
+			.collect::<Vec<_>>();
+		states.reverse();
+		Path { cost: self.nodes[idx].cost, states, }
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.nodes.len()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.nodes.is_empty()
+	}
+
+	/// The cheapest cost of reaching the state.
+	///
+	/// Unless the search ran to completion, this is only an upper bound for states which were
+	/// discovered but never expanded.
+	pub fn cost(&self, state: &S) -> Option<usize>
+	{
+		self.index.get(state).map(|&idx| self.nodes[idx].cost)
+	}
+
+	/// Reconstructs the path from one of the start states to the given state.
+	pub fn path_to(&self, state: &S) -> Option<Path<S>>
+	{
+		self.index.get(state).map(|&idx| self.reconstruct(idx))
+	}
+
+	/// The path to the goal, if the search was stopped by reaching one.
+	pub fn goal(&self) -> Option<Path<S>>
+	{
+		self.goal.map(|idx| self.reconstruct(idx))
+	}
+
+	/// All reached states with their cost, in order of discovery.
+	pub fn iter(&self) -> impl Iterator<Item=(&S, usize)>
+	{
+		self.nodes.iter().map(|node| (&node.state, node.cost))
+	}
+}
+
+fn breadth_first<S, I, N, IN, G>(starts: I, mut neighbours: N, mut goal: G) -> Explored<S>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=S>,
+		G: FnMut(&S) -> bool,
+{
+	let mut explored = Explored::new();
+	let mut queue = VecDeque::new();
+	for start in starts
+	{
+		if !explored.index.contains_key(&start)
+		{
+			queue.push_back(explored.insert(start, 0, None));
+		}
+	}
+
+	while let Some(idx) = queue.pop_front()
+	{
+		let node = &explored.nodes[idx];
+		let (state, cost) = (node.state.clone(), node.cost);
+		if goal(&state)
+		{
+			explored.goal = Some(idx);
+			break;
+		}
+		for next in neighbours(&state)
+		{
+			if !explored.index.contains_key(&next)
+			{
+				queue.push_back(explored.insert(next, cost + 1, Some(idx)));
+			}
+		}
+	}
+
+	explored
+}
+
+fn best_first<S, I, N, IN, H, G>(starts: I, mut neighbours: N, mut heuristic: H, mut goal: G) -> Explored<S>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=(S, usize)>,
+		H: FnMut(&S) -> usize,
+		G: FnMut(&S) -> bool,
+{
+	let mut explored = Explored::new();
+	let mut queue = BinaryHeap::new();
+	for start in starts
+	{
+		if !explored.index.contains_key(&start)
+		{
+			let estimate = heuristic(&start);
+			queue.push(Reverse((estimate, 0, explored.insert(start, 0, None))));
+		}
+	}
+
+	while let Some(Reverse((_, cost, idx))) = queue.pop()
+	{
+		let node = &mut explored.nodes[idx];
+		if node.settled || cost > node.cost
+		{
+			continue;
+		}
+		node.settled = true;
+		let state = node.state.clone();
+		if goal(&state)
+		{
+			explored.goal = Some(idx);
+			break;
+		}
+		for (next, step) in neighbours(&state)
+		{
+			let cost = cost + step;
+			let idx = match explored.index.get(&next)
+			{
+				Some(&next) =>
+				{
+					let node = &mut explored.nodes[next];
+					if node.settled || node.cost <= cost
+					{
+						continue;
+					}
+					node.cost = cost;
+					node.parent = Some(idx);
+					next
+				},
+				None => explored.insert(next.clone(), cost, Some(idx)),
+			};
+			queue.push(Reverse((cost + heuristic(&next), cost, idx)));
+		}
+	}
+
+	explored
+}
+
+/// Breadth-first search where every step costs 1.
+pub fn bfs<S, I, N, IN, G>(starts: I, neighbours: N, goal: G) -> Option<Path<S>>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=S>,
+		G: FnMut(&S) -> bool,
+{
+	breadth_first(starts, neighbours, goal).goal()
+}
+
+/// Breadth-first search reaching every state reachable from the start states.
+pub fn bfs_all<S, I, N, IN>(starts: I, neighbours: N) -> Explored<S>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=S>,
+{
+	breadth_first(starts, neighbours, |_| false)
+}
+
+/// Dijkstra's algorithm, the neighbours come with the cost of the step.
+pub fn dijkstra<S, I, N, IN, G>(starts: I, neighbours: N, goal: G) -> Option<Path<S>>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=(S, usize)>,
+		G: FnMut(&S) -> bool,
+{
+	best_first(starts, neighbours, |_| 0, goal).goal()
+}
+
+/// Dijkstra's algorithm reaching every state reachable from the start states.
+pub fn dijkstra_all<S, I, N, IN>(starts: I, neighbours: N) -> Explored<S>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=(S, usize)>,
+{
+	best_first(starts, neighbours, |_| 0, |_| false)
+}
+
+/// A* search, the heuristic must never overestimate the remaining cost and must be consistent.
+pub fn astar<S, I, N, IN, H, G>(starts: I, neighbours: N, heuristic: H, goal: G) -> Option<Path<S>>
+	where
+		S: Clone + Eq + Hash,
+		I: IntoIterator<Item=S>,
+		N: FnMut(&S) -> IN,
+		IN: IntoIterator<Item=(S, usize)>,
+		H: FnMut(&S) -> usize,
+		G: FnMut(&S) -> bool,
+{
+	best_first(starts, neighbours, heuristic, goal).goal()
+}
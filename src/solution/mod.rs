@@ -1,11 +1,17 @@
-#![allow(clippy::match_like_matches_macro,clippy::collapsible_else_if,clippy::type_complexity)]
+#![allow(clippy::match_like_matches_macro,clippy::collapsible_else_if,clippy::type_complexity,clippy::manual_is_multiple_of,clippy::manual_div_ceil,clippy::manual_repeat_n,clippy::manual_inspect)]
 
+pub mod common;
+
+#[cfg(feature = "y2019")]
+pub mod y2019;
 #[cfg(feature = "y2020")]
 pub mod y2020;
 #[cfg(feature = "y2021")]
 pub mod y2021;
 #[cfg(feature = "y2023")]
 pub mod y2023;
+#[cfg(feature = "y2024")]
+pub mod y2024;
 
 use crate::error::*;
 
@@ -23,9 +29,14 @@ pub trait InputParser<'a>
 	{
 		Some(reqwest::Url::parse(&format!("https://adventofcode.com/{}/day/{}/input", self.year(), self.day())).expect("compile time url invalid"))
 	}
+	/// Additional arguments taken by the solution on top of the input.
+	fn args(&self) -> Vec<clap::Arg>
+	{
+		Vec::new()
+	}
 	fn usage(&self) -> clap::Command
 	{
-		let subcommand = clap::Command::new(self.name());
+		let subcommand = clap::Command::new(self.name()).args(self.args());
 		if self.input_url().is_some()
 		{
 			subcommand
@@ -78,6 +89,18 @@ impl<'a> InputParser<'a> for (usize, usize, usize, Box<dyn Fn(Option<String>) ->
 	}
 }
 
+impl<'a> InputParser<'a> for (usize, usize, usize, Vec<clap::Arg>, Box<dyn Fn(Option<String>, &clap::ArgMatches) -> Box<dyn Solution>>)
+{
+	fn year(&self) -> usize { self.0 }
+	fn day(&self) -> usize { self.1 }
+	fn part(&self) -> usize { self.2 }
+	fn args(&self) -> Vec<clap::Arg> { self.3.clone() }
+	fn parse(&self, input: Option<String>, matches: &clap::ArgMatches) -> Box<dyn Solution>
+	{
+		(self.4)(input, matches)
+	}
+}
+
 pub trait Solution
 {
 	fn solve(&self) -> Result<String>;
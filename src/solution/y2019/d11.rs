@@ -0,0 +1,129 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+/// # Examples
+///
+/// The program below ignores the camera and replays the moves from the puzzle description.
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d11::Solution, Solution as S };
+/// # env_logger::init();
+/// let moves = [(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)];
+/// let input = moves.iter()
+///     .map(|(color, turn)| format!("3,1000,104,{},104,{},", color, turn))
+///     .chain(std::iter::once("99".to_string()))
+///     .collect::<String>();
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "6");
+/// let output = "  #|\n  #|\n## |\n";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), output);
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+fn paint(mut machine: Machine, start: bool) -> Result<std::collections::BTreeMap<(isize, isize), bool>>
+{
+	let mut panels = std::collections::BTreeMap::new();
+	if start
+	{
+		panels.insert((0, 0), true);
+	}
+	let mut position = (0isize, 0isize);
+	let mut direction = (0isize, -1isize);
+
+	loop
+	{
+		let current = panels.get(&position).copied().unwrap_or(false);
+		machine.push_input(current as i64);
+		let status = machine.run()?;
+		let output = machine.drain_output();
+		for chunk in output.chunks(2)
+		{
+			let (color, turn) = match chunk
+			{
+				&[color, turn] => (color, turn),
+				_ => bail!("robot emitted incomplete instruction {:?}", chunk),
+			};
+			panels.insert(position, color == 1);
+			direction = match turn
+			{
+				0 => (direction.1, -direction.0),
+				1 => (-direction.1, direction.0),
+				turn => bail!("robot cannot turn {}", turn),
+			};
+			position = (position.0 + direction.0, position.1 + direction.1);
+			trace!("painted {}, now at {:?} facing {:?}", color, position, direction);
+		}
+		if status == Status::Halted
+		{
+			return Ok(panels);
+		}
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let machine: Machine = self.input.parse()?;
+
+		match self.part
+		{
+			Part::One =>
+			{
+				let panels = paint(machine, false)?;
+				Ok(format!("{}", panels.len()))
+			},
+			Part::Two =>
+			{
+				let panels = paint(machine, true)?;
+				let white = panels.into_iter()
+					.filter(|&(_, color)| color)
+					.map(|(position, _)| position)
+					.collect::<std::collections::BTreeSet<_>>();
+
+				let x_min = *white.iter().map(|(x, _)| x).min().ok_or(Error::AocNoSolution)?;
+				let x_max = *white.iter().map(|(x, _)| x).max().ok_or(Error::AocNoSolution)?;
+				let y_min = *white.iter().map(|(_, y)| y).min().ok_or(Error::AocNoSolution)?;
+				let y_max = *white.iter().map(|(_, y)| y).max().ok_or(Error::AocNoSolution)?;
+				let white = &white;
+
+				let output = (y_min..=y_max)
+					.flat_map(move |y|
+					{
+						(x_min..=x_max)
+							.map(move |x| if white.contains(&(x, y)) { '#' } else { ' ' })
+							.chain("|\n".chars())
+					})
+					.collect::<String>();
+
+				Ok(output)
+			},
+		}
+	}
+}
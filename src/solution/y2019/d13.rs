@@ -0,0 +1,151 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+use std::convert::TryFrom;
+
+/// # Examples
+///
+/// The program below just draws a fixed screen.
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d13::Solution, Solution as S };
+/// # env_logger::init();
+/// let tiles = [(1, 2, 3), (6, 5, 4), (2, 2, 2), (3, 2, 2), (3, 2, 0)];
+/// let input = tiles.iter()
+///     .map(|(x, y, id)| format!("104,{},104,{},104,{},", x, y, id))
+///     .chain(std::iter::once("99".to_string()))
+///     .collect::<String>();
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "1");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile
+{
+	Empty,
+	Wall,
+	Block,
+	Paddle,
+	Ball,
+}
+
+impl TryFrom<i64> for Tile
+{
+	type Error = Error;
+	fn try_from(id: i64) -> std::result::Result<Self, Error>
+	{
+		Ok(match id
+		{
+			0 => Tile::Empty,
+			1 => Tile::Wall,
+			2 => Tile::Block,
+			3 => Tile::Paddle,
+			4 => Tile::Ball,
+			id => Err(anyhow!("unknown tile id {}", id))?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+struct Screen
+{
+	tiles: std::collections::BTreeMap<(i64, i64), Tile>,
+	score: i64,
+}
+
+impl Screen
+{
+	fn update(&mut self, output: Vec<i64>) -> Result<()>
+	{
+		for chunk in output.chunks(3)
+		{
+			match *chunk
+			{
+				[-1, 0, score] => self.score = score,
+				[x, y, id] =>
+				{
+					self.tiles.insert((x, y), Tile::try_from(id)?);
+				},
+				_ => bail!("incomplete draw instruction {:?}", chunk),
+			}
+		}
+		Ok(())
+	}
+
+	fn find(&self, tile: Tile) -> Option<(i64, i64)>
+	{
+		self.tiles.iter()
+			.find(|&(_, &t)| t == tile)
+			.map(|(&position, _)| position)
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+		let mut screen = Screen::default();
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				machine.run()?;
+				screen.update(machine.drain_output())?;
+				screen.tiles.values().filter(|&&tile| tile == Tile::Block).count() as i64
+			},
+			Part::Two =>
+			{
+				machine.write(0, 2)?;
+				loop
+				{
+					let status = machine.run()?;
+					screen.update(machine.drain_output())?;
+					if status == Status::Halted
+					{
+						break;
+					}
+					let ball = screen.find(Tile::Ball).ok_or_else(|| anyhow!("no ball on screen"))?;
+					let paddle = screen.find(Tile::Paddle).ok_or_else(|| anyhow!("no paddle on screen"))?;
+					trace!("ball at {:?}, paddle at {:?}, score {}", ball, paddle, screen.score);
+					machine.push_input((ball.0 - paddle.0).signum());
+				}
+				if screen.tiles.values().any(|&tile| tile == Tile::Block)
+				{
+					bail!("game over with blocks remaining");
+				}
+				screen.score
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
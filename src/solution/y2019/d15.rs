@@ -0,0 +1,119 @@
+use crate::error::*;
+
+use super::intcode::Machine;
+
+/// Explores the whole section with copies of the droid, then measures distances on the map.
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+type Position = (i64, i64);
+
+const MOVES: [(i64, Position); 4] = [(1, (0, -1)), (2, (0, 1)), (3, (-1, 0)), (4, (1, 0))];
+
+/// Returns all open positions and the position of the oxygen system.
+fn explore(machine: Machine) -> Result<(std::collections::BTreeSet<Position>, Position)>
+{
+	let mut open = std::collections::BTreeSet::new();
+	let mut oxygen = None;
+	let mut queue = std::collections::VecDeque::new();
+	open.insert((0, 0));
+	queue.push_back(((0, 0), machine));
+
+	while let Some((position, machine)) = queue.pop_front()
+	{
+		for &(command, (dx, dy)) in MOVES.iter()
+		{
+			let next = (position.0 + dx, position.1 + dy);
+			if open.contains(&next)
+			{
+				continue;
+			}
+			let mut machine = machine.clone();
+			machine.push_input(command);
+			machine.run()?;
+			match machine.drain_output().as_slice()
+			{
+				[0] => {},
+				[status @ (1 | 2)] =>
+				{
+					if *status == 2
+					{
+						oxygen = Some(next);
+					}
+					open.insert(next);
+					queue.push_back((next, machine));
+				},
+				output => bail!("droid reported unexpected status {:?}", output),
+			}
+		}
+	}
+
+	Ok((open, oxygen.ok_or_else(|| anyhow!("no oxygen system found"))?))
+}
+
+fn distances(open: &std::collections::BTreeSet<Position>, start: Position) -> std::collections::BTreeMap<Position, usize>
+{
+	let mut distances = std::collections::BTreeMap::new();
+	let mut queue = std::collections::VecDeque::new();
+	distances.insert(start, 0);
+	queue.push_back(start);
+
+	while let Some(position) = queue.pop_front()
+	{
+		let distance = distances[&position];
+		for &(_, (dx, dy)) in MOVES.iter()
+		{
+			let next = (position.0 + dx, position.1 + dy);
+			if open.contains(&next) && !distances.contains_key(&next)
+			{
+				distances.insert(next, distance + 1);
+				queue.push_back(next);
+			}
+		}
+	}
+
+	distances
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let machine: Machine = self.input.parse()?;
+		let (open, oxygen) = explore(machine)?;
+		debug!("explored {} open positions, oxygen system at {:?}", open.len(), oxygen);
+
+		let result = match self.part
+		{
+			Part::One => *distances(&open, oxygen).get(&(0, 0)).ok_or(Error::AocNoSolution)?,
+			Part::Two => distances(&open, oxygen).into_values().max().ok_or(Error::AocNoSolution)?,
+		};
+
+		Ok(format!("{}", result))
+	}
+}
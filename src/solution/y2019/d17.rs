@@ -0,0 +1,255 @@
+use crate::error::*;
+
+use super::intcode::Machine;
+
+/// # Examples
+///
+/// The program below just prints the camera image from the puzzle description.
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d17::Solution, Solution as S };
+/// # env_logger::init();
+/// let image = "\
+///     ..#..........\n\
+///     ..#..........\n\
+///     #######...###\n\
+///     #.#...#...#.#\n\
+///     #############\n\
+///     ..#...#...#..\n\
+///     ..#####...^..\n";
+/// let input = image.bytes()
+///     .map(|byte| format!("104,{},", byte))
+///     .chain(std::iter::once("99".to_string()))
+///     .collect::<String>();
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "76");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+type Position = (isize, isize);
+
+struct Camera
+{
+	scaffold: std::collections::BTreeSet<Position>,
+	robot: Position,
+	direction: Position,
+}
+
+impl std::str::FromStr for Camera
+{
+	type Err = Error;
+	fn from_str(image: &str) -> std::result::Result<Self, Error>
+	{
+		let mut scaffold = std::collections::BTreeSet::new();
+		let mut robot = None;
+		for (y, line) in image.lines().enumerate()
+		{
+			for (x, ch) in line.chars().enumerate()
+			{
+				let position = (x as isize, y as isize);
+				let direction = match ch
+				{
+					'.' | 'X' => continue,
+					'#' => None,
+					'^' => Some((0, -1)),
+					'v' => Some((0, 1)),
+					'<' => Some((-1, 0)),
+					'>' => Some((1, 0)),
+					ch => Err(anyhow!("unknown camera pixel {:?}", ch))?,
+				};
+				scaffold.insert(position);
+				if let Some(direction) = direction
+				{
+					robot = Some((position, direction));
+				}
+			}
+		}
+		let (robot, direction) = robot.ok_or_else(|| anyhow!("no robot on camera"))?;
+		Ok(Self { scaffold, robot, direction, })
+	}
+}
+
+impl Camera
+{
+	fn intersections(&self) -> impl Iterator<Item=Position> + '_
+	{
+		self.scaffold.iter()
+			.copied()
+			.filter(move |&(x, y)|
+			{
+				[(0, -1), (0, 1), (-1, 0), (1, 0)].iter().all(|(dx, dy)| self.scaffold.contains(&(x + dx, y + dy)))
+			})
+	}
+
+	/// Follows the scaffold straight ahead until the end, turning only where necessary.
+	fn path(&self) -> Vec<String>
+	{
+		let mut path = Vec::new();
+		let (mut position, mut direction) = (self.robot, self.direction);
+		loop
+		{
+			let left = (direction.1, -direction.0);
+			let right = (-direction.1, direction.0);
+			let step = |(x, y): Position, (dx, dy): Position| (x + dx, y + dy);
+			direction = if self.scaffold.contains(&step(position, left))
+			{
+				path.push("L".to_string());
+				left
+			}
+			else if self.scaffold.contains(&step(position, right))
+			{
+				path.push("R".to_string());
+				right
+			}
+			else
+			{
+				return path;
+			};
+			let mut count = 0;
+			while self.scaffold.contains(&step(position, direction))
+			{
+				position = step(position, direction);
+				count += 1;
+			}
+			path.push(count.to_string());
+		}
+	}
+}
+
+/// Splits the path into a main routine and three movement functions, each at most 20 characters.
+fn compress(path: &[String]) -> Option<(Vec<usize>, Vec<&[String]>)>
+{
+	fn fits(tokens: &[String]) -> bool
+	{
+		tokens.iter().map(|token| token.len() + 1).sum::<usize>() <= 21
+	}
+
+	fn search<'a>(path: &'a [String], functions: &mut Vec<&'a [String]>, main: &mut Vec<usize>) -> bool
+	{
+		if path.is_empty()
+		{
+			return fits(&vec!["A".to_string(); main.len()]);
+		}
+		if !fits(&vec!["A".to_string(); main.len() + 1])
+		{
+			return false;
+		}
+		for idx in 0..functions.len()
+		{
+			if path.starts_with(functions[idx])
+			{
+				main.push(idx);
+				if search(&path[functions[idx].len()..], functions, main)
+				{
+					return true;
+				}
+				main.pop();
+			}
+		}
+		if functions.len() < 3
+		{
+			// movement functions always cover pairs of turn and distance
+			for len in (1..=path.len() / 2).rev().map(|pairs| pairs * 2)
+			{
+				if !fits(&path[..len])
+				{
+					continue;
+				}
+				functions.push(&path[..len]);
+				main.push(functions.len() - 1);
+				if search(&path[len..], functions, main)
+				{
+					return true;
+				}
+				main.pop();
+				functions.pop();
+			}
+		}
+		false
+	}
+
+	let mut functions = Vec::new();
+	let mut main = Vec::new();
+	if search(path, &mut functions, &mut main)
+	{
+		Some((main, functions))
+	}
+	else
+	{
+		None
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				machine.run()?;
+				let (image, _) = machine.drain_ascii();
+				debug!("camera image:\n{}", image);
+				let camera: Camera = image.parse()?;
+				camera.intersections().map(|(x, y)| x * y).sum::<isize>() as i64
+			},
+			Part::Two =>
+			{
+				let mut camera_machine = machine.clone();
+				camera_machine.run()?;
+				let (image, _) = camera_machine.drain_ascii();
+				let camera: Camera = image.parse()?;
+
+				let path = camera.path();
+				debug!("path: {}", path.join(","));
+				let (main, functions) = compress(&path).ok_or_else(|| anyhow!("path cannot be split into movement functions"))?;
+
+				let main = main.iter()
+					.map(|&idx| ((b'A' + idx as u8) as char).to_string())
+					.collect::<Vec<_>>()
+					.join(",");
+				let functions = (0..3)
+					.map(|idx| functions.get(idx).map(|function| function.join(",")).unwrap_or_else(|| "L".to_string()))
+					.collect::<Vec<_>>();
+				debug!("main: {}, functions: {:?}", main, functions);
+
+				machine.write(0, 2)?;
+				machine.push_ascii(&format!("{}\n{}\nn\n", main, functions.join("\n")));
+				machine.run()?;
+				let (log, dust) = machine.drain_ascii();
+				debug!("robot output:\n{}", log);
+				*dust.last().ok_or_else(|| anyhow!("robot did not report dust: {}", log))?
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
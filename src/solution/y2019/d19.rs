@@ -0,0 +1,91 @@
+use crate::error::*;
+
+use super::intcode::Machine;
+
+/// Queries the drone system once per coordinate, a fresh copy of the program is used every time.
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+fn pulled(program: &Machine, x: i64, y: i64) -> Result<bool>
+{
+	let mut machine = program.clone();
+	machine.extend_input([x, y]);
+	machine.run()?;
+	match machine.drain_output().as_slice()
+	{
+		[0] => Ok(false),
+		[1] => Ok(true),
+		output => bail!("drone reported unexpected output {:?} for {:?}", output, (x, y)),
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let program: Machine = self.input.parse()?;
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				let mut count = 0;
+				for y in 0..50
+				{
+					for x in 0..50
+					{
+						count += pulled(&program, x, y)? as i64;
+					}
+				}
+				count
+			},
+			Part::Two =>
+			{
+				let size = 100;
+				// walk the lower left edge of the beam, rows close to the emitter may be empty
+				let mut x = 0;
+				let mut y = size - 1;
+				loop
+				{
+					let start = (x..x + 2 * y + 10).find(|&x| pulled(&program, x, y).unwrap_or(false));
+					if let Some(start) = start
+					{
+						x = start;
+						if pulled(&program, x + size - 1, y - (size - 1))?
+						{
+							break x * 10000 + y - (size - 1);
+						}
+					}
+					y += 1;
+				}
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,78 @@
+use crate::error::*;
+
+use super::intcode::Machine;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d2::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "1,9,10,3,2,3,11,0,99,30,40,50";
+/// assert_eq!(Solution::with_noun_verb(9, 10, input.to_string()).solve().unwrap(), "3500");
+/// let input = "1,1,1,4,99,5,6,0,99";
+/// assert_eq!(Solution::with_noun_verb(1, 1, input.to_string()).solve().unwrap(), "30");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self::with_noun_verb(12, 2, input)
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two(19690720), input, }
+	}
+
+	pub fn with_noun_verb(noun: i64, verb: i64, input: String) -> Self
+	{
+		Self { part: Part::One(noun, verb), input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One(i64, i64),
+	Two(i64),
+}
+
+fn execute(program: &Machine, noun: i64, verb: i64) -> Result<i64>
+{
+	let mut machine = program.clone();
+	machine.write(1, noun)?;
+	machine.write(2, verb)?;
+	machine.run()?;
+	Ok(machine.read(0))
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let program: Machine = self.input.parse()?;
+
+		let result = match self.part
+		{
+			Part::One(noun, verb) => execute(&program, noun, verb)?,
+			Part::Two(target) =>
+			{
+				(0..100)
+					.flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+					.find(|&(noun, verb)| execute(&program, noun, verb).map(|result| result == target).unwrap_or(false))
+					.map(|(noun, verb)| 100 * noun + verb)
+					.ok_or(Error::AocNoSolution)?
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,72 @@
+use crate::error::*;
+
+use super::intcode::Machine;
+
+/// Runs a fixed springscript program on the springdroid.
+///
+/// Walking, the droid jumps whenever there is a hole in the next three tiles and it can land on
+/// the fourth one.
+/// Running it additionally makes sure that it can either walk or immediately jump again after
+/// landing.
+pub struct Solution
+{
+	input: String,
+	script: &'static str,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self
+		{
+			script: "\
+				NOT A J\n\
+				NOT B T\n\
+				OR T J\n\
+				NOT C T\n\
+				OR T J\n\
+				AND D J\n\
+				WALK\n",
+			input,
+		}
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self
+		{
+			script: "\
+				NOT A J\n\
+				NOT B T\n\
+				OR T J\n\
+				NOT C T\n\
+				OR T J\n\
+				AND D J\n\
+				NOT E T\n\
+				NOT T T\n\
+				OR H T\n\
+				AND T J\n\
+				RUN\n",
+			input,
+		}
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+		machine.push_ascii(self.script);
+		machine.run()?;
+
+		let (log, damage) = machine.drain_ascii();
+		debug!("springdroid output:\n{}", log);
+		let damage = damage.last().ok_or_else(|| anyhow!("springdroid fell into space:\n{}", log))?;
+
+		Ok(format!("{}", damage))
+	}
+}
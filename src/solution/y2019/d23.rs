@@ -0,0 +1,119 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+use std::convert::TryFrom;
+
+/// Simulates the network of 50 computers round-robin.
+///
+/// Computers without pending packets receive `-1` whenever they ask for input.
+/// The network is considered idle once a full round passes without any packet being sent while
+/// all computers are waiting for input.
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+const NAT: i64 = 255;
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let program: Machine = self.input.parse()?;
+		let mut machines = (0..50)
+			.map(|address|
+			{
+				let mut machine = program.clone();
+				machine.push_input(address);
+				machine
+			})
+			.collect::<Vec<_>>();
+
+		let mut nat: Option<(i64, i64)> = None;
+		let mut last_wakeup: Option<i64> = None;
+
+		loop
+		{
+			let mut idle = true;
+			let mut packets = Vec::new();
+			for machine in machines.iter_mut()
+			{
+				if !machine.has_input()
+				{
+					machine.push_input(-1);
+				}
+				else
+				{
+					idle = false;
+				}
+				if machine.run()? == Status::Halted
+				{
+					bail!("network computer halted");
+				}
+				let output = machine.drain_output();
+				if output.len() % 3 != 0
+				{
+					bail!("network computer sent incomplete packet {:?}", output);
+				}
+				packets.extend(output.chunks(3).map(|chunk| (chunk[0], chunk[1], chunk[2])));
+			}
+
+			idle &= packets.is_empty();
+			for (address, x, y) in packets
+			{
+				trace!("packet ({}, {}) to {}", x, y, address);
+				if address == NAT
+				{
+					if self.part == Part::One
+					{
+						return Ok(format!("{}", y));
+					}
+					nat = Some((x, y));
+				}
+				else
+				{
+					let machine = usize::try_from(address).ok()
+						.and_then(|address| machines.get_mut(address))
+						.ok_or_else(|| anyhow!("packet sent to unknown address {}", address))?;
+					machine.extend_input([x, y]);
+				}
+			}
+
+			if idle
+			{
+				let (x, y) = nat.ok_or_else(|| anyhow!("network idle without any packet to the NAT"))?;
+				debug!("network idle, NAT sends ({}, {})", x, y);
+				if last_wakeup == Some(y)
+				{
+					return Ok(format!("{}", y));
+				}
+				last_wakeup = Some(y);
+				machines[0].extend_input([x, y]);
+			}
+		}
+	}
+}
@@ -0,0 +1,216 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+/// Plays the text adventure automatically.
+///
+/// The droid explores the whole ship picking up every item which does not end the game, then
+/// walks to the security checkpoint and tries combinations of items (changing one item at a
+/// time) until the pressure-sensitive floor lets it pass.
+pub struct Solution
+{
+	input: String,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { input, }
+	}
+}
+
+/// Instruction limit after which a command is considered to never finish.
+const LIMIT: usize = 10_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Room
+{
+	name: String,
+	doors: Vec<String>,
+	items: Vec<String>,
+}
+
+impl Room
+{
+	/// Parses the last room description in the output, rooms may be left immediately.
+	fn parse(output: &str) -> Option<Self>
+	{
+		let description = &output[output.rfind("== ")?..];
+		let mut lines = description.lines();
+		let name = lines.next()?.trim_matches(|c| c == '=' || c == ' ').to_string();
+		let mut doors = Vec::new();
+		let mut items = Vec::new();
+		let mut list = None;
+		for line in lines
+		{
+			match line
+			{
+				"Doors here lead:" => list = Some(&mut doors),
+				"Items here:" => list = Some(&mut items),
+				line if line.starts_with("- ") =>
+				{
+					if let Some(ref mut list) = list
+					{
+						list.push(line[2..].to_string());
+					}
+				},
+				_ => list = None,
+			}
+		}
+		Some(Self { name, doors, items, })
+	}
+}
+
+fn opposite(direction: &str) -> Result<&'static str>
+{
+	Ok(match direction
+	{
+		"north" => "south",
+		"south" => "north",
+		"east" => "west",
+		"west" => "east",
+		direction => bail!("unknown direction {:?}", direction),
+	})
+}
+
+/// Sends a command and returns the textual response.
+fn command(machine: &mut Machine, command: &str) -> Result<(Status, String)>
+{
+	trace!("> {}", command);
+	machine.push_ascii(command);
+	machine.push_ascii("\n");
+	let status = machine.run_limited(LIMIT)?;
+	let (output, _) = machine.drain_ascii();
+	trace!("{}", output);
+	Ok((status, output))
+}
+
+/// Checks on a copy of the droid whether carrying the item keeps the game going.
+fn is_safe(machine: &Machine, item: &str, room: &Room) -> Result<bool>
+{
+	let mut machine = machine.clone();
+	if command(&mut machine, &format!("take {}", item))?.0 != Status::WaitingForInput
+	{
+		return Ok(false);
+	}
+	if let Some(door) = room.doors.first()
+	{
+		let (status, output) = command(&mut machine, door)?;
+		if status != Status::WaitingForInput || output.contains("can't move")
+		{
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
+struct Explorer
+{
+	machine: Machine,
+	visited: std::collections::BTreeSet<String>,
+	inventory: Vec<String>,
+	/// Path from the start to the checkpoint and the direction of the pressure-sensitive floor.
+	checkpoint: Option<(Vec<String>, String)>,
+}
+
+impl Explorer
+{
+	fn explore(&mut self, room: Room, path: &mut Vec<String>) -> Result<()>
+	{
+		debug!("exploring {:?}", room);
+		self.visited.insert(room.name.clone());
+
+		for item in room.items.iter()
+		{
+			if is_safe(&self.machine, item, &room)?
+			{
+				debug!("taking {}", item);
+				command(&mut self.machine, &format!("take {}", item))?;
+				self.inventory.push(item.clone());
+			}
+			else
+			{
+				debug!("leaving {} behind", item);
+			}
+		}
+
+		for door in room.doors.iter()
+		{
+			let (_, output) = command(&mut self.machine, door)?;
+			let next = Room::parse(&output).ok_or_else(|| anyhow!("cannot parse room:\n{}", output))?;
+			if next.name == room.name
+			{
+				// bounced back from the pressure-sensitive floor
+				self.checkpoint = Some((path.clone(), door.clone()));
+				continue;
+			}
+			if self.visited.contains(&next.name)
+			{
+				command(&mut self.machine, opposite(door)?)?;
+				continue;
+			}
+			path.push(door.clone());
+			self.explore(next, path)?;
+			path.pop();
+			command(&mut self.machine, opposite(door)?)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+		machine.run_limited(LIMIT)?;
+		let (output, _) = machine.drain_ascii();
+		let start = Room::parse(&output).ok_or_else(|| anyhow!("cannot parse room:\n{}", output))?;
+
+		let mut explorer = Explorer
+		{
+			machine,
+			visited: Default::default(),
+			inventory: Default::default(),
+			checkpoint: None,
+		};
+		explorer.explore(start, &mut Vec::new())?;
+
+		let Explorer { mut machine, inventory, checkpoint, .. } = explorer;
+		let (path, floor) = checkpoint.ok_or_else(|| anyhow!("security checkpoint not found"))?;
+		for door in path
+		{
+			command(&mut machine, &door)?;
+		}
+		debug!("at the checkpoint with {:?}", inventory);
+
+		// gray code, so every attempt only drops or takes a single item
+		let mut carrying = (1u64 << inventory.len()) - 1;
+		for idx in 0u64..(1 << inventory.len())
+		{
+			let wanted = ((1u64 << inventory.len()) - 1) ^ (idx ^ (idx >> 1));
+			let changed = carrying ^ wanted;
+			if changed != 0
+			{
+				let item = &inventory[changed.trailing_zeros() as usize];
+				let verb = if wanted & changed != 0 { "take" } else { "drop" };
+				command(&mut machine, &format!("{} {}", verb, item))?;
+				carrying = wanted;
+			}
+
+			let (_, output) = command(&mut machine, &floor)?;
+			if let Some(code) = output.split_whitespace()
+				.skip_while(|&word| word != "typing")
+				.nth(1)
+			{
+				return Ok(code.to_string());
+			}
+		}
+
+		bail!(Error::AocNoSolution)
+	}
+}
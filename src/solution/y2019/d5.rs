@@ -0,0 +1,56 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d5::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "3,9,8,9,10,9,4,9,99,-1,8";
+/// assert_eq!(Solution::with_system(8, input.to_string()).solve().unwrap(), "1");
+/// assert_eq!(Solution::with_system(7, input.to_string()).solve().unwrap(), "0");
+/// let input = "3,3,1107,-1,8,3,4,3,99";
+/// assert_eq!(Solution::with_system(5, input.to_string()).solve().unwrap(), "1");
+/// assert_eq!(Solution::with_system(9, input.to_string()).solve().unwrap(), "0");
+/// let input = "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9";
+/// assert_eq!(Solution::with_system(0, input.to_string()).solve().unwrap(), "0");
+/// assert_eq!(Solution::with_system(3, input.to_string()).solve().unwrap(), "1");
+/// ```
+pub struct Solution
+{
+	input: String,
+	system: i64,
+}
+
+impl Solution
+{
+	pub fn with_system(system: i64, input: String) -> Self
+	{
+		Self { input, system, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+		machine.push_input(self.system);
+		if machine.run()? != Status::Halted
+		{
+			bail!("diagnostic program requested more input");
+		}
+
+		let output = machine.drain_output();
+		let (&code, tests) = output.split_last().ok_or(Error::AocNoSolution)?;
+		if let Some((idx, failure)) = tests.iter().enumerate().find(|&(_, &value)| value != 0)
+		{
+			bail!("diagnostic test {} failed with {}", idx, failure);
+		}
+
+		Ok(format!("{}", code))
+	}
+}
@@ -0,0 +1,117 @@
+use crate::error::*;
+
+use super::intcode::{ Chain, Machine };
+
+/// # Examples
+///
+/// Part 1:
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d7::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "43210");
+/// let input = "\
+///     3,23,3,24,1002,24,10,24,1002,23,-1,23,\
+///     101,5,23,23,1,24,23,23,4,23,99,0,0";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "54321");
+/// let input = "\
+///     3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,\
+///     1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "65210");
+/// ```
+///
+/// Part 2:
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d7::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+///     27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "139629729");
+/// let input = "\
+///     3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,\
+///     -5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,\
+///     53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "18216");
+/// ```
+pub struct Solution
+{
+	input: String,
+	feedback: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { feedback: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { feedback: true, input, }
+	}
+}
+
+fn permutations(items: Vec<i64>) -> Vec<Vec<i64>>
+{
+	if items.len() <= 1
+	{
+		return vec![items];
+	}
+	(0..items.len())
+		.flat_map(|idx|
+		{
+			let mut rest = items.clone();
+			let item = rest.remove(idx);
+			permutations(rest).into_iter()
+				.map(move |mut permutation|
+				{
+					permutation.insert(0, item);
+					permutation
+				})
+		})
+		.collect()
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let program: Machine = self.input.parse()?;
+		let phases = if self.feedback { (5..10).collect() } else { (0..5).collect() };
+
+		let result = permutations(phases).into_iter()
+			.map(|phases|
+			{
+				let machines = phases.iter()
+					.map(|&phase|
+					{
+						let mut machine = program.clone();
+						machine.push_input(phase);
+						machine
+					});
+				let mut chain = Chain::new(machines);
+				if self.feedback
+				{
+					chain = chain.with_feedback();
+				}
+				let signal = chain.run([0])?
+					.last()
+					.copied()
+					.ok_or(Error::AocNoSolution)?;
+				trace!("phases {:?} yield {}", phases, signal);
+				Ok(signal)
+			})
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.max()
+			.ok_or(Error::AocNoSolution)?;
+
+		Ok(format!("{}", result))
+	}
+}
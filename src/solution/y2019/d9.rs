@@ -0,0 +1,52 @@
+use crate::error::*;
+
+use super::intcode::{ Machine, Status };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2019::d9::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+/// assert_eq!(Solution::with_mode(1, input.to_string()).solve().unwrap(), input);
+/// let input = "1102,34915192,34915192,7,4,7,99,0";
+/// assert_eq!(Solution::with_mode(1, input.to_string()).solve().unwrap(), "1219070632396864");
+/// let input = "104,1125899906842624,99";
+/// assert_eq!(Solution::with_mode(1, input.to_string()).solve().unwrap(), "1125899906842624");
+/// ```
+pub struct Solution
+{
+	input: String,
+	mode: i64,
+}
+
+impl Solution
+{
+	pub fn with_mode(mode: i64, input: String) -> Self
+	{
+		Self { input, mode, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut machine: Machine = self.input.parse()?;
+		machine.push_input(self.mode);
+		if machine.run()? != Status::Halted
+		{
+			bail!("BOOST program requested more input");
+		}
+
+		// more than one value means the self-test reports malfunctioning opcodes
+		let result = machine.drain_output().into_iter()
+			.map(|value| value.to_string())
+			.collect::<Vec<_>>()
+			.join(",");
+
+		Ok(result)
+	}
+}
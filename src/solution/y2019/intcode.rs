@@ -0,0 +1,677 @@
+//! The Intcode computer shared by most of the 2019 puzzles.
+//!
+//! A [`Machine`] owns its memory (which grows on demand), an instruction pointer, the relative base
+//! and an input as well as an output queue.
+//! Running a machine never blocks: if an input instruction finds the input queue empty the machine
+//! stops with [`Status::WaitingForInput`] and can be resumed once more input was pushed.
+//! Multiple machines can be wired together using a [`Chain`].
+//!
+//! # Examples
+//!
+//! Memory is modified in place:
+//!
+//! ```
+//! # use adventofcode::solution::y2019::intcode::{ Machine, Status };
+//! let mut machine: Machine = "1,9,10,3,2,3,11,0,99,30,40,50".parse().unwrap();
+//! assert_eq!(machine.run().unwrap(), Status::Halted);
+//! assert_eq!(machine.memory(), &[3500,9,10,70,2,3,11,0,99,30,40,50]);
+//! ```
+//!
+//! Parameter modes, jumps and comparisons:
+//!
+//! ```
+//! # use adventofcode::solution::y2019::intcode::Machine;
+//! let program: Machine = "\
+//!     3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+//!     1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+//!     999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99".parse().unwrap();
+//! for (input, output) in [(7, 999), (8, 1000), (9, 1001)]
+//! {
+//!     let mut machine = program.clone();
+//!     machine.push_input(input);
+//!     machine.run().unwrap();
+//!     assert_eq!(machine.drain_output(), vec![output]);
+//! }
+//! ```
+//!
+//! Relative base and memory growth:
+//!
+//! ```
+//! # use adventofcode::solution::y2019::intcode::Machine;
+//! let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+//! let mut machine: Machine = quine.parse().unwrap();
+//! machine.run().unwrap();
+//! assert_eq!(machine.drain_output(), quine.split(',').map(|s| s.parse().unwrap()).collect::<Vec<i64>>());
+//!
+//! let mut machine: Machine = "104,1125899906842624,99".parse().unwrap();
+//! machine.run().unwrap();
+//! assert_eq!(machine.drain_output(), vec![1125899906842624]);
+//!
+//! // but not without bounds
+//! let mut machine: Machine = "1101,1,1,1000000000000,99".parse().unwrap();
+//! assert!(machine.run().is_err());
+//! let mut machine: Machine = "109,9223372036854775807,22201,1,1,0,99".parse().unwrap();
+//! assert!(machine.run().is_err());
+//! ```
+//!
+//! Blocking on input:
+//!
+//! ```
+//! # use adventofcode::solution::y2019::intcode::{ Machine, Status };
+//! let mut machine: Machine = "3,0,4,0,99".parse().unwrap();
+//! assert_eq!(machine.run().unwrap(), Status::WaitingForInput);
+//! machine.push_input(42);
+//! assert_eq!(machine.run().unwrap(), Status::Halted);
+//! assert_eq!(machine.pop_output(), Some(42));
+//! ```
+//!
+//! Disassembling:
+//!
+//! ```
+//! # use adventofcode::solution::y2019::intcode::disassemble;
+//! let program = [1002,4,3,4,33,109,-5,204,2,99];
+//! let expected = "\
+//!     0000: mul   [4], 3, [4]\n\
+//!     0004: data  33\n\
+//!     0005: arb   -5\n\
+//!     0007: out   [rb+2]\n\
+//!     0009: halt\n";
+//! assert_eq!(disassemble(&program).to_string(), expected);
+//! ```
+
+use crate::error::*;
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+/// The largest memory a machine may grow to, in cells.
+pub const MEMORY_LIMIT: usize = 1 << 24;
+
+/// How a parameter of an [`Instruction`] is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode
+{
+	Position,
+	Immediate,
+	Relative,
+}
+
+impl TryFrom<i64> for Mode
+{
+	type Error = Error;
+	fn try_from(mode: i64) -> std::result::Result<Self, Error>
+	{
+		Ok(match mode
+		{
+			0 => Mode::Position,
+			1 => Mode::Immediate,
+			2 => Mode::Relative,
+			mode => Err(anyhow!("unknown parameter mode {}", mode))?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode
+{
+	Add,
+	Multiply,
+	Input,
+	Output,
+	JumpIfTrue,
+	JumpIfFalse,
+	LessThan,
+	Equals,
+	AdjustBase,
+	Halt,
+}
+
+impl Opcode
+{
+	/// Number of parameters following the opcode in memory.
+	pub fn arity(&self) -> usize
+	{
+		match self
+		{
+			Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+			Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+			Opcode::Input | Opcode::Output | Opcode::AdjustBase => 1,
+			Opcode::Halt => 0,
+		}
+	}
+
+	pub fn mnemonic(&self) -> &'static str
+	{
+		match self
+		{
+			Opcode::Add => "add",
+			Opcode::Multiply => "mul",
+			Opcode::Input => "in",
+			Opcode::Output => "out",
+			Opcode::JumpIfTrue => "jnz",
+			Opcode::JumpIfFalse => "jz",
+			Opcode::LessThan => "lt",
+			Opcode::Equals => "eq",
+			Opcode::AdjustBase => "arb",
+			Opcode::Halt => "halt",
+		}
+	}
+
+	/// Whether the last parameter is an address that gets written to.
+	fn writes(&self) -> bool
+	{
+		match self
+		{
+			Opcode::Add | Opcode::Multiply | Opcode::Input | Opcode::LessThan | Opcode::Equals => true,
+			_ => false,
+		}
+	}
+}
+
+impl TryFrom<i64> for Opcode
+{
+	type Error = Error;
+	fn try_from(opcode: i64) -> std::result::Result<Self, Error>
+	{
+		Ok(match opcode
+		{
+			1 => Opcode::Add,
+			2 => Opcode::Multiply,
+			3 => Opcode::Input,
+			4 => Opcode::Output,
+			5 => Opcode::JumpIfTrue,
+			6 => Opcode::JumpIfFalse,
+			7 => Opcode::LessThan,
+			8 => Opcode::Equals,
+			9 => Opcode::AdjustBase,
+			99 => Opcode::Halt,
+			opcode => Err(anyhow!("unknown opcode {}", opcode))?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parameter
+{
+	pub mode: Mode,
+	pub value: i64,
+}
+
+impl std::fmt::Display for Parameter
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		match self.mode
+		{
+			Mode::Position => write!(f, "[{}]", self.value),
+			Mode::Immediate => write!(f, "{}", self.value),
+			Mode::Relative => write!(f, "[rb{:+}]", self.value),
+		}
+	}
+}
+
+/// A single decoded instruction including its parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Instruction
+{
+	pub opcode: Opcode,
+	pub parameters: Vec<Parameter>,
+}
+
+impl Instruction
+{
+	/// Decodes the instruction at `address`, memory outside of `memory` reads as zero.
+	pub fn decode(memory: &[i64], address: usize) -> Result<Self>
+	{
+		let read = |address: usize| memory.get(address).copied().unwrap_or(0);
+		let value = read(address);
+		if value < 0
+		{
+			bail!("negative instruction {} at address {}", value, address);
+		}
+		let opcode = Opcode::try_from(value % 100)
+			.with_context(|| format!("cannot decode instruction {} at address {}", value, address))?;
+		let parameters = (0..opcode.arity())
+			.map(|idx|
+			{
+				let mode = Mode::try_from(value / 10i64.pow(idx as u32 + 2) % 10)
+					.with_context(|| format!("cannot decode instruction {} at address {}", value, address))?;
+				if mode == Mode::Immediate && opcode.writes() && idx + 1 == opcode.arity()
+				{
+					bail!("instruction {} at address {} writes to an immediate parameter", value, address);
+				}
+				Ok(Parameter { mode, value: read(address + idx + 1), })
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(Self { opcode, parameters, })
+	}
+
+	/// Number of memory cells occupied by the instruction.
+	pub fn size(&self) -> usize
+	{
+		self.parameters.len() + 1
+	}
+}
+
+impl std::fmt::Display for Instruction
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		if self.parameters.is_empty()
+		{
+			return write!(f, "{}", self.opcode.mnemonic());
+		}
+		write!(f, "{:<5}", self.opcode.mnemonic())?;
+		for (idx, parameter) in self.parameters.iter().enumerate()
+		{
+			if idx == 0
+			{
+				write!(f, " {}", parameter)?;
+			}
+			else
+			{
+				write!(f, ", {}", parameter)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Result of a linear sweep over a program, see [`disassemble`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disassembly(pub Vec<(usize, std::result::Result<Instruction, i64>)>);
+
+/// Decodes the program front to back.
+///
+/// Any cell which cannot be decoded as an instruction is reported as data, as Intcode programs
+/// freely mix code and data as well as modifying themselves this is merely a debugging aid.
+pub fn disassemble(memory: &[i64]) -> Disassembly
+{
+	let mut result = Vec::new();
+	let mut address = 0;
+	while address < memory.len()
+	{
+		match Instruction::decode(memory, address)
+		{
+			Ok(instruction) =>
+			{
+				let len = instruction.size();
+				result.push((address, Ok(instruction)));
+				address += len;
+			},
+			Err(_) =>
+			{
+				result.push((address, Err(memory[address])));
+				address += 1;
+			},
+		}
+	}
+	Disassembly(result)
+}
+
+impl std::fmt::Display for Disassembly
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		for (address, entry) in self.0.iter()
+		{
+			match entry
+			{
+				Ok(instruction) => writeln!(f, "{:04}: {}", address, instruction)?,
+				Err(data) => writeln!(f, "{:04}: {:<5} {}", address, "data", data)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Why a [`Machine`] stopped executing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status
+{
+	/// The machine can continue executing.
+	Running,
+	/// An input instruction was reached while the input queue was empty.
+	WaitingForInput,
+	/// The machine executed a halt instruction.
+	Halted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Machine
+{
+	memory: Vec<i64>,
+	ip: usize,
+	relative_base: i64,
+	input: VecDeque<i64>,
+	output: VecDeque<i64>,
+	halted: bool,
+}
+
+impl std::str::FromStr for Machine
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let program = input.trim()
+			.split(',')
+			.map(|num| num.trim().parse::<i64>().with_context(|| format!("cannot parse intcode {:?}", num)))
+			.collect::<Result<Vec<_>>>()
+			.context(Error::AocParsing)?;
+		Ok(Self::new(program))
+	}
+}
+
+impl Machine
+{
+	pub fn new(program: Vec<i64>) -> Self
+	{
+		Self
+		{
+			memory: program,
+			ip: 0,
+			relative_base: 0,
+			input: Default::default(),
+			output: Default::default(),
+			halted: false,
+		}
+	}
+
+	pub fn memory(&self) -> &[i64]
+	{
+		&self.memory
+	}
+
+	pub fn is_halted(&self) -> bool
+	{
+		self.halted
+	}
+
+	/// Reads memory, cells never written to read as zero.
+	pub fn read(&self, address: usize) -> i64
+	{
+		self.memory.get(address).copied().unwrap_or(0)
+	}
+
+	/// Writes memory, growing it as required up to [`MEMORY_LIMIT`] cells.
+	pub fn write(&mut self, address: usize, value: i64) -> Result<()>
+	{
+		if address >= self.memory.len()
+		{
+			if address >= MEMORY_LIMIT
+			{
+				bail!("write to address {} exceeds the memory limit of {} cells", address, MEMORY_LIMIT);
+			}
+			self.memory.resize(address + 1, 0);
+		}
+		self.memory[address] = value;
+		Ok(())
+	}
+
+	pub fn push_input(&mut self, value: i64)
+	{
+		self.input.push_back(value);
+	}
+
+	pub fn extend_input<I: IntoIterator<Item=i64>>(&mut self, values: I)
+	{
+		self.input.extend(values);
+	}
+
+	/// Queues the bytes of the string as input, for the puzzles speaking ASCII.
+	pub fn push_ascii(&mut self, input: &str)
+	{
+		self.input.extend(input.bytes().map(i64::from));
+	}
+
+	pub fn has_input(&self) -> bool
+	{
+		!self.input.is_empty()
+	}
+
+	pub fn pop_output(&mut self) -> Option<i64>
+	{
+		self.output.pop_front()
+	}
+
+	pub fn drain_output(&mut self) -> Vec<i64>
+	{
+		self.output.drain(..).collect()
+	}
+
+	/// Drains the output and interprets it as ASCII.
+	///
+	/// Values outside of the ASCII range are returned separately as the puzzles use them to report
+	/// the actual result after a textual log.
+	pub fn drain_ascii(&mut self) -> (String, Vec<i64>)
+	{
+		let (text, other): (Vec<i64>, Vec<i64>) = self.output.drain(..).partition(|value| (0..128).contains(value));
+		(text.into_iter().map(|value| value as u8 as char).collect(), other)
+	}
+
+	fn address(&self, parameter: &Parameter) -> Result<usize>
+	{
+		let address = match parameter.mode
+		{
+			Mode::Position => parameter.value,
+			Mode::Relative => self.relative_base.checked_add(parameter.value)
+				.with_context(|| format!("relative address overflow at ip {}", self.ip))?,
+			Mode::Immediate => bail!("immediate parameter {} has no address", parameter.value),
+		};
+		usize::try_from(address).with_context(|| format!("negative address {} at ip {}", address, self.ip))
+	}
+
+	fn load(&self, parameter: &Parameter) -> Result<i64>
+	{
+		Ok(match parameter.mode
+		{
+			Mode::Immediate => parameter.value,
+			_ => self.read(self.address(parameter)?),
+		})
+	}
+
+	fn store(&mut self, parameter: &Parameter, value: i64) -> Result<()>
+	{
+		let address = self.address(parameter)?;
+		self.write(address, value)
+	}
+
+	/// Executes a single instruction.
+	pub fn step(&mut self) -> Result<Status>
+	{
+		if self.halted
+		{
+			return Ok(Status::Halted);
+		}
+
+		let instruction = Instruction::decode(&self.memory, self.ip)?;
+		trace!("{:04}: {}", self.ip, instruction);
+		let params = &instruction.parameters;
+		let mut next = self.ip + instruction.size();
+
+		match instruction.opcode
+		{
+			Opcode::Add =>
+			{
+				let value = self.load(&params[0])?.checked_add(self.load(&params[1])?).context("addition overflow")?;
+				self.store(&params[2], value)?;
+			},
+			Opcode::Multiply =>
+			{
+				let value = self.load(&params[0])?.checked_mul(self.load(&params[1])?).context("multiplication overflow")?;
+				self.store(&params[2], value)?;
+			},
+			Opcode::Input =>
+			{
+				match self.input.pop_front()
+				{
+					Some(value) => self.store(&params[0], value)?,
+					None => return Ok(Status::WaitingForInput),
+				}
+			},
+			Opcode::Output =>
+			{
+				let value = self.load(&params[0])?;
+				self.output.push_back(value);
+			},
+			Opcode::JumpIfTrue | Opcode::JumpIfFalse =>
+			{
+				let condition = self.load(&params[0])? != 0;
+				if condition == (instruction.opcode == Opcode::JumpIfTrue)
+				{
+					let target = self.load(&params[1])?;
+					next = usize::try_from(target).with_context(|| format!("jump to negative address {} at ip {}", target, self.ip))?;
+				}
+			},
+			Opcode::LessThan =>
+			{
+				let value = (self.load(&params[0])? < self.load(&params[1])?) as i64;
+				self.store(&params[2], value)?;
+			},
+			Opcode::Equals =>
+			{
+				let value = (self.load(&params[0])? == self.load(&params[1])?) as i64;
+				self.store(&params[2], value)?;
+			},
+			Opcode::AdjustBase =>
+			{
+				self.relative_base = self.relative_base.checked_add(self.load(&params[0])?).context("relative base overflow")?;
+			},
+			Opcode::Halt =>
+			{
+				self.halted = true;
+				return Ok(Status::Halted);
+			},
+		}
+
+		self.ip = next;
+		Ok(Status::Running)
+	}
+
+	/// Runs until the machine halts or waits for input.
+	pub fn run(&mut self) -> Result<Status>
+	{
+		loop
+		{
+			match self.step()?
+			{
+				Status::Running => {},
+				status => return Ok(status),
+			}
+		}
+	}
+
+	/// Like [`Machine::run`] but gives up after `limit` instructions, returning [`Status::Running`].
+	pub fn run_limited(&mut self, limit: usize) -> Result<Status>
+	{
+		for _ in 0..limit
+		{
+			match self.step()?
+			{
+				Status::Running => {},
+				status => return Ok(status),
+			}
+		}
+		Ok(Status::Running)
+	}
+}
+
+/// Machines wired in series, the output of each machine being the input of the next one.
+///
+/// With feedback enabled the output of the last machine is fed back into the first one until all
+/// machines halted.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2019::intcode::{ Chain, Machine };
+/// let program: Machine = "\
+///     3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+///     27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5".parse().unwrap();
+/// let machines = [9,8,7,6,5].iter()
+///     .map(|&phase|
+///     {
+///         let mut machine = program.clone();
+///         machine.push_input(phase);
+///         machine
+///     });
+/// let mut chain = Chain::new(machines).with_feedback();
+/// assert_eq!(chain.run([0]).unwrap().last(), Some(&139629729));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Chain
+{
+	machines: Vec<Machine>,
+	feedback: bool,
+}
+
+impl Chain
+{
+	pub fn new<I: IntoIterator<Item=Machine>>(machines: I) -> Self
+	{
+		Self
+		{
+			machines: machines.into_iter().collect(),
+			feedback: false,
+		}
+	}
+
+	pub fn with_feedback(self) -> Self
+	{
+		Self
+		{
+			feedback: true,
+			..self
+		}
+	}
+
+	pub fn machines(&self) -> &[Machine]
+	{
+		&self.machines
+	}
+
+	/// Feeds the input to the first machine and runs the chain until it halts.
+	///
+	/// Returns all values emitted by the last machine.
+	pub fn run<I: IntoIterator<Item=i64>>(&mut self, input: I) -> Result<Vec<i64>>
+	{
+		let len = self.machines.len();
+		let first = self.machines.first_mut().ok_or_else(|| anyhow!("empty chain of machines"))?;
+		first.extend_input(input);
+
+		let mut result = Vec::new();
+		loop
+		{
+			let mut progress = false;
+			for idx in 0..len
+			{
+				let status = self.machines[idx].run()?;
+				let output = self.machines[idx].drain_output();
+				progress |= !output.is_empty();
+				if idx + 1 == len
+				{
+					result.extend(output.iter().copied());
+					if self.feedback
+					{
+						self.machines[0].extend_input(output);
+					}
+				}
+				else
+				{
+					self.machines[idx + 1].extend_input(output);
+				}
+				trace!("machine {} stopped with {:?}", idx, status);
+			}
+
+			if self.machines.iter().all(Machine::is_halted)
+			{
+				return Ok(result);
+			}
+			if !progress
+			{
+				bail!("chain of machines deadlocked waiting for input");
+			}
+		}
+	}
+}
@@ -0,0 +1,89 @@
+pub mod intcode;
+
+pub mod d2;
+pub mod d5;
+pub mod d7;
+pub mod d9;
+pub mod d11;
+pub mod d13;
+pub mod d15;
+pub mod d17;
+pub mod d19;
+pub mod d21;
+pub mod d23;
+pub mod d25;
+
+pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
+{
+	#[allow(unused)]
+	enum InputType<'a>
+	{
+		UrlInput(Box<dyn Fn(Option<String>) -> Box<dyn super::Solution + 'static>>),
+		Parser(Box<dyn super::InputParser<'a>>),
+	}
+
+	let days: Vec<Option<InputType>> = vec!
+	[
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d2::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d2::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d5::Solution::with_system(1, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d5::Solution::with_system(5, input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d7::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d7::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d9::Solution::with_mode(1, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d9::Solution::with_mode(2, input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d11::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d11::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d13::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d13::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d17::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d17::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part2(input.expect("empty input received")))))),
+		None,
+		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d25::Solution::part1(input.expect("empty input received")))))),
+	];
+
+	days.into_iter()
+		.enumerate()
+		.filter_map(|(idx,opt)| opt.map(|parser| (idx,parser)))
+		.map(|(idx,parser)|
+			match parser
+			{
+				InputType::UrlInput(parser) => Box::new((2019usize,idx/2+1,idx%2+1,parser)) as Box<dyn super::InputParser>,
+				InputType::Parser(parser) => parser,
+			}
+		)
+		.collect()
+}
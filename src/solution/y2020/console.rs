@@ -0,0 +1,520 @@
+//! The handheld game console of the 2020 puzzles.
+//!
+//! A [`Console`] executes a program of [`Instruction`]s, keeping track of which instructions it
+//! already executed.
+//! Jumps are unconditional so executing an instruction a second time means the program loops
+//! forever, hence the console stops with [`Status::Looped`] instead.
+//! A program terminates by attempting to execute the instruction right after its last one.
+//!
+//! # Examples
+//!
+//! ```
+//! # use adventofcode::solution::y2020::console::{ Console, Instruction, Status, repair };
+//! let program = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+//! let mut console: Console = program.parse().unwrap();
+//! assert_eq!(console.run().unwrap(), Status::Looped(1));
+//! assert_eq!(console.accumulator(), 5);
+//!
+//! let mut program = console.program().to_vec();
+//! let address = repair(&program).unwrap();
+//! assert_eq!(address, 7);
+//! program[address] = program[address].flipped().unwrap();
+//! let mut console = Console::new(program).with_trace();
+//! console.add_breakpoint(6);
+//! assert_eq!(console.run().unwrap(), Status::Breakpoint(6));
+//! assert_eq!(console.run().unwrap(), Status::Terminated);
+//! assert_eq!(console.accumulator(), 8);
+//! assert_eq!(console.trace().last().unwrap().to_string(), "0008: acc +6 | acc 8");
+//! assert_eq!(console.trace().len(), 6);
+//! ```
+
+use crate::error::*;
+
+use std::collections::{ BTreeSet, VecDeque };
+use std::convert::TryFrom;
+use std::io::{ BufRead, Write };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction
+{
+	Acc(isize),
+	Jmp(isize),
+	Nop(isize),
+}
+
+impl std::str::FromStr for Instruction
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let (operation, argument) = input.trim().split_once(' ')
+			.ok_or_else(|| anyhow!("missing argument in instruction {:?}", input))?;
+		let argument = argument.parse()?;
+		Ok(match operation
+		{
+			"acc" => Instruction::Acc(argument),
+			"jmp" => Instruction::Jmp(argument),
+			"nop" => Instruction::Nop(argument),
+			operation => Err(anyhow!("unknown operation {:?}", operation))?,
+		})
+	}
+}
+
+impl std::fmt::Display for Instruction
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		let (operation, argument) = match self
+		{
+			Instruction::Acc(argument) => ("acc", argument),
+			Instruction::Jmp(argument) => ("jmp", argument),
+			Instruction::Nop(argument) => ("nop", argument),
+		};
+		f.pad(&format!("{} {:+}", operation, argument))
+	}
+}
+
+impl Instruction
+{
+	/// The instruction with `jmp` and `nop` swapped, `acc` has no counterpart.
+	pub fn flipped(&self) -> Option<Self>
+	{
+		match *self
+		{
+			Instruction::Acc(_) => None,
+			Instruction::Jmp(argument) => Some(Instruction::Nop(argument)),
+			Instruction::Nop(argument) => Some(Instruction::Jmp(argument)),
+		}
+	}
+
+	/// The address executed after this instruction at `address`, unless it would be negative.
+	pub fn next(&self, address: usize) -> Option<usize>
+	{
+		let offset = match *self
+		{
+			Instruction::Jmp(offset) => offset,
+			_ => 1,
+		};
+		isize::try_from(address).ok()
+			.and_then(|address| address.checked_add(offset))
+			.and_then(|address| usize::try_from(address).ok())
+	}
+}
+
+/// An executed instruction and the accumulator after executing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Step
+{
+	pub address: usize,
+	pub instruction: Instruction,
+	pub accumulator: isize,
+}
+
+impl std::fmt::Display for Step
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		write!(f, "{:04}: {:<7}| acc {}", self.address, self.instruction, self.accumulator)
+	}
+}
+
+/// Why a [`Console`] stopped executing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status
+{
+	/// The console can continue executing.
+	Running,
+	/// The next instruction at the address has a breakpoint.
+	Breakpoint(usize),
+	/// The next instruction at the address was executed before.
+	Looped(usize),
+	/// The program ran past its last instruction.
+	Terminated,
+}
+
+impl std::fmt::Display for Status
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+	{
+		match self
+		{
+			Status::Running => write!(f, "running"),
+			Status::Breakpoint(address) => write!(f, "breakpoint at {:04}", address),
+			Status::Looped(address) => write!(f, "loop at {:04}", address),
+			Status::Terminated => write!(f, "terminated"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Console
+{
+	program: Vec<Instruction>,
+	ip: usize,
+	accumulator: isize,
+	executed: Vec<bool>,
+	breakpoints: BTreeSet<usize>,
+	trace: Option<Vec<Step>>,
+}
+
+impl std::str::FromStr for Console
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let program = input.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.trim().is_empty())
+			.map(|(idx, line)| line.parse::<Instruction>().with_context(|| format!("cannot parse instruction {:?} on line {}", line, idx + 1)))
+			.collect::<Result<Vec<_>>>()
+			.context(Error::AocParsing)?;
+		Ok(Self::new(program))
+	}
+}
+
+impl Console
+{
+	pub fn new(program: Vec<Instruction>) -> Self
+	{
+		Self
+		{
+			executed: vec![false; program.len()],
+			program,
+			ip: 0,
+			accumulator: 0,
+			breakpoints: Default::default(),
+			trace: None,
+		}
+	}
+
+	/// Records every executed instruction, see [`Console::trace`].
+	pub fn with_trace(self) -> Self
+	{
+		Self
+		{
+			trace: Some(Vec::new()),
+			..self
+		}
+	}
+
+	pub fn program(&self) -> &[Instruction]
+	{
+		&self.program
+	}
+
+	pub fn ip(&self) -> usize
+	{
+		self.ip
+	}
+
+	pub fn accumulator(&self) -> isize
+	{
+		self.accumulator
+	}
+
+	/// The executed instructions in order, empty unless tracing was enabled.
+	pub fn trace(&self) -> &[Step]
+	{
+		self.trace.as_deref().unwrap_or(&[])
+	}
+
+	pub fn breakpoints(&self) -> &BTreeSet<usize>
+	{
+		&self.breakpoints
+	}
+
+	/// Returns whether the breakpoint was newly added.
+	pub fn add_breakpoint(&mut self, address: usize) -> bool
+	{
+		self.breakpoints.insert(address)
+	}
+
+	/// Returns whether the breakpoint existed.
+	pub fn remove_breakpoint(&mut self, address: usize) -> bool
+	{
+		self.breakpoints.remove(&address)
+	}
+
+	/// Executes a single instruction, unless the program terminated or is about to loop.
+	///
+	/// Breakpoints are ignored when stepping.
+	pub fn step(&mut self) -> Result<Status>
+	{
+		let instruction = match self.program.get(self.ip)
+		{
+			Some(_) if self.executed[self.ip] => return Ok(Status::Looped(self.ip)),
+			Some(&instruction) => instruction,
+			None if self.ip == self.program.len() => return Ok(Status::Terminated),
+			None => bail!("instruction pointer {} is out of bounds of the program of length {}", self.ip, self.program.len()),
+		};
+
+		trace!("{:04}: {}", self.ip, instruction);
+		// a failing instruction leaves the console untouched
+		let accumulator = match instruction
+		{
+			Instruction::Acc(argument) => self.accumulator.checked_add(argument).context("accumulator overflow")?,
+			_ => self.accumulator,
+		};
+		let next = instruction.next(self.ip).ok_or_else(|| anyhow!("jump to negative address at {}", self.ip))?;
+
+		self.executed[self.ip] = true;
+		self.accumulator = accumulator;
+		if let Some(trace) = self.trace.as_mut()
+		{
+			trace.push(Step { address: self.ip, instruction, accumulator, });
+		}
+		self.ip = next;
+
+		Ok(Status::Running)
+	}
+
+	/// Runs until the program terminates, loops or reaches a breakpoint.
+	///
+	/// The first instruction is always executed, so running again continues past a breakpoint.
+	pub fn run(&mut self) -> Result<Status>
+	{
+		loop
+		{
+			match self.step()?
+			{
+				Status::Running if self.breakpoints.contains(&self.ip) => return Ok(Status::Breakpoint(self.ip)),
+				Status::Running => {},
+				status => return Ok(status),
+			}
+		}
+	}
+}
+
+/// Finds the single `jmp` or `nop` which, flipped, makes the looping program terminate.
+///
+/// The instructions from which the end of the program is reachable are found by walking the
+/// control flow backwards from the end.
+/// Only an instruction on the looping path can fix the loop and none of these reach the end, so
+/// flipping one repairs the program iff its new successor reaches the end.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2020::console::{ Console, repair };
+/// let program = |source: &str| source.parse::<Console>().unwrap().program().to_vec();
+/// let example = program("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6");
+/// assert_eq!(repair(&example).unwrap(), 7);
+///
+/// // jumping out of bounds is repaired just like looping
+/// assert_eq!(repair(&program("acc +1\njmp -2")).unwrap(), 1);
+///
+/// // nothing to repair, nothing that helps, or several instructions that would do
+/// assert!(repair(&program("nop +0\nacc +1")).is_err());
+/// assert!(repair(&program("jmp +0\njmp -1")).is_err());
+/// assert!(repair(&program("nop +3\njmp +0\nacc +0")).is_err());
+/// ```
+pub fn repair(program: &[Instruction]) -> Result<usize>
+{
+	let len = program.len();
+	let mut predecessors = vec![Vec::new(); len + 1];
+	for (address, instruction) in program.iter().enumerate()
+	{
+		if let Some(next) = instruction.next(address).filter(|&next| next <= len)
+		{
+			predecessors[next].push(address);
+		}
+	}
+
+	let mut finishing = vec![false; len + 1];
+	finishing[len] = true;
+	let mut queue = VecDeque::from(vec![len]);
+	while let Some(address) = queue.pop_front()
+	{
+		for &previous in predecessors[address].iter()
+		{
+			if !std::mem::replace(&mut finishing[previous], true)
+			{
+				queue.push_back(previous);
+			}
+		}
+	}
+	if finishing[0]
+	{
+		bail!("the program terminates without any repair");
+	}
+
+	// the path ends in a loop or by jumping out of bounds, a jump to a negative address fails
+	// before it is traced though
+	let mut console = Console::new(program.to_vec()).with_trace();
+	let failed = console.run().is_err();
+	let candidates = console.trace().iter()
+		.map(|step| step.address)
+		.chain(Some(console.ip()).filter(|&address| failed && address < len))
+		.filter_map(|address| Some(address).zip(program[address].flipped()))
+		.filter(|&(address, flipped)| flipped.next(address).map(|next| next <= len && finishing[next]).unwrap_or(false))
+		.map(|(address, _)| address)
+		.collect::<Vec<_>>();
+
+	match candidates[..]
+	{
+		[address] => Ok(address),
+		[] => bail!(Error::AocNoSolution),
+		_ => bail!("ambiguous repair, flipping any of {:?} terminates the program", candidates),
+	}
+}
+
+/// Parses an address argument of a debugger command.
+fn address(argument: Option<&str>) -> Result<Option<usize>>
+{
+	Ok(argument.map(str::parse).transpose()?)
+}
+
+/// An interactive debugger reading commands line by line until `quit` or the end of the input.
+///
+/// Errors of the program are printed and the session goes on, only failing to read or write ends it.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2020::console::{ Console, debug };
+/// let mut console: Console = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6".parse().unwrap();
+/// let commands = "break 6\ncontinue\nstep 2\nlist\ncontinue\nprint\nquit\nstep\n";
+/// let mut output = Vec::new();
+/// debug(&mut console, commands.as_bytes(), &mut output).unwrap();
+/// let expected =
+/// [
+///     "(console) breakpoint set at 0006",
+///     "(console) breakpoint at 0006, acc 1",
+///     "(console) 0006: acc +1 | acc 2",
+///     "0007: jmp -4 | acc 2",
+///     "(console)   0001: acc +1",
+///     "  0002: jmp +4",
+///     "> 0003: acc +3",
+///     "  0004: jmp -3",
+///     "  0005: acc -99",
+///     " *0006: acc +1",
+///     "(console) loop at 0001, acc 5",
+///     "(console) ip 0001, acc 5",
+///     "(console) ",
+/// ];
+/// assert_eq!(String::from_utf8(output).unwrap(), expected.join("\n"));
+/// assert_eq!(console.accumulator(), 5);
+///
+/// let mut console: Console = "acc +1\njmp -2".parse().unwrap();
+/// let commands = "continue\nstep 3\nlist 18446744073709551615\nprint\n";
+/// let mut output = Vec::new();
+/// debug(&mut console, commands.as_bytes(), &mut output).unwrap();
+/// let expected =
+/// [
+///     "(console) error: jump to negative address at 1",
+///     "(console) error: jump to negative address at 1",
+///     "(console) (console) ip 0001, acc 1",
+///     "(console) ",
+///     "",
+/// ];
+/// assert_eq!(String::from_utf8(output).unwrap(), expected.join("\n"));
+/// ```
+pub fn debug<R: BufRead, W: Write>(console: &mut Console, input: R, mut output: W) -> Result<()>
+{
+	let mut lines = input.lines();
+	loop
+	{
+		write!(output, "(console) ")?;
+		output.flush()?;
+		let line = match lines.next()
+		{
+			Some(line) => line?,
+			None =>
+			{
+				writeln!(output)?;
+				return Ok(());
+			},
+		};
+		let mut words = line.split_whitespace();
+		let command = words.next();
+		let argument = match address(words.next())
+		{
+			Ok(argument) => argument,
+			Err(_) =>
+			{
+				writeln!(output, "invalid address in {:?}", line)?;
+				continue;
+			},
+		};
+
+		match (command, argument)
+		{
+			(None, _) => {},
+			(Some("s" | "step"), count) =>
+			{
+				for _ in 0..count.unwrap_or(1)
+				{
+					let address = console.ip();
+					match console.step()
+					{
+						Err(err) =>
+						{
+							writeln!(output, "error: {}", err)?;
+							break;
+						},
+						Ok(Status::Running) =>
+						{
+							let instruction = console.program()[address];
+							writeln!(output, "{}", Step { address, instruction, accumulator: console.accumulator(), })?;
+						},
+						Ok(status) =>
+						{
+							writeln!(output, "{}, acc {}", status, console.accumulator())?;
+							break;
+						},
+					}
+				}
+			},
+			(Some("c" | "continue"), None) =>
+			{
+				match console.run()
+				{
+					Ok(status) => writeln!(output, "{}, acc {}", status, console.accumulator())?,
+					Err(err) => writeln!(output, "error: {}", err)?,
+				}
+			},
+			(Some("b" | "break"), Some(address)) =>
+			{
+				if console.add_breakpoint(address)
+				{
+					writeln!(output, "breakpoint set at {:04}", address)?;
+				}
+				else
+				{
+					writeln!(output, "breakpoint at {:04} already set", address)?;
+				}
+			},
+			(Some("d" | "delete"), Some(address)) =>
+			{
+				if console.remove_breakpoint(address)
+				{
+					writeln!(output, "breakpoint at {:04} deleted", address)?;
+				}
+				else
+				{
+					writeln!(output, "no breakpoint at {:04}", address)?;
+				}
+			},
+			(Some("l" | "list"), around) =>
+			{
+				let around = around.unwrap_or_else(|| console.ip());
+				let end = around.saturating_add(4).min(console.program().len());
+				for address in around.saturating_sub(2)..end
+				{
+					let ip = if address == console.ip() { '>' } else { ' ' };
+					let breakpoint = if console.breakpoints().contains(&address) { '*' } else { ' ' };
+					writeln!(output, "{}{}{:04}: {}", ip, breakpoint, address, console.program()[address])?;
+				}
+			},
+			(Some("p" | "print"), None) =>
+			{
+				writeln!(output, "ip {:04}, acc {}", console.ip(), console.accumulator())?;
+			},
+			(Some("q" | "quit"), None) => return Ok(()),
+			_ =>
+			{
+				writeln!(output, "commands: step [count], continue, break <address>, delete <address>, list [address], print, quit")?;
+			},
+		}
+	}
+}
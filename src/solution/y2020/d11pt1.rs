@@ -1,5 +1,13 @@
 use crate::error::*;
 
+use std::convert::TryFrom;
+
+use super::super::common::
+{
+	automaton::{ life, Automaton },
+	grid::{ Grid, Position },
+};
+
 /// # Examples
 ///
 /// ```
@@ -33,94 +41,25 @@ impl Solution
 		Self { input, }
 	}
 }
-#[derive(Clone,Debug,Eq,PartialEq)]
-struct State
-{
-	vec: Vec<Vec<Option<bool>>>,
-}
-
-impl State
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Tile
 {
-	fn step(&self) -> Self
-	{
-		let mut outer = Vec::new();
-		for i in 0..self.vec.len()
-		{
-			let mut inner = Vec::new();
-
-			for j in 0..self.vec[i].len()
-			{
-				if let Some(current) = *self.vec.get(i).and_then(|v| v.get(j)).unwrap()
-				{
-					let count = (-1..=1)
-						.flat_map(|x: isize|
-						{
-							(-1..=1)
-								.map(|y| (x+(i as isize),y+(j as isize)))
-								.collect::<Vec<(isize,isize)>>()
-						})
-						.flat_map(|(i,j)|
-						{
-							self.vec.get(i as usize)
-								.and_then(|v| v.get(j as usize))
-						})
-						.filter(|&seat| match seat
-						{
-							None => false,
-							&Some(x) => x,
-						})
-						.count();
-					let new = match (current,count)
-					{
-						(false, 0) => true,
-						(true, 5..=10) => false,
-						(x,_) => x,
-					};
-					inner.push(Some(new));
-				}
-				else
-				{
-					inner.push(None);
-					continue;
-				}
-			}
-			outer.push(inner);
-		}
-		State
-		{
-			vec: outer,
-		}
-	}
-
-	fn count(&self) -> usize
-	{
-		self.vec.iter().map(|vec| vec.iter().filter(|opt| opt.unwrap_or(false)).count()).sum()
-	}
+	Floor,
+	Empty,
+	Occupied,
 }
 
-impl std::str::FromStr for State
+impl TryFrom<char> for Tile
 {
-	type Err = Error;
-	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	type Error = Error;
+	fn try_from(ch: char) -> std::result::Result<Self, Error>
 	{
-		let vec = input.lines()
-			.map(|line|
-			{
-				line.chars()
-					.map(|ch| Ok(match ch
-					{
-						'.' => None,
-						'L' => Some(false),
-						'#' => Some(true),
-						_ => bail!(Error::AocParsing),
-					}))
-					.collect::<Result<Vec<_>>>()
-			})
-			.collect::<Result<Vec<Vec<_>>>>()?;
-
-		Ok(State
+		Ok(match ch
 		{
-			vec,
+			'.' => Tile::Floor,
+			'L' => Tile::Empty,
+			'#' => Tile::Occupied,
+			_ => Err(anyhow!("cannot convert {:?} to Tile", ch))?,
 		})
 	}
 }
@@ -129,20 +68,32 @@ impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
 	{
-		let mut state = self.input.parse::<State>()?;
+		debug!("called with input: {}", self.input);
 
-		loop
+		let layout = self.input.parse::<Grid<Tile>>()?;
+		let seats = layout.iter()
+			.filter(|(_, &tile)| tile != Tile::Floor)
+			.map(|(pos, &tile)| (pos, tile))
+			.collect::<Vec<_>>();
+		let occupied = seats.iter()
+			.filter(|(_, tile)| *tile == Tile::Occupied)
+			.map(|&(pos, _)| pos);
+
+		let neighbours = |&pos: &Position|
+		{
+			layout.neighbours8(pos)
+				.filter(|&pos| layout[pos] != Tile::Floor)
+				.collect()
+		};
+
+		let mut automaton = Automaton::bounded(occupied, seats.iter().map(|&(pos, _)| pos), neighbours, life([0], 0..4));
+		let mut rounds = 0;
+		while automaton.step()
 		{
-			let new = state.step();
-			if new == state
-			{
-				return Ok(format!("{}", state.count()));
-			}
-			else
-			{
-				state = new;
-			}
+			rounds += 1;
 		}
+		debug!("stable after {} rounds", rounds);
+
+		Ok(format!("{}", automaton.population().ok_or(Error::AocNoSolution)?))
 	}
 }
-
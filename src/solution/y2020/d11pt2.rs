@@ -1,5 +1,13 @@
 use crate::error::*;
 
+use std::convert::TryFrom;
+
+use super::super::common::
+{
+	automaton::{ life, Automaton },
+	grid::{ Grid, Position, NEIGHBOURS8, offset },
+};
+
 /// # Examples
 ///
 /// ```
@@ -34,96 +42,25 @@ impl Solution
 	}
 }
 
-#[derive(Clone,Debug,Eq,PartialEq)]
-struct State
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Tile
 {
-	vec: Vec<Vec<Option<bool>>>,
+	Floor,
+	Empty,
+	Occupied,
 }
 
-impl State
+impl TryFrom<char> for Tile
 {
-	fn step(&self) -> Self
+	type Error = Error;
+	fn try_from(ch: char) -> std::result::Result<Self, Error>
 	{
-		let mut outer = Vec::new();
-		for i in 0..self.vec.len()
-		{
-			let mut inner = Vec::new();
-
-			for j in 0..self.vec[i].len()
-			{
-				if let Some(current) = *self.vec.get(i).and_then(|v| v.get(j)).unwrap()
-				{
-					let count = (-1..=1)
-						.flat_map(|x: isize|
-						{
-							(-1..=1)
-								.map(|y| (x,y))
-								.collect::<Vec<(isize,isize)>>()
-						})
-						.filter(|&(x,y)| x != 0 || y != 0)
-						.map(|(x,y)|
-						{
-							(1..)
-								.map(|f| (x*f+(i as isize),y*f+(j as isize)))
-								.take_while(|&(i,j)| i >= 0 && j >= 0 && i < self.vec.len() as isize && self.vec.get(i as usize).map(|v| j < v.len() as isize).unwrap_or(false))
-								.flat_map(|(i,j)| self.vec.get(i as usize).and_then(|v| v.get(j as usize)))
-								.copied()
-								.find(Option::is_some)
-								.unwrap_or(None)
-						})
-						.filter(|&seat| seat.unwrap_or(false))
-						.count();
-					let new = match (current,count)
-					{
-						(false, 0) => true,
-						(true, 5..=10) => false,
-						(x,_) => x,
-					};
-					inner.push(Some(new));
-				}
-				else
-				{
-					inner.push(None);
-					continue;
-				}
-			}
-			outer.push(inner);
-		}
-		State
+		Ok(match ch
 		{
-			vec: outer,
-		}
-	}
-
-	fn count(&self) -> usize
-	{
-		self.vec.iter().map(|vec| vec.iter().filter(|opt| opt.unwrap_or(false)).count()).sum()
-	}
-}
-
-impl std::str::FromStr for State
-{
-	type Err = Error;
-	fn from_str(input: &str) -> std::result::Result<Self, Error>
-	{
-		let vec = input.lines()
-			.map(|line|
-			{
-				line.chars()
-					.map(|ch| Ok(match ch
-					{
-						'.' => None,
-						'L' => Some(false),
-						'#' => Some(true),
-						_ => bail!(Error::AocParsing),
-					}))
-					.collect::<Result<Vec<_>>>()
-			})
-			.collect::<Result<Vec<Vec<_>>>>()?;
-
-		Ok(State
-		{
-			vec,
+			'.' => Tile::Floor,
+			'L' => Tile::Empty,
+			'#' => Tile::Occupied,
+			_ => Err(anyhow!("cannot convert {:?} to Tile", ch))?,
 		})
 	}
 }
@@ -132,20 +69,41 @@ impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
 	{
-		let mut state = self.input.parse::<State>()?;
+		debug!("called with input: {}", self.input);
 
-		loop
-		{
-			let new = state.step();
-			if new == state
-			{
-				return Ok(format!("{}", state.count()));
-			}
-			else
+		let layout = self.input.parse::<Grid<Tile>>()?;
+		let seats = layout.iter()
+			.filter(|(_, &tile)| tile != Tile::Floor)
+			.map(|(pos, &tile)| (pos, tile))
+			.collect::<Vec<_>>();
+		let occupied = seats.iter()
+			.filter(|(_, tile)| *tile == Tile::Occupied)
+			.map(|&(pos, _)| pos);
+
+		// the first seat visible in each of the eight directions
+		let visible = seats.iter()
+			.map(|&(pos, _)|
 			{
-				state = new;
-			}
+				let visible = NEIGHBOURS8.iter()
+					.filter_map(|&vector|
+					{
+						std::iter::successors(offset(pos, vector), |&pos| offset(pos, vector))
+							.take_while(|&pos| layout.contains(pos))
+							.find(|&pos| layout[pos] != Tile::Floor)
+					})
+					.collect::<Vec<_>>();
+				(pos, visible)
+			})
+			.collect::<std::collections::HashMap<_, _>>();
+
+		let mut automaton = Automaton::bounded(occupied, seats.iter().map(|&(pos, _)| pos), |pos: &Position| visible[pos].clone(), life([0], 0..5));
+		let mut rounds = 0;
+		while automaton.step()
+		{
+			rounds += 1;
 		}
+		debug!("stable after {} rounds", rounds);
+
+		Ok(format!("{}", automaton.population().ok_or(Error::AocNoSolution)?))
 	}
 }
-
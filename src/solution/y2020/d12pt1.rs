@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::grid::Direction;
+
 /// # Examples
 ///
 /// ```
@@ -44,7 +46,7 @@ impl Ship
 		{
 			x: 0,
 			y: 0,
-			direction: Direction::East,
+			direction: Direction::Right,
 		}
 	}
 
@@ -54,7 +56,7 @@ impl Ship
 		{
 			Action::Directional(dir,number) =>
 			{
-				let (x,y) = dir.coords();
+				let (x,y) = dir.vector();
 				self.x += x * number;
 				self.y += y * number;
 			},
@@ -69,30 +71,6 @@ impl Ship
 	}
 }
 
-#[derive(Copy,Clone,Debug,Eq,PartialEq)]
-enum Direction
-{
-	North,
-	East,
-	West,
-	South,
-}
-
-impl Direction
-{
-	fn coords(&self) -> (isize,isize)
-	{
-		use Direction::*;
-		match self
-		{
-			North => (0,1),
-			East => (1,0),
-			South => (0,-1),
-			West => (-1,0),
-		}
-	}
-}
-
 #[derive(Clone,Debug,Eq,PartialEq)]
 enum Action
 {
@@ -109,20 +87,7 @@ impl Action
 		{
 			Action::Rotate(by) =>
 			{
-				match ((by + match direction
-					{
-						Direction::North => 0,
-						Direction::East => 90,
-						Direction::South => 180,
-						Direction::West => 270,
-					}) % 360 + 360) % 360
-				{
-					0 => Direction::North,
-					90 => Direction::East,
-					180 => Direction::South,
-					270 => Direction::West,
-					_ => unreachable!(),
-				}
+				(0..(by / 90).rem_euclid(4)).fold(direction, |direction, _| direction.right())
 			},
 			_ => panic!("rotating something not a Rotate"),
 		}
@@ -143,10 +108,10 @@ impl std::str::FromStr for Action
 
 		Ok(match ch
 		{
-			Some('N') => Directional(North,number),
-			Some('E') => Directional(East,number),
-			Some('S') => Directional(South,number),
-			Some('W') => Directional(West,number),
+			Some('N') => Directional(Up,number),
+			Some('E') => Directional(Right,number),
+			Some('S') => Directional(Down,number),
+			Some('W') => Directional(Left,number),
 			Some('F') => Forward(number),
 			Some('L') => Rotate(-number),
 			Some('R') => Rotate(number),
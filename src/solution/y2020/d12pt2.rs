@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::grid::Direction;
+
 /// # Examples
 ///
 /// ```
@@ -46,7 +48,7 @@ impl Ship
 			x: 0,
 			y: 0,
 			way_x: 10,
-			way_y: 1,
+			way_y: -1,
 		}
 	}
 
@@ -56,7 +58,7 @@ impl Ship
 		{
 			Action::Directional(dir,number) =>
 			{
-				let (x,y) = dir.coords();
+				let (x,y) = dir.vector();
 				self.way_x += x * number;
 				self.way_y += y * number;
 			},
@@ -71,7 +73,7 @@ impl Ship
 				for _ in 0..times
 				{
 					std::mem::swap(&mut self.way_x, &mut self.way_y);
-					self.way_y = -self.way_y;
+					self.way_x = -self.way_x;
 				}
 			}
 		}
@@ -83,30 +85,6 @@ impl Ship
 	}
 }
 
-#[derive(Copy,Clone,Debug,Eq,PartialEq)]
-enum Direction
-{
-	North,
-	East,
-	West,
-	South,
-}
-
-impl Direction
-{
-	fn coords(&self) -> (isize,isize)
-	{
-		use Direction::*;
-		match self
-		{
-			North => (0,1),
-			East => (1,0),
-			South => (0,-1),
-			West => (-1,0),
-		}
-	}
-}
-
 #[derive(Clone,Debug,Eq,PartialEq)]
 enum Action
 {
@@ -129,10 +107,10 @@ impl std::str::FromStr for Action
 
 		Ok(match ch
 		{
-			Some('N') => Directional(North,number),
-			Some('E') => Directional(East,number),
-			Some('S') => Directional(South,number),
-			Some('W') => Directional(West,number),
+			Some('N') => Directional(Up,number),
+			Some('E') => Directional(Right,number),
+			Some('S') => Directional(Down,number),
+			Some('W') => Directional(Left,number),
 			Some('F') => Forward(number),
 			Some('L') => Rotate(-number/90%4),
 			Some('R') => Rotate(number/90%4),
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::number::crt;
+
 /// # Examples
 ///
 /// ```
@@ -25,74 +27,26 @@ impl Solution
 	}
 }
 
-#[derive(Clone,Debug)]
-struct Bus
-{
-	offset: u128,
-	step_size: u128,
-}
-
-impl Bus
-{
-	fn new(offset: u128, id: u128) -> Self
-	{
-		Self
-		{
-			offset,
-			step_size: id,
-		}
-	}
-
-	fn contains(&self, number: u128) -> bool
-	{
-		number % self.step_size == 0
-	}
-}
-
 impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
 	{
 		let mut lines = self.input.lines();
 		let _ = lines.next().ok_or(Error::AocParsing)?;
-		let mut busses = lines.next()
+		let busses = lines.next()
 			.ok_or(Error::AocParsing)?
 			.split(',')
 			.enumerate()
 			.filter(|&(_,id)| id != "x")
-			.map(|(idx,id)| Ok(Bus::new(idx as u128,id.parse()?)))
+			.map(|(idx,id)| Ok((idx as i128,id.parse::<i128>()?)))
 			.collect::<Result<Vec<_>>>()?;
 
-		busses.sort_by_key(|bus| bus.step_size);
-		let mut new_busses = busses.clone();
+		debug!("busses (offset, id): {:?}", busses);
 
-		let mut output_timer = std::time::Instant::now();
+		// bus departs at time + offset, i.e. time is congruent to -offset modulo the bus id
+		let (time, _) = crt(busses.iter().map(|&(offset,id)| (-offset,id)))
+			.ok_or(Error::AocNoSolution)?;
 
-		let mut step_size = 1;
-		let mut base_time = 1;
-		loop
-		{
-			if busses.iter().all(|bus| bus.contains(base_time + bus.offset))
-			{
-				return Ok(format!("{}", base_time));
-			}
-
-			while let Some(idx) = new_busses.iter().position(|bus| bus.contains(base_time + bus.offset))
-			{
-				let bus = new_busses.swap_remove(idx);
-				step_size = num::integer::lcm(bus.step_size,step_size);
-			}
-
-			base_time += step_size;
-
-			if output_timer.elapsed().as_secs() > 1
-			{
-				debug!("step_size: {}", step_size);
-				debug!("base_time: {}", base_time);
-				debug!("new_busses: {}", new_busses.len());
-				output_timer = std::time::Instant::now();
-			}
-		}
+		Ok(format!("{}", time))
 	}
 }
-
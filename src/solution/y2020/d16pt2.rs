@@ -1,5 +1,11 @@
 use crate::error::*;
 
+use super::super::common::
+{
+	interval::IntervalSet,
+	matching::unique_matching,
+};
+
 /// # Examples
 ///
 /// ```
@@ -37,14 +43,14 @@ impl Solution
 #[derive(Clone,Debug)]
 struct Rule
 {
-	ranges: Vec<std::ops::RangeInclusive<usize>>,
+	ranges: IntervalSet<usize>,
 }
 
 impl Rule
 {
 	fn validate(&self, number: usize) -> bool
 	{
-		self.ranges.iter().any(|range| range.contains(&number))
+		self.ranges.contains(&number)
 	}
 }
 
@@ -65,7 +71,7 @@ impl std::str::FromStr for Rule
 						{
 							bail!(Error::AocParsing);
 						}
-						Ok(vec[0]..=vec[1])
+						Ok(vec[0]..(vec[1] + 1))
 					})
 			})
 			.collect::<Result<_>>().context("parsing for rule")?;
@@ -102,51 +108,28 @@ impl super::super::Solution for Solution
 
 		let my_ticket = parts.next().ok_or(Error::AocParsing)?.lines().nth(1).ok_or(Error::AocParsing)?.parse::<Ticket>()?;
 
+		let valid = rules.values()
+			.fold(IntervalSet::new(), |valid, rule| valid.union(&rule.ranges));
+
 		let tickets = parts.next().ok_or(Error::AocParsing)?.lines().skip(1)
 			.map(|line| Ok(line.parse::<Ticket>()?))
 			.collect::<Result<Vec<Ticket>>>()?
 			.into_iter()
-			.filter(|ticket| ticket.0.iter().all(|&num| rules.values().any(|rule| rule.validate(num))))
+			.filter(|ticket| ticket.0.iter().all(|num| valid.contains(num)))
 			.collect::<Vec<_>>();
 
-		let mut possibilities = rules.iter()
-			.flat_map(|kv|
+		let candidates = rules.iter()
+			.map(|(&name, rule)|
 			{
-				(0..rules.len())
-					.map(
-					{
-						move |i| (i,kv)
-					})
+				let columns = (0..rules.len())
+					.filter(|&idx| tickets.iter().all(|ticket| ticket.0.get(idx).map(|&value| rule.validate(value)).unwrap_or(false)))
+					.collect::<std::collections::BTreeSet<_>>();
+				(name, columns)
 			})
-			.filter(|&(idx,(_,rule))| tickets.iter().all(|ticket| ticket.0.get(idx).map(|&value| rule.validate(value)).unwrap_or(false)))
-			.map(|(idx,(name,_))| (name,idx))
-			.collect::<Vec<_>>();
-
-		let mut mapping = std::collections::HashMap::new();
-
-		loop
-		{
-			if possibilities.is_empty()
-			{
-				break;
-			}
-			let map = possibilities.iter().map(|(_,idx)| idx).copied()
-				.fold(std::collections::BTreeMap::<usize,usize>::new(), |mut map, value|
-				{
-					map.entry(value)
-						.and_modify(|value| *value += 1)
-						.or_insert(1);
-					map
-				});
-			for (key,_) in map.into_iter().filter(|&(_,value)| value == 1)
-			{
-				let (name,_) = possibilities.iter().cloned().find(|(_,idx)| *idx == key).unwrap();
-				mapping.insert(name, key);
-				possibilities.retain(|e| e.1 != key && e.0 != name);
+			.collect::<std::collections::BTreeMap<_, _>>();
 
-				println!("{}: {}", name, key);
-			}
-		}
+		let mapping = unique_matching(&candidates)?;
+		debug!("mapping: {:?}", mapping);
 
 		Ok(format!("{}", mapping.into_iter().filter(|(name,_)| name.starts_with("departure")).map(|(_,idx)| Ok(my_ticket.0.get(idx).ok_or(Error::AocParsing)?)).collect::<Result<Vec<_>>>()?.into_iter().product::<usize>()))
 	}
@@ -0,0 +1,93 @@
+use crate::error::*;
+
+use super::super::common::automaton::{ life, moore, Automaton };
+
+use std::convert::TryFrom;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::
+/// # {
+/// #     y2020::D17 as Solution,
+/// #     Solution as S,
+/// # };
+/// # env_logger::init();
+/// let input = ".#.\n\
+///     ..#\n\
+///     ####\n";
+/// assert_eq!(Solution::new(input.to_string(), 3).solve().expect("1"), "112");
+/// assert_eq!(Solution::new(input.to_string(), 4).solve().expect("1"), "848");
+/// assert!(Solution::new(input.to_string(), 41).solve().is_err());
+/// ```
+pub struct Solution
+{
+	input: String,
+	dimensions: usize,
+}
+
+impl Solution
+{
+	pub fn new(input: String, dimensions: usize) -> Self
+	{
+		Self { input, dimensions, }
+	}
+
+	/// The number of dimensions, which defaults to 3 for part one and 4 for part two.
+	pub fn args(part: usize) -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("dimensions")
+				.value_name("DIMENSIONS")
+				.short('d')
+				.long("dimensions")
+				.help("number of dimensions of the pocket dimension")
+				.value_parser(clap::value_parser!(usize))
+				.default_value(if part == 1 { "3" } else { "4" }),
+		]
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		if self.dimensions < 2
+		{
+			bail!("the slice needs at least two dimensions, got {}", self.dimensions);
+		}
+
+		let dimensions = self.dimensions;
+		let cubes = self.input.lines()
+			.enumerate()
+			.flat_map(|(y, line)|
+			{
+				line.chars()
+					.enumerate()
+					.filter(|&(_, ch)| ch == '#')
+					.map(move |(x, _)|
+					{
+						let mut cube = vec![0; dimensions];
+						cube[0] = x as isize;
+						cube[1] = y as isize;
+						cube
+					})
+			})
+			.collect::<Vec<_>>();
+
+		let neighbours = u32::try_from(dimensions).ok()
+			.and_then(|exponent| 3usize.checked_pow(exponent))
+			.with_context(|| format!("{} dimensions have too many neighbours to count", dimensions))? - 1;
+		let mut automaton = Automaton::new(cubes, neighbours, |cube: &Vec<isize>| moore(cube), life([3], [2, 3]));
+		for cycle in 1..=6
+		{
+			automaton.step();
+			debug!("{} active cubes after cycle {}", automaton.cells().len(), cycle);
+		}
+
+		Ok(format!("{}", automaton.population().ok_or(Error::AocNoSolution)?))
+	}
+}
@@ -0,0 +1,300 @@
+use crate::error::*;
+
+use num::{ BigInt, Zero };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::
+/// # {
+/// #     y2020::D18 as Solution,
+/// #     Solution as S,
+/// # };
+/// let same = "+*".parse().unwrap();
+/// assert_eq!(Solution::new("2 * 3 + (4 * 5)".to_string(), same).solve().expect("1"), "26");
+/// let same = "+*".parse().unwrap();
+/// assert_eq!(Solution::new("5 + (8 * 3 + 9 + 3 * 4 * 3)".to_string(), same).solve().expect("2"), "437");
+/// let same = "+*".parse().unwrap();
+/// assert_eq!(Solution::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))".to_string(), same).solve().expect("3"), "12240");
+/// let same = "+*".parse().unwrap();
+/// assert_eq!(Solution::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2".to_string(), same).solve().expect("4"), "13632");
+///
+/// let addition_first = "+>*".parse().unwrap();
+/// assert_eq!(Solution::new("1 + (2 * 3) + (4 * (5 + 6))".to_string(), addition_first).solve().expect("1"), "51");
+/// let addition_first = "+>*".parse().unwrap();
+/// assert_eq!(Solution::new("2 * 3 + (4 * 5)".to_string(), addition_first).solve().expect("2"), "46");
+/// let addition_first = "+>*".parse().unwrap();
+/// assert_eq!(Solution::new("5 + (8 * 3 + 9 + 3 * 4 * 3)".to_string(), addition_first).solve().expect("3"), "1445");
+/// let addition_first = "+>*".parse().unwrap();
+/// assert_eq!(Solution::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))".to_string(), addition_first).solve().expect("4"), "669060");
+/// let addition_first = "+>*".parse().unwrap();
+/// assert_eq!(Solution::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2 ".to_string(), addition_first).solve().expect("4"), "23340");
+/// ```
+pub struct Solution
+{
+	input: String,
+	precedence: Precedence,
+}
+
+impl Solution
+{
+	pub fn new(input: String, precedence: Precedence) -> Self
+	{
+		Self { input, precedence, }
+	}
+
+	/// The operator precedence, which defaults to `+*` for part one and `+>*` for part two.
+	pub fn args(part: usize) -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("precedence")
+				.value_name("PRECEDENCE")
+				.short('p')
+				.long("precedence")
+				.help("operators from tightest to loosest binding, levels separated by '>', 'r' marking a right associative level")
+				.value_parser(|s: &str| s.parse::<Precedence>().map_err(|err| match err
+				{
+					Error::Anyhow(err) => err.to_string(),
+					err => err.to_string(),
+				}))
+				.default_value(if part == 1 { "+*" } else { "+>*" }),
+		]
+	}
+}
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Associativity
+{
+	Left,
+	Right,
+}
+
+/// The binary operators with their precedence and associativity.
+///
+/// Written as levels from the tightest to the loosest binding separated by `>`, every level
+/// listing its operators, with a trailing `r` for right associative ones.
+/// The supported operators are `+`, `-`, `*` and `/`, the latter rounding towards zero.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2020::d18::Precedence;
+/// let usual: Precedence = "*/>+-".parse().unwrap();
+/// let expression = usual.parse("1 + 2 * 3 - 8 / 4 / 2").unwrap();
+/// assert_eq!(expression.to_string(), "((1 + (2 * 3)) - ((8 / 4) / 2))");
+/// assert_eq!(expression.evaluate().unwrap(), 6.into());
+///
+/// let right: Precedence = "-r".parse().unwrap();
+/// assert_eq!(right.parse("8 - 3 - 2").unwrap().to_string(), "(8 - (3 - 2))");
+///
+/// // no overflow, no matter how large the numbers get
+/// let big = usual.parse("99999999999 * 99999999999 * 99999999999").unwrap().evaluate().unwrap();
+/// assert_eq!(big.to_string(), "999999999970000000000299999999999");
+///
+/// assert!(usual.parse("1 / (2 - 2)").unwrap().evaluate().is_err());
+/// assert!(usual.parse("1 + (2").is_err());
+/// assert!(usual.parse("1 ^ 2").is_err());
+/// assert!("+>+".parse::<Precedence>().is_err());
+/// assert!("^".parse::<Precedence>().is_err());
+/// ```
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Precedence
+{
+	/// Every operator with its level, counting from the loosest binding one.
+	operators: Vec<(char, usize, Associativity)>,
+}
+
+impl std::str::FromStr for Precedence
+{
+	type Err = Error;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let levels = input.split('>').collect::<Vec<_>>();
+		let mut operators = Vec::new();
+		for (idx, level) in levels.iter().enumerate()
+		{
+			let (level, associativity) = match level.strip_suffix('r')
+			{
+				Some(level) => (level, Associativity::Right),
+				None => (*level, Associativity::Left),
+			};
+			if level.is_empty()
+			{
+				Err(anyhow!("empty precedence level in {:?}", input))?;
+			}
+			for op in level.chars()
+			{
+				if !"+-*/".contains(op)
+				{
+					Err(anyhow!("unsupported operator {:?}", op))?;
+				}
+				if operators.iter().any(|&(other, _, _)| other == op)
+				{
+					Err(anyhow!("operator {:?} is given twice", op))?;
+				}
+				operators.push((op, levels.len() - 1 - idx, associativity));
+			}
+		}
+		Ok(Precedence { operators, })
+	}
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum Expression
+{
+	Number(BigInt),
+	Binary(char, Box<Expression>, Box<Expression>),
+}
+
+/// Fully parenthesized, so the structure is visible regardless of precedence.
+impl std::fmt::Display for Expression
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Expression::Number(number) => write!(f, "{}", number),
+			Expression::Binary(op, lhs, rhs) => write!(f, "({} {} {})", lhs, op, rhs),
+		}
+	}
+}
+
+impl Expression
+{
+	pub fn evaluate(&self) -> Result<BigInt>
+	{
+		match self
+		{
+			Expression::Number(number) => Ok(number.clone()),
+			Expression::Binary(op, lhs, rhs) =>
+			{
+				let (lhs, rhs) = (lhs.evaluate()?, rhs.evaluate()?);
+				Ok(match op
+				{
+					'+' => lhs + rhs,
+					'-' => lhs - rhs,
+					'*' => lhs * rhs,
+					'/' =>
+					{
+						anyhow::ensure!(!rhs.is_zero(), "division of {} by zero", lhs);
+						lhs / rhs
+					},
+					_ => bail!("unsupported operator {:?}", op),
+				})
+			},
+		}
+	}
+}
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+enum Token<'a>
+{
+	Number(&'a str),
+	Operator(char),
+	Open,
+	Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>>
+{
+	let mut tokens = Vec::new();
+	let mut rest = input.trim_start();
+	while let Some(ch) = rest.chars().next()
+	{
+		let (token, len) = match ch
+		{
+			'0'..='9' =>
+			{
+				let len = rest.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len());
+				(Token::Number(&rest[..len]), len)
+			},
+			'(' => (Token::Open, 1),
+			')' => (Token::Close, 1),
+			ch if ch.is_ascii_punctuation() => (Token::Operator(ch), 1),
+			ch => bail!("unexpected {:?} at {}", ch, input.len() - rest.len()),
+		};
+		tokens.push(token);
+		rest = rest[len..].trim_start();
+	}
+	Ok(tokens)
+}
+
+impl Precedence
+{
+	/// The binding power on the left and right of an operator, tighter levels binding more.
+	fn binding(&self, op: char) -> Option<(usize, usize)>
+	{
+		self.operators.iter()
+			.find(|&&(other, _, _)| other == op)
+			.map(|&(_, level, associativity)| match associativity
+			{
+				Associativity::Left => (2 * level + 1, 2 * level + 2),
+				Associativity::Right => (2 * level + 2, 2 * level + 1),
+			})
+	}
+
+	/// Parses the tokens as long as operators bind at least as tight as `min`.
+	fn expression<'a, I>(&self, tokens: &mut std::iter::Peekable<I>, min: usize) -> Result<Expression>
+		where
+			I: Iterator<Item=Token<'a>>,
+	{
+		let mut lhs = match tokens.next()
+		{
+			Some(Token::Number(number)) => Expression::Number(number.parse()?),
+			Some(Token::Open) =>
+			{
+				let inner = self.expression(tokens, 0)?;
+				anyhow::ensure!(tokens.next() == Some(Token::Close), "missing closing parenthesis");
+				inner
+			},
+			token => bail!("expected a number or an opening parenthesis, got {:?}", token),
+		};
+
+		while let Some(&Token::Operator(op)) = tokens.peek()
+		{
+			let (left, right) = self.binding(op).ok_or_else(|| anyhow!("unknown operator {:?}", op))?;
+			if left < min
+			{
+				break;
+			}
+			tokens.next();
+			let rhs = self.expression(tokens, right)?;
+			lhs = Expression::Binary(op, Box::new(lhs), Box::new(rhs));
+		}
+
+		Ok(lhs)
+	}
+
+	pub fn parse(&self, input: &str) -> Result<Expression>
+	{
+		let mut tokens = tokenize(input)?.into_iter().peekable();
+		let expression = self.expression(&mut tokens, 0)?;
+		if let Some(token) = tokens.next()
+		{
+			bail!("unexpected {:?} after expression", token);
+		}
+		Ok(expression)
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let result = self.input.lines()
+			.filter(|line| !line.trim().is_empty())
+			.map(|line|
+			{
+				let expression = self.precedence.parse(line)?;
+				trace!("{} parsed as {}", line, expression);
+				expression.evaluate()
+			})
+			.sum::<Result<BigInt>>()?;
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,323 @@
+use crate::error::*;
+
+use std::collections::{ BTreeMap, HashSet };
+
+use nom::
+{
+	character::complete::*,
+	bytes::complete::*,
+	combinator::*,
+	sequence::*,
+	branch::*,
+	multi::*,
+	IResult,
+};
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::
+/// # {
+/// #     y2020::D19 as Solution,
+/// #     Solution as S,
+/// # };
+/// # env_logger::init();
+/// let example = "\
+///     0: 4 1 5\n\
+///     1: 2 3 | 3 2\n\
+///     2: 4 4 | 5 5\n\
+///     3: 4 5 | 5 4\n\
+///     4: \"a\"\n\
+///     5: \"b\"\n\
+///     \n\
+///     ababbb\n\
+///     bababa\n\
+///     abbbab\n\
+///     aaabbb\n\
+///     aaaabbb";
+/// assert_eq!(Solution::new(example.to_string(), Vec::new()).solve().expect("1"), "2");
+///
+/// let example = "\
+///     42: 9 14 | 10 1\n\
+///     9: 14 27 | 1 26\n\
+///     10: 23 14 | 28 1\n\
+///     1: \"a\"\n\
+///     11: 42 31\n\
+///     5: 1 14 | 15 1\n\
+///     19: 14 1 | 14 14\n\
+///     12: 24 14 | 19 1\n\
+///     16: 15 1 | 14 14\n\
+///     31: 14 17 | 1 13\n\
+///     6: 14 14 | 1 14\n\
+///     2: 1 24 | 14 4\n\
+///     0: 8 11\n\
+///     13: 14 3 | 1 12\n\
+///     15: 1 | 14\n\
+///     17: 14 2 | 1 7\n\
+///     23: 25 1 | 22 14\n\
+///     28: 16 1\n\
+///     4: 1 1\n\
+///     20: 14 14 | 1 15\n\
+///     3: 5 14 | 16 1\n\
+///     27: 1 6 | 14 18\n\
+///     14: \"b\"\n\
+///     21: 14 1 | 1 14\n\
+///     25: 1 1 | 1 14\n\
+///     22: 14 14\n\
+///     8: 42\n\
+///     26: 14 22 | 1 20\n\
+///     18: 15 15\n\
+///     7: 14 5 | 1 21\n\
+///     24: 14 1\n\
+///     \n\
+///     abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa\n\
+///     bbabbbbaabaabba\n\
+///     babbbbaabbbbbabbbbbbaabaaabaaa\n\
+///     aaabbbbbbaaaabaababaabababbabaaabbababababaaa\n\
+///     bbbbbbbaaaabbbbaaabbabaaa\n\
+///     bbbababbbbaaaaaaaabbababaaababaabab\n\
+///     ababaaaaaabaaab\n\
+///     ababaaaaabbbaba\n\
+///     baabbaaaabbaaaababbaababb\n\
+///     abbbbabbbbaaaababbbbbbaaaababb\n\
+///     aaaaabbaabaaaaababaa\n\
+///     aaaabbaaaabbaaa\n\
+///     aaaabbaabbaaaaaaabbbabbbaaabbaabaaa\n\
+///     babaaabbbaaabaababbaabababaaab\n\
+///     aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+/// assert_eq!(Solution::new(example.to_string(), Vec::new()).solve().expect("1"), "3");
+/// let loops = vec!["8: 42 | 42 8".to_string(), "11: 42 31 | 42 11 31".to_string()];
+/// assert_eq!(Solution::new(example.to_string(), loops).solve().expect("1"), "12");
+/// ```
+pub struct Solution
+{
+	input: String,
+	replacements: Vec<String>,
+}
+
+impl Solution
+{
+	/// Validates the messages after replacing rules by the given ones, like `8: 42 | 42 8`.
+	pub fn new(input: String, replacements: Vec<String>) -> Self
+	{
+		Self { input, replacements, }
+	}
+
+	/// The rule replacements, which default to the loops of rules 8 and 11 for part two.
+	pub fn args(part: usize) -> Vec<clap::Arg>
+	{
+		let replace = clap::Arg::new("replace")
+			.value_name("RULE")
+			.short('r')
+			.long("replace")
+			.help("replace a rule before validating, like \"8: 42 | 42 8\"")
+			.action(clap::ArgAction::Append);
+		vec!
+		[
+			if part == 1 { replace } else { replace.default_values(["8: 42 | 42 8", "11: 42 31 | 42 11 31"]) },
+		]
+	}
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+enum Symbol
+{
+	Char(char),
+	Rule(usize),
+}
+
+/// A context-free grammar with numbered rules, each having one or more alternative sequences of
+/// characters and rules.
+///
+/// Messages are matched with an Earley parser, so rules may refer to each other in any way,
+/// including left recursion.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2020::d19::Grammar;
+/// // non-empty sequences of balanced parentheses
+/// let grammar: Grammar = "0: 1 | 0 1\n1: \"()\" | \"(\" 0 \")\"".parse().unwrap();
+/// assert!(grammar.matches(0, "(()())()"));
+/// assert!(!grammar.matches(0, "(()"));
+/// assert!(!grammar.matches(0, ""));
+///
+/// // compared with counting the depth for every string of up to ten parentheses
+/// for length in 1..=10
+/// {
+///     for bits in 0..(1 << length)
+///     {
+///         let input = (0..length).map(|i| if bits >> i & 1 == 1 { '(' } else { ')' }).collect::<String>();
+///         let depths = input.chars().scan(0, |depth, ch| { *depth += if ch == '(' { 1 } else { -1 }; Some(*depth) }).collect::<Vec<_>>();
+///         let balanced = depths.iter().all(|&depth| depth >= 0) && depths.last() == Some(&0);
+///         assert_eq!(grammar.matches(0, &input), balanced, "{}", input);
+///     }
+/// }
+///
+/// // rules can be replaced, but never refer to missing ones
+/// let mut grammar = grammar;
+/// grammar.replace("1: \"[]\" | \"[\" 0 \"]\"").unwrap();
+/// assert!(grammar.matches(0, "[[][]]"));
+/// assert!(grammar.replace("1: 2").is_err());
+/// assert!("0: 1".parse::<Grammar>().is_err());
+/// ```
+#[derive(Clone,Debug)]
+pub struct Grammar
+{
+	rules: BTreeMap<usize,Vec<Vec<Symbol>>>,
+}
+
+fn number(input: &str) -> IResult<&str,usize>
+{
+	map_res(recognize(digit1), str::parse)(input)
+}
+
+fn symbols(input: &str) -> IResult<&str,Vec<Symbol>>
+{
+	alt
+	((
+		map(number, |id| vec![Symbol::Rule(id)]),
+		map(delimited(char('"'), many1(none_of("\"")), char('"')), |chars| chars.into_iter().map(Symbol::Char).collect()),
+	))(input)
+}
+
+fn rule(input: &str) -> IResult<&str,(usize,Vec<Vec<Symbol>>)>
+{
+	let (input,id) = number(input)?;
+	let (input,_) = tag(": ")(input)?;
+	let (input,alternatives) = separated_list1(tag(" | "), map(separated_list1(char(' '), symbols), |vec| vec.concat()))(input)?;
+	let (input,_) = eof(input)?;
+
+	Ok((input,(id,alternatives)))
+}
+
+impl std::str::FromStr for Grammar
+{
+	type Err = Error;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let rules = input.lines()
+			.inspect(|line| debug!("got rule: {}", line))
+			.map(|line| Ok(rule(line).map_err(|err| anyhow!("{}", err)).context(Error::AocParsing)?.1))
+			.collect::<Result<BTreeMap<_,_>>>()?;
+		let grammar = Grammar { rules, };
+		grammar.check()?;
+		Ok(grammar)
+	}
+}
+
+impl Grammar
+{
+	fn check(&self) -> Result<()>
+	{
+		let missing = self.rules.iter()
+			.flat_map(|(id, alternatives)| alternatives.iter().flatten().map(move |symbol| (id, symbol)))
+			.find(|(_, symbol)| match symbol { Symbol::Rule(other) => !self.rules.contains_key(other), Symbol::Char(_) => false, });
+		if let Some((id, symbol)) = missing
+		{
+			bail!("rule {} refers to missing {:?}", id, symbol);
+		}
+		Ok(())
+	}
+
+	/// Replaces a rule, or adds it if it does not exist yet.
+	pub fn replace(&mut self, line: &str) -> Result<()>
+	{
+		let (_, (id, alternatives)) = rule(line).map_err(|err| anyhow!("{}", err)).context(Error::AocParsing)?;
+		let previous = self.rules.insert(id, alternatives);
+		if let Err(err) = self.check()
+		{
+			match previous
+			{
+				Some(previous) => self.rules.insert(id, previous),
+				None => self.rules.remove(&id),
+			};
+			return Err(err);
+		}
+		Ok(())
+	}
+
+	/// Whether the whole input can be derived from the start rule.
+	pub fn matches(&self, start: usize, input: &str) -> bool
+	{
+		let input = input.chars().collect::<Vec<_>>();
+		// an item is a rule, one of its alternatives, how many of its symbols matched and where
+		// the match started
+		let mut sets: Vec<Vec<(usize, usize, usize, usize)>> = vec![Vec::new(); input.len() + 1];
+		let mut seen: Vec<HashSet<(usize, usize, usize, usize)>> = vec![HashSet::new(); input.len() + 1];
+		let mut add = |sets: &mut Vec<Vec<_>>, pos: usize, item|
+		{
+			if seen[pos].insert(item)
+			{
+				sets[pos].push(item);
+			}
+		};
+
+		for alternative in 0..self.rules.get(&start).map(Vec::len).unwrap_or(0)
+		{
+			add(&mut sets, 0, (start, alternative, 0, 0));
+		}
+		for pos in 0..=input.len()
+		{
+			let mut idx = 0;
+			while idx < sets[pos].len()
+			{
+				let (id, alternative, dot, origin) = sets[pos][idx];
+				idx += 1;
+				match self.rules[&id][alternative].get(dot)
+				{
+					// completion: advance everything waiting for this rule where it started
+					None =>
+					{
+						let waiting = sets[origin].iter()
+							.filter(|&&(other, alt, dot, _)| self.rules[&other][alt].get(dot) == Some(&Symbol::Rule(id)))
+							.map(|&(other, alt, dot, start)| (other, alt, dot + 1, start))
+							.collect::<Vec<_>>();
+						waiting.into_iter().for_each(|item| add(&mut sets, pos, item));
+					},
+					Some(&Symbol::Rule(next)) =>
+					{
+						for alternative in 0..self.rules[&next].len()
+						{
+							add(&mut sets, pos, (next, alternative, 0, pos));
+						}
+					},
+					Some(&Symbol::Char(ch)) =>
+					{
+						if input.get(pos) == Some(&ch)
+						{
+							add(&mut sets, pos + 1, (id, alternative, dot + 1, origin));
+						}
+					},
+				}
+			}
+		}
+
+		sets[input.len()].iter()
+			.any(|&(id, alternative, dot, origin)| id == start && origin == 0 && dot == self.rules[&id][alternative].len())
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (rules, messages) = self.input.split_once("\n\n").ok_or(Error::AocParsing)?;
+		let mut grammar = rules.parse::<Grammar>()?;
+		for replacement in self.replacements.iter()
+		{
+			grammar.replace(replacement)?;
+		}
+		anyhow::ensure!(grammar.rules.contains_key(&0), "rule 0 is missing");
+
+		let count = messages.lines()
+			.filter(|line| grammar.matches(0, line))
+			.inspect(|line| debug!("passed validation: {}", line))
+			.count();
+
+		Ok(format!("{}", count))
+	}
+}
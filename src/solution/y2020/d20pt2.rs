@@ -1,9 +1,12 @@
 use crate::error::*;
 
+use std::collections::HashMap;
+
+use super::super::common::grid::Grid;
+
 /// # Examples
 ///
-/// ```no_run
-/// # // FIXME: currently broken
+/// ```
 /// # use adventofcode::solution::
 /// # {
 /// #     y2020::D20Pt2 as Solution,
@@ -118,633 +121,262 @@ use crate::error::*;
 /// #   ..#.......\n\
 ///     ..#.###...\n";
 /// assert_eq!(Solution::new(example.to_string()).solve().expect("1"), "273");
+///
+/// // two sea monsters of fifteen cells each
+/// let image = Solution::new(example.to_string()).image().unwrap();
+/// assert_eq!((image.width(), image.height()), (24, 24));
+/// assert_eq!(image.iter().filter(|&(_, &ch)| ch == 'O').count(), 30);
 /// ```
 pub struct Solution
 {
 	input: String,
+	dump_image: bool,
 }
 
 impl Solution
 {
 	pub fn new(input: String) -> Self
 	{
-		Self { input, }
+		Self { input, dump_image: false, }
 	}
-}
 
-fn get_10array<I: IntoIterator<Item=T>,T>(iter: I) -> Result<[T;10]>
-{
-	let mut iter = iter.into_iter();
-	Ok([
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-		iter.next().ok_or(Error::AocParsing)?,
-	])
-}
-
-#[derive(Clone,Debug,Eq,PartialEq)]
-struct Tile
-{
-	id: usize,
-	grid: [[bool;10];10],
-}
-
-impl Tile
-{
-	fn top(&self) -> [[bool;10];2]
+	/// Prints the assembled image with the sea monsters highlighted.
+	pub fn dump_image(self, dump_image: bool) -> Self
 	{
-		[
-			self.grid[0],
-			get_10array(self.grid[0].iter().copied().rev()).unwrap(),
-		]
+		Self { dump_image, ..self }
 	}
 
-	fn bottom(&self) -> [[bool;10];2]
+	pub fn args() -> Vec<clap::Arg>
 	{
+		vec!
 		[
-			get_10array(self.grid[9].iter().copied().rev()).unwrap(),
-			self.grid[9],
+			clap::Arg::new("dump-image")
+				.long("dump-image")
+				.help("print the assembled image with the sea monsters marked as O")
+				.action(clap::ArgAction::SetTrue),
 		]
 	}
 
-	fn left(&self) -> [[bool;10];2]
+	/// The assembled image without the tile borders, oriented so the sea monsters show up, with
+	/// every cell belonging to a sea monster replaced by `O`.
+	pub fn image(&self) -> Result<Grid<char>>
 	{
-		[
-			get_10array(self.grid.iter().map(|row| row[0]).rev()).unwrap(),
-			get_10array(self.grid.iter().map(|row| row[0])).unwrap(),
-		]
-	}
+		let tiles = self.input.split("\n\n")
+			.map(str::trim)
+			.filter(|s| !s.is_empty())
+			.map(|tile| Ok(tile.parse()?))
+			.collect::<Result<Vec<Tile>>>()?;
+		let image = assemble(&tiles)?;
 
-	fn right(&self) -> [[bool;10];2]
-	{
-		[
-			get_10array(self.grid.iter().map(|row| row[9])).unwrap(),
-			get_10array(self.grid.iter().map(|row| row[9]).rev()).unwrap(),
-		]
-	}
+		let monster: Grid<char> = MONSTER.parse()?;
+		let monster = monster.iter()
+			.filter(|&(_, &ch)| ch == '#')
+			.map(|(pos, _)| pos)
+			.collect::<Vec<_>>();
 
-	fn borders(&self) -> [[bool;10];8]
-	{
-		[
-			self.top()[0],
-			self.top()[1],
-			self.right()[0],
-			self.right()[1],
-			self.bottom()[0],
-			self.bottom()[1],
-			self.left()[0],
-			self.left()[1],
-		]
+		orientations(&image)
+			.into_iter()
+			.find_map(|mut image|
+			{
+				let found = find_monsters(&image, &monster);
+				debug!("found {} sea monsters", found.len());
+				if found.is_empty()
+				{
+					return None;
+				}
+				for (x, y) in found
+				{
+					monster.iter().for_each(|&(dx, dy)| image[(x + dx, y + dy)] = 'O');
+				}
+				Some(image)
+			})
+			.ok_or_else(|| anyhow!("no sea monster in any orientation of the image"))
 	}
+}
+
+const MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+#[derive(Clone,Debug,Eq,PartialEq)]
+struct Tile
+{
+	id: usize,
+	grid: Grid<char>,
+}
 
-	fn flip(&mut self)
+impl std::str::FromStr for Tile
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
-		for vec in &mut self.grid
+		let (header, grid) = input.split_once('\n').ok_or(Error::AocParsing)?;
+		let id = header.strip_prefix("Tile ")
+			.and_then(|header| header.strip_suffix(':'))
+			.ok_or_else(|| anyhow!("invalid tile header {:?}", header))?
+			.parse()?;
+		let grid: Grid<char> = grid.parse()?;
+		if grid.width() != grid.height() || grid.iter().any(|(_, &ch)| ch != '#' && ch != '.')
 		{
-			vec.reverse();
+			Err(anyhow!("tile {} is not a square of '#' and '.'", id))?;
 		}
-	}
-
-	fn rotate(&mut self)
-	{
-		self.grid = get_10array(self.grid[9].iter().copied()
-			.zip(self.grid[8].iter().copied())
-			.zip(self.grid[7].iter().copied())
-			.zip(self.grid[6].iter().copied())
-			.zip(self.grid[5].iter().copied())
-			.zip(self.grid[4].iter().copied())
-			.zip(self.grid[3].iter().copied())
-			.zip(self.grid[2].iter().copied())
-			.zip(self.grid[1].iter().copied())
-			.zip(self.grid[0].iter().copied())
-			.map(|(((((((((it0,it1),it2),it3),it4),it5),it6),it7),it8),it9)| [it0,it1,it2,it3,it4,it5,it6,it7,it8,it9]))
-			.unwrap();
+		Ok(Tile
+		{
+			id,
+			grid,
+		})
 	}
 }
 
-impl super::super::Solution for Solution
+/// All eight ways to rotate and flip a grid.
+fn orientations(grid: &Grid<char>) -> Vec<Grid<char>>
 {
-	fn solve(&self) -> Result<String>
-	{
-		debug!("called with input: {}", self.input);
-
-		let tiles = self.input.split("\n\n")
-			.filter(|s| !s.is_empty())
-			.map(|split|
-			{
-				let mut lines = split.lines();
-				let header = lines.next().ok_or(Error::AocParsing)?;
-				let id = header.chars().skip(5).take(4).collect::<String>().parse()?;
-				let grid = get_10array(lines
-					.map(|line|
-					{
-						get_10array(line.chars().map(|ch| ch == '#'))
-					})
-					.collect::<Result<Vec<_>>>()?)?;
-				Ok(Tile
-				{
-					id,
-					grid,
-				})
-			})
-			.collect::<Result<Vec<Tile>>>()?;
-
-		let map = tiles.iter()
-			.cloned()
-			.flat_map(|tile|
-			{
-				tile.borders()
-					.iter()
-					.cloned()
-					.map(
-					{
-						move |border| (tile.id, border)
-					})
-					.collect::<Vec<_>>()
-			})
-			.fold(std::collections::HashMap::<[bool;10],std::collections::BTreeSet<usize>>::new(), |mut map, (id, border)|
-			{
-				let key = border.min(get_10array(border.iter().rev().copied()).unwrap());
-				map.entry(key).or_default().insert(id);
-				map
-			});
-
-		let unmatched = map.values()
-			.filter_map(|set| if set.len() == 1 { set.iter().next() } else { None })
-			.fold(std::collections::BTreeMap::<usize,usize>::new(), |mut map, &id|
-			{
-				map.entry(id)
-					.and_modify(|val| *val += 1)
-					.or_insert(1);
-				map
-			});
+	[grid.clone(), grid.transpose()].iter()
+		.flat_map(|grid| std::iter::successors(Some(grid.clone()), |grid| Some(grid.rotate_clockwise())).take(4))
+		.collect()
+}
 
-		let corners = unmatched.iter()
-			.filter(|&(_,&count)| count == 2)
-			.map(|(&id,_)| tiles.iter().find(|&tile| tile.id == id).unwrap())
-			.cloned()
-			.collect::<Vec<_>>();
+/// A tile in one of its orientations.
+struct Placement
+{
+	tile: usize,
+	grid: Grid<char>,
+	top: String,
+	bottom: String,
+	left: String,
+	right: String,
+}
 
-		let edges = unmatched.iter()
-			.filter(|&(_,&count)| count == 1)
-			.map(|(&id,_)| tiles.iter().find(|&tile| tile.id == id).unwrap())
-			.cloned()
-			.collect::<Vec<_>>();
+impl Placement
+{
+	fn new(tile: usize, grid: Grid<char>) -> Self
+	{
+		let top = grid.row(0).unwrap().iter().collect();
+		let bottom = grid.row(grid.height() - 1).unwrap().iter().collect();
+		let left = grid.column(0).collect();
+		let right = grid.column(grid.width() - 1).collect();
+		Self { tile, grid, top, bottom, left, right, }
+	}
+}
 
-		debug!("corners: {}", corners.len());
-		debug!("edges: {}", edges.len());
+/// The placements chosen so far, row by row, with every placement indexed by its left and top
+/// border.
+struct Layout<'a>
+{
+	placements: &'a [Placement],
+	by_left: HashMap<&'a str, Vec<usize>>,
+	by_top: HashMap<&'a str, Vec<usize>>,
+	side: usize,
+	used: Vec<bool>,
+	layout: Vec<usize>,
+}
 
-		if corners.len() != 4 || edges.len() != 4
+impl<'a> Layout<'a>
+{
+	fn new(placements: &'a [Placement], side: usize, tiles: usize) -> Self
+	{
+		let mut by_left = HashMap::<_, Vec<_>>::new();
+		let mut by_top = HashMap::<_, Vec<_>>::new();
+		for (idx, placement) in placements.iter().enumerate()
 		{
-			bail!(Error::AocNoSolution);
+			by_left.entry(placement.left.as_str()).or_default().push(idx);
+			by_top.entry(placement.top.as_str()).or_default().push(idx);
 		}
+		Self { placements, by_left, by_top, side, used: vec![false; tiles], layout: Vec::with_capacity(tiles), }
+	}
 
-		let mut big_grid = [[false;30];30];
-
-		let fill_at = |small: &[[bool;10];10],big: &mut [[bool;30];30],offx: isize,offy: isize|
+	/// Fills the rest of the square, backtracking whenever no remaining tile fits its neighbours.
+	fn place(&mut self) -> bool
+	{
+		let side = self.side;
+		if self.layout.len() == side * side
 		{
-			for (y,line) in small.iter().enumerate()
-			{
-				if y as isize + offy < 0 || y + offy as usize >= big.len()
-				{
-					continue;
-				}
-				for (x,v) in line.iter().enumerate()
-				{
-					if x as isize + offx < 0 || x + offx as usize >= big[0].len()
-					{
-						continue;
-					}
-					big[offy as usize+y][offx as usize+x] = *v;
-				}
-			}
-		};
-
-		let middle = tiles.iter().find(|tile| !corners.contains(tile) && !edges.contains(tile)).ok_or(Error::AocNoSolution)?;
-		fill_at(&middle.grid, &mut big_grid, 10, 10);
-
-		let mut top = None;
-		let mut bottom = None;
-
-		for edge in edges.iter()
+			return true;
+		}
+		let (x, y) = (self.layout.len() % side, self.layout.len() / side);
+		let candidates = if x > 0
 		{
-			if let Some(offset) = edge.borders().iter().position(|border| border == &middle.bottom()[0])
-			{
-				let mut edge = edge.clone();
-				if offset % 2 == 0
-				{
-					edge.flip();
-				}
-				let offset = edge.borders().iter().position(|border| border == &middle.bottom()[0]).unwrap();
-				for _ in 0..(offset/2)
-				{
-					edge.rotate();
-				}
-				fill_at(&edge.grid, &mut big_grid, 10, 20);
-				bottom = Some(edge);
-			}
-			if let Some(offset) = edge.borders().iter().position(|border| border == &middle.top()[0])
-			{
-				let mut edge = edge.clone();
-				if offset % 2 == 0
-				{
-					edge.flip();
-				}
-				let offset = edge.borders().iter().position(|border| border == &middle.top()[0]).unwrap();
-				for _ in 0..((offset+4)/2)
-				{
-					edge.rotate();
-				}
-				fill_at(&edge.grid, &mut big_grid, 10, 0);
-				top = Some(edge);
-			}
-			if let Some(offset) = edge.borders().iter().position(|border| border == &middle.right()[0])
-			{
-				let mut edge = edge.clone();
-				if offset % 2 == 0
-				{
-					edge.flip();
-				}
-				let offset = edge.borders().iter().position(|border| border == &middle.right()[0]).unwrap();
-				for _ in 0..((offset+6)/2)
-				{
-					edge.rotate();
-				}
-				fill_at(&edge.grid, &mut big_grid, 20, 10);
-			}
-			if let Some(offset) = edge.borders().iter().position(|border| border == &middle.left()[0])
-			{
-				let mut edge = edge.clone();
-				if offset % 2 == 0
-				{
-					edge.flip();
-				}
-				let offset = edge.borders().iter().position(|border| border == &middle.left()[0]).unwrap();
-				for _ in 0..((offset+6)/2)
-				{
-					edge.rotate();
-				}
-				fill_at(&edge.grid, &mut big_grid, 0, 10);
-			}
+			self.by_left.get(self.placements[self.layout[self.layout.len() - 1]].right.as_str()).cloned().unwrap_or_default()
 		}
+		else if y > 0
+		{
+			self.by_top.get(self.placements[self.layout[self.layout.len() - side]].bottom.as_str()).cloned().unwrap_or_default()
+		}
+		else
+		{
+			(0..self.placements.len()).collect()
+		};
 
-		debug!("{}",big_grid.chunks(10)
-			.map(|chunk|
-			{
-				chunk.iter()
-					.map(|line|
-					{
-						line.chunks(10)
-							.map(|chunk|
-							{
-								chunk.iter()
-									.map(|&b| if b { '#' } else { '.' })
-									.collect::<String>()
-							})
-							.collect::<Vec<String>>()
-							.join(" ")
-					})
-					.collect::<Vec<_>>()
-					.join("\n")
-			})
-			.collect::<Vec<_>>()
-			.join("\n\n"));
-
-		let top = top.ok_or(Error::AocNoSolution)?;
-		let bottom = bottom.ok_or(Error::AocNoSolution)?;
-
-		for corner in corners.iter()
+		for idx in candidates
 		{
-			if let Some(offset) = corner.borders().iter().position(|border| border == &top.left()[0])
-			{
-				let mut corner = corner.clone();
-				if offset % 2 == 0
-				{
-					corner.flip();
-				}
-				let offset = corner.borders().iter().position(|border| border == &top.left()[0]).unwrap();
-				for _ in 0..((offset+6)/2)
-				{
-					corner.rotate();
-				}
-				fill_at(&corner.grid, &mut big_grid, 0, 0);
-			}
-			if let Some(offset) = corner.borders().iter().position(|border| border == &bottom.left()[0])
-			{
-				let mut corner = corner.clone();
-				if offset % 2 == 0
-				{
-					corner.flip();
-				}
-				let offset = corner.borders().iter().position(|border| border == &bottom.left()[0]).unwrap();
-				for _ in 0..((offset+6)/2)
-				{
-					corner.rotate();
-				}
-				fill_at(&corner.grid, &mut big_grid, 0, 20);
-			}
-			if let Some(offset) = corner.borders().iter().position(|border| border == &top.right()[0])
+			let placement = &self.placements[idx];
+			let fits = !self.used[placement.tile]
+				&& (y == 0 || self.placements[self.layout[self.layout.len() - side]].bottom == placement.top);
+			if !fits
 			{
-				let mut corner = corner.clone();
-				if offset % 2 == 0
-				{
-					corner.flip();
-				}
-				let offset = corner.borders().iter().position(|border| border == &top.right()[0]).unwrap();
-				for _ in 0..((offset+2)/2)
-				{
-					corner.rotate();
-				}
-				fill_at(&corner.grid, &mut big_grid, 20, 0);
+				continue;
 			}
-			if let Some(offset) = corner.borders().iter().position(|border| border == &bottom.right()[0])
+			self.used[placement.tile] = true;
+			self.layout.push(idx);
+			if self.place()
 			{
-				let mut corner = corner.clone();
-				if offset % 2 == 0
-				{
-					corner.flip();
-				}
-				let offset = corner.borders().iter().position(|border| border == &bottom.right()[0]).unwrap();
-				for _ in 0..((offset+2)/2)
-				{
-					corner.rotate();
-				}
-				fill_at(&corner.grid, &mut big_grid, 20, 20);
+				return true;
 			}
+			self.layout.pop();
+			self.used[placement.tile] = false;
 		}
+		false
+	}
+}
 
-		debug!("{}",big_grid.chunks(10)
-			.map(|chunk|
-			{
-				chunk.iter()
-					.map(|line|
-					{
-						line.chunks(10)
-							.map(|chunk|
-							{
-								chunk.iter()
-									.map(|&b| if b { '#' } else { '.' })
-									.collect::<String>()
-							})
-							.collect::<Vec<String>>()
-							.join(" ")
-					})
-					.collect::<Vec<_>>()
-					.join("\n")
-			})
-			.collect::<Vec<_>>()
-			.join("\n\n"));
-
-		let small_grid = big_grid[1..9].iter().chain(big_grid[11..19].iter()).chain(big_grid[21..29].iter()).copied()
-			.map(|line| line[1..9].iter().chain(line[11..19].iter()).chain(line[21..29].iter()).copied().collect::<Vec<_>>())
-			.collect::<Vec<_>>();
-
-		debug!("{}",small_grid.chunks(8)
-			.map(|chunk|
-			{
-				chunk.iter()
-					.map(|line|
-					{
-						line.chunks(8)
-							.map(|chunk|
-							{
-								chunk.iter()
-									.map(|&b| if b { '#' } else { '.' })
-									.collect::<String>()
-							})
-							.collect::<Vec<String>>()
-							.join(" ")
-					})
-					.collect::<Vec<_>>()
-					.join("\n")
-			})
-			.collect::<Vec<_>>()
-			.join("\n\n"));
-
-		let monster = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ".lines()
-			.map(|line| line.chars().map(|ch| ch == '#').collect::<Vec<_>>())
-			.collect::<Vec<_>>();
-		let monster_rot = monster[0].iter().copied().zip(monster[1].iter().copied()).zip(monster[2].iter().copied())
-			.map(|((a,b),c)| vec![a,b,c])
-			.collect::<Vec<_>>();
-
-		/*(0..(small_grid.len()-monster.len()))
-			.flat_map(|yoff|
-			{
-				(0..(small_grid[0].len()-monster[0].len()))
-					.map(|xoff|
-					{
-						let matching = (0..monster.len())
-							.flat_map(|y|
-							{
-								vec!
-								[
-									(0..monster[0].len())
-									.flat_map(|x|
-									{
-										let grid_pos = (x+xoff,y+yoff);
-										let monster_pos = (x,y);
-
-									})
-									.filter_map(|((grid_x,grid_y),(mon_x,mon_y))|
-									{
-										if small_grid[y]
-									})
-								]
-							})
-							.collect::<Vec<_>>();
-
-					})
-			})
-*/
-		let coords_normal = small_grid.windows(3)
-			.enumerate()
-			.flat_map(|(y,s): (usize,&[Vec<bool>])|
-			{
-				s[0].windows(monster[0].len()).collect::<Vec<_>>().iter().zip(s[1].windows(monster[0].len()).collect::<Vec<_>>()).zip(s[2].windows(monster[0].len()).collect::<Vec<_>>())
-					.enumerate()
-					.flat_map(
-					{
-						let monster = monster.clone();
-						move |(xoff,((s0,s1),s2))|
-						{
-							vec!
-							[
-								std::iter::empty()
-									.chain(s0.iter().copied().zip(monster[0].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y),b)))
-									.chain(s1.iter().copied().zip(monster[1].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y+1),b)))
-									.chain(s2.iter().copied().zip(monster[2].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y+2),b)))
-									.collect::<Vec<_>>(),
-								std::iter::empty()
-									.chain(s0.iter().copied().zip(monster[0].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y),b)))
-									.chain(s1.iter().copied().zip(monster[1].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y+1),b)))
-									.chain(s2.iter().copied().zip(monster[2].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y+2),b)))
-									.collect::<Vec<_>>(),
-								std::iter::empty()
-									.chain(s0.iter().copied().zip(monster[2].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y),b)))
-									.chain(s1.iter().copied().zip(monster[1].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y+1),b)))
-									.chain(s2.iter().copied().zip(monster[0].iter().rev().copied().collect::<Vec<_>>()).enumerate().map(move |(x,b)| ((x+xoff,y+2),b)))
-									.collect::<Vec<_>>(),
-								std::iter::empty()
-									.chain(s0.iter().copied().zip(monster[2].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y),b)))
-									.chain(s1.iter().copied().zip(monster[1].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y+1),b)))
-									.chain(s2.iter().copied().zip(monster[0].to_vec()).enumerate().map(move |(x,b)| ((x+xoff,y+2),b)))
-									.collect::<Vec<_>>(),
-							]
-						}
-					})
-					.collect::<Vec<_>>()
-			})
-			.filter_map(|vec|
-			{
-				trace!("{:?}", vec[0].0);
-				if vec[0].0 == (2,2)
-				{
-					debug!("{:?}",vec);
-				}
-				let filtered = vec.into_iter().filter(|&(_,(_sea, monster))| monster).collect::<Vec<_>>();
-				if filtered.iter().all(|(_,(sea, monster))| sea & monster)
-				{
-					Some(filtered.iter().map(|&(coords,_)| coords).collect::<Vec<_>>())
-				}
-				else
-				{
-					None
-				}
-			})
-			.flatten()
-			.collect::<Vec<(usize,usize)>>();
+/// Arranges the tiles into a square image and strips their borders.
+fn assemble(tiles: &[Tile]) -> Result<Grid<char>>
+{
+	let side = (1..=tiles.len()).find(|side| side * side >= tiles.len()).unwrap_or(0);
+	anyhow::ensure!(side > 0 && side * side == tiles.len(), "{} tiles do not form a square", tiles.len());
+	let size = tiles[0].grid.width();
+	anyhow::ensure!(size > 2 && tiles.iter().all(|tile| tile.grid.width() == size), "all tiles need the same size");
+
+	let placements = tiles.iter()
+		.enumerate()
+		.flat_map(|(idx, tile)| orientations(&tile.grid).into_iter().map(move |grid| Placement::new(idx, grid)))
+		.collect::<Vec<_>>();
+	let mut layout = Layout::new(&placements, side, tiles.len());
+	if !layout.place()
+	{
+		bail!(Error::AocNoSolution);
+	}
+	let layout = layout.layout;
+	debug!("layout: {:?}", layout.iter().map(|&idx| tiles[placements[idx].tile].id).collect::<Vec<_>>());
 
-		debug!("coords_normal: {}", coords_normal.len());
+	let inner = size - 2;
+	Ok(Grid::from_fn(side * inner, side * inner, |(x, y)|
+	{
+		let placement = &placements[layout[(y / inner) * side + x / inner]];
+		placement.grid[(x % inner + 1, y % inner + 1)]
+	}))
+}
 
-		let coords_rot = small_grid.windows(20)
-			.enumerate()
-			.flat_map(|(y,s)|
-			{
-				vec!
-				[
-					std::iter::empty()
-						.chain(s[0].iter().copied().zip(monster_rot[0].iter().copied()).enumerate().map(|(x,b)| ((x,y),b)))
-						.chain(s[1].iter().copied().zip(monster_rot[1].iter().copied()).enumerate().map(|(x,b)| ((x,y+1),b)))
-						.chain(s[2].iter().copied().zip(monster_rot[2].iter().copied()).enumerate().map(|(x,b)| ((x,y+2),b)))
-						.chain(s[3].iter().copied().zip(monster_rot[3].iter().copied()).enumerate().map(|(x,b)| ((x,y+3),b)))
-						.chain(s[4].iter().copied().zip(monster_rot[4].iter().copied()).enumerate().map(|(x,b)| ((x,y+4),b)))
-						.chain(s[5].iter().copied().zip(monster_rot[5].iter().copied()).enumerate().map(|(x,b)| ((x,y+5),b)))
-						.chain(s[6].iter().copied().zip(monster_rot[6].iter().copied()).enumerate().map(|(x,b)| ((x,y+6),b)))
-						.chain(s[7].iter().copied().zip(monster_rot[7].iter().copied()).enumerate().map(|(x,b)| ((x,y+7),b)))
-						.chain(s[8].iter().copied().zip(monster_rot[8].iter().copied()).enumerate().map(|(x,b)| ((x,y+8),b)))
-						.chain(s[9].iter().copied().zip(monster_rot[9].iter().copied()).enumerate().map(|(x,b)| ((x,y+9),b)))
-						.chain(s[10].iter().copied().zip(monster_rot[10].iter().copied()).enumerate().map(|(x,b)| ((x,y+10),b)))
-						.chain(s[11].iter().copied().zip(monster_rot[11].iter().copied()).enumerate().map(|(x,b)| ((x,y+11),b)))
-						.chain(s[12].iter().copied().zip(monster_rot[12].iter().copied()).enumerate().map(|(x,b)| ((x,y+12),b)))
-						.chain(s[13].iter().copied().zip(monster_rot[13].iter().copied()).enumerate().map(|(x,b)| ((x,y+13),b)))
-						.chain(s[14].iter().copied().zip(monster_rot[14].iter().copied()).enumerate().map(|(x,b)| ((x,y+14),b)))
-						.chain(s[15].iter().copied().zip(monster_rot[15].iter().copied()).enumerate().map(|(x,b)| ((x,y+15),b)))
-						.chain(s[16].iter().copied().zip(monster_rot[16].iter().copied()).enumerate().map(|(x,b)| ((x,y+16),b)))
-						.chain(s[17].iter().copied().zip(monster_rot[17].iter().copied()).enumerate().map(|(x,b)| ((x,y+17),b)))
-						.chain(s[18].iter().copied().zip(monster_rot[18].iter().copied()).enumerate().map(|(x,b)| ((x,y+18),b)))
-						.chain(s[19].iter().copied().zip(monster_rot[19].iter().copied()).enumerate().map(|(x,b)| ((x,y+19),b)))
-						.collect::<Vec<_>>(),
-					std::iter::empty()
-						.chain(s[0].iter().copied().zip(monster_rot[0].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y),b)))
-						.chain(s[1].iter().copied().zip(monster_rot[1].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+1),b)))
-						.chain(s[2].iter().copied().zip(monster_rot[2].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+2),b)))
-						.chain(s[3].iter().copied().zip(monster_rot[3].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+3),b)))
-						.chain(s[4].iter().copied().zip(monster_rot[4].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+4),b)))
-						.chain(s[5].iter().copied().zip(monster_rot[5].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+5),b)))
-						.chain(s[6].iter().copied().zip(monster_rot[6].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+6),b)))
-						.chain(s[7].iter().copied().zip(monster_rot[7].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+7),b)))
-						.chain(s[8].iter().copied().zip(monster_rot[8].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+8),b)))
-						.chain(s[9].iter().copied().zip(monster_rot[9].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+9),b)))
-						.chain(s[10].iter().copied().zip(monster_rot[10].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+10),b)))
-						.chain(s[11].iter().copied().zip(monster_rot[11].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+11),b)))
-						.chain(s[12].iter().copied().zip(monster_rot[12].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+12),b)))
-						.chain(s[13].iter().copied().zip(monster_rot[13].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+13),b)))
-						.chain(s[14].iter().copied().zip(monster_rot[14].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+14),b)))
-						.chain(s[15].iter().copied().zip(monster_rot[15].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+15),b)))
-						.chain(s[16].iter().copied().zip(monster_rot[16].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+16),b)))
-						.chain(s[17].iter().copied().zip(monster_rot[17].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+17),b)))
-						.chain(s[18].iter().copied().zip(monster_rot[18].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+18),b)))
-						.chain(s[19].iter().copied().zip(monster_rot[19].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+19),b)))
-						.collect::<Vec<_>>(),
-					std::iter::empty()
-						.chain(s[19].iter().copied().zip(monster_rot[0].iter().copied()).enumerate().map(|(x,b)| ((x,y),b)))
-						.chain(s[18].iter().copied().zip(monster_rot[1].iter().copied()).enumerate().map(|(x,b)| ((x,y+1),b)))
-						.chain(s[17].iter().copied().zip(monster_rot[2].iter().copied()).enumerate().map(|(x,b)| ((x,y+2),b)))
-						.chain(s[16].iter().copied().zip(monster_rot[3].iter().copied()).enumerate().map(|(x,b)| ((x,y+3),b)))
-						.chain(s[15].iter().copied().zip(monster_rot[4].iter().copied()).enumerate().map(|(x,b)| ((x,y+4),b)))
-						.chain(s[14].iter().copied().zip(monster_rot[5].iter().copied()).enumerate().map(|(x,b)| ((x,y+5),b)))
-						.chain(s[13].iter().copied().zip(monster_rot[6].iter().copied()).enumerate().map(|(x,b)| ((x,y+6),b)))
-						.chain(s[12].iter().copied().zip(monster_rot[7].iter().copied()).enumerate().map(|(x,b)| ((x,y+7),b)))
-						.chain(s[11].iter().copied().zip(monster_rot[8].iter().copied()).enumerate().map(|(x,b)| ((x,y+8),b)))
-						.chain(s[10].iter().copied().zip(monster_rot[9].iter().copied()).enumerate().map(|(x,b)| ((x,y+9),b)))
-						.chain(s[9].iter().copied().zip(monster_rot[10].iter().copied()).enumerate().map(|(x,b)| ((x,y+10),b)))
-						.chain(s[8].iter().copied().zip(monster_rot[11].iter().copied()).enumerate().map(|(x,b)| ((x,y+11),b)))
-						.chain(s[7].iter().copied().zip(monster_rot[12].iter().copied()).enumerate().map(|(x,b)| ((x,y+12),b)))
-						.chain(s[6].iter().copied().zip(monster_rot[13].iter().copied()).enumerate().map(|(x,b)| ((x,y+13),b)))
-						.chain(s[5].iter().copied().zip(monster_rot[14].iter().copied()).enumerate().map(|(x,b)| ((x,y+14),b)))
-						.chain(s[4].iter().copied().zip(monster_rot[15].iter().copied()).enumerate().map(|(x,b)| ((x,y+15),b)))
-						.chain(s[3].iter().copied().zip(monster_rot[16].iter().copied()).enumerate().map(|(x,b)| ((x,y+16),b)))
-						.chain(s[2].iter().copied().zip(monster_rot[17].iter().copied()).enumerate().map(|(x,b)| ((x,y+17),b)))
-						.chain(s[1].iter().copied().zip(monster_rot[18].iter().copied()).enumerate().map(|(x,b)| ((x,y+18),b)))
-						.chain(s[0].iter().copied().zip(monster_rot[19].iter().copied()).enumerate().map(|(x,b)| ((x,y+19),b)))
-						.collect::<Vec<_>>(),
-					std::iter::empty()
-						.chain(s[19].iter().copied().zip(monster_rot[0].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y),b)))
-						.chain(s[18].iter().copied().zip(monster_rot[1].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+1),b)))
-						.chain(s[17].iter().copied().zip(monster_rot[2].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+2),b)))
-						.chain(s[16].iter().copied().zip(monster_rot[3].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+3),b)))
-						.chain(s[15].iter().copied().zip(monster_rot[4].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+4),b)))
-						.chain(s[14].iter().copied().zip(monster_rot[5].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+5),b)))
-						.chain(s[13].iter().copied().zip(monster_rot[6].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+6),b)))
-						.chain(s[12].iter().copied().zip(monster_rot[7].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+7),b)))
-						.chain(s[11].iter().copied().zip(monster_rot[8].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+8),b)))
-						.chain(s[10].iter().copied().zip(monster_rot[9].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+9),b)))
-						.chain(s[9].iter().copied().zip(monster_rot[10].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+10),b)))
-						.chain(s[8].iter().copied().zip(monster_rot[11].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+11),b)))
-						.chain(s[7].iter().copied().zip(monster_rot[12].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+12),b)))
-						.chain(s[6].iter().copied().zip(monster_rot[13].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+13),b)))
-						.chain(s[5].iter().copied().zip(monster_rot[14].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+14),b)))
-						.chain(s[4].iter().copied().zip(monster_rot[15].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+15),b)))
-						.chain(s[3].iter().copied().zip(monster_rot[16].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+16),b)))
-						.chain(s[2].iter().copied().zip(monster_rot[17].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+17),b)))
-						.chain(s[1].iter().copied().zip(monster_rot[18].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+18),b)))
-						.chain(s[0].iter().copied().zip(monster_rot[19].iter().rev().copied()).enumerate().map(|(x,b)| ((x,y+19),b)))
-						.collect::<Vec<_>>(),
-				]
-			})
-			.filter_map(|vec|
-			{
-				let filtered = vec.into_iter().filter(|&(_,(_sea, monster))| monster).collect::<Vec<_>>();
-				if filtered.iter().all(|(_,(sea, monster))| sea & monster)
-				{
-					Some(filtered.iter().map(|&(coords,_)| coords).collect::<Vec<_>>())
-				}
-				else
-				{
-					None
-				}
-			})
-			.flatten()
-			.collect::<Vec<(usize,usize)>>();
+/// The top left corners of all sea monsters, given as the offsets of their cells.
+fn find_monsters(image: &Grid<char>, monster: &[(usize, usize)]) -> Vec<(usize, usize)>
+{
+	image.positions()
+		.filter(|&(x, y)| monster.iter().all(|&(dx, dy)| image.get((x + dx, y + dy)) == Some(&'#')))
+		.collect()
+}
 
-		debug!("coords_rot: {}", coords_rot.len());
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
 
-		let mut small_grid = small_grid;
-		for &(x,y) in coords_normal.iter().chain(coords_rot.iter())
+		let image = self.image()?;
+		if self.dump_image
 		{
-			small_grid[y][x] = false;
+			print!("{}", image);
 		}
-		let result = small_grid.iter().flatten().copied().filter(|&b| b).count();
+
+		let result = image.iter().filter(|&(_, &ch)| ch == '#').count();
 
 		Ok(format!("{}", result))
 	}
 }
-
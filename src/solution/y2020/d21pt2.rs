@@ -1,9 +1,12 @@
 use crate::error::*;
 
+use std::collections::{ BTreeMap, BTreeSet };
+
+use super::super::common::matching::unique_matching;
+
 /// # Examples
 ///
-/// ```no_run
-/// # // FIXME: currently broken
+/// ```
 /// # use adventofcode::solution::
 /// # {
 /// #     y2020::D21Pt2 as Solution,
@@ -15,6 +18,9 @@ use crate::error::*;
 ///     sqjhc fvjkl (contains soy)\n\
 ///     sqjhc mxmxvkd sbzzf (contains fish)";
 /// assert_eq!(Solution::new(example.to_string()).solve().expect("1"), "mxmxvkd,sqjhc,fvjkl");
+///
+/// // either ingredient could contain either allergen
+/// assert!(Solution::new("abc def (contains dairy, soy)".to_string()).solve().is_err());
 /// ```
 pub struct Solution
 {
@@ -94,131 +100,22 @@ impl super::super::Solution for Solution
 			.map(|line| line.parse::<Recipe>())
 			.collect::<std::result::Result<Vec<_>, Error>>()?;
 
-		let mut unmatched = recipes.clone();
-
-		let mut allergenes = std::collections::BTreeSet::new();
-
-		while !unmatched.is_empty()
-		{
-			for recipe in &mut unmatched
-			{
-				recipe.ingredients.retain(|i1| allergenes.iter().all(|(i2,_)| i1 != i2));
-				recipe.allergenes.retain(|a1| allergenes.iter().all(|(_,a2)| a1 != a2));
-			}
-			unmatched.retain(|recipe| !recipe.allergenes.is_empty());
-			for recipe in unmatched.iter()
-				.filter(|recipe| recipe.ingredients.len() == 1)
+		// an allergen can only be in ingredients listed by every recipe containing it
+		let candidates = recipes.iter()
+			.flat_map(|recipe| recipe.allergenes.iter().map(move |allergene| (allergene, recipe)))
+			.fold(BTreeMap::<&str, BTreeSet<&str>>::new(), |mut map, (allergene, recipe)|
 			{
-				let ingredient = recipe.ingredients[0].clone();
-				let allergene = recipe.allergenes[0].clone();
-				allergenes.insert((ingredient,allergene));
-
-				continue;
-			}
-			let most_common = unmatched.iter()
-				.flat_map(|recipe|
-				{
-					recipe.ingredients.iter()
-						.flat_map(|ingredient|
-						{
-							recipe.allergenes.iter()
-								.map(|allergene| (ingredient,allergene))
-								.collect::<Vec<_>>()
-						})
-						.collect::<Vec<_>>()
-				})
-				.fold(std::collections::BTreeMap::new(),|mut acc,new|
-				{
-					*acc.entry(new).or_insert(0) += 1;
-					acc
-				})
-				.into_iter()
-				.max_by_key(|&(_,n)| n);
-			if let Some(((ingredient,allergene),_)) = most_common
-			{
-				allergenes.insert((ingredient.to_string(), allergene.to_string()));
-			}
-		}
+				let ingredients = recipe.ingredients.iter().map(String::as_str).collect::<BTreeSet<_>>();
+				map.entry(allergene)
+					.and_modify(|set| set.retain(|ingredient| ingredients.contains(ingredient)))
+					.or_insert(ingredients);
+				map
+			});
+		debug!("candidates: {:?}", candidates);
 
-		let safe = recipes.iter()
-			.flat_map(|recipe| recipe.ingredients.iter().filter(|ingredient| allergenes.iter().all(|(i,_)| i != *ingredient)).cloned().collect::<Vec<_>>())
-			.collect::<std::collections::HashSet<_>>();
-
-		let mut unmatched = recipes.iter()
-			.map(|recipe|
-			{
-				let Recipe { ingredients, allergenes, } = recipe.clone();
-				let mut ingredients = ingredients;
-				ingredients.retain(|ingredient| safe.contains(ingredient));
-				Recipe { ingredients, allergenes, }
-			})
-			.collect::<Vec<_>>();
-
-		allergenes.clear();
-
-		while !unmatched.is_empty()
-		{
-			debug!("{:#?}", allergenes);
-			for recipe in &mut unmatched
-			{
-				recipe.ingredients.retain(|i1| allergenes.iter().all(|(i2,_)| i1 != i2));
-				recipe.allergenes.retain(|a1| allergenes.iter().all(|(_,a2)| a1 != a2));
-			}
-			unmatched.retain(|recipe| !recipe.allergenes.is_empty());
-			for recipe in unmatched.iter()
-				.filter(|recipe| recipe.ingredients.len() == 1)
-			{
-				let ingredient = recipe.ingredients[0].clone();
-				let allergene = recipe.allergenes[0].clone();
-				allergenes.insert((ingredient,allergene));
-
-				continue;
-			}
-			let mut map = unmatched.iter()
-				.flat_map(|recipe|
-				{
-					recipe.allergenes.iter()
-						.flat_map(|allergen|
-						{
-							recipe.ingredients.iter()
-								.map(|ingredient| (allergen,ingredient))
-								.collect::<Vec<_>>()
-						})
-						.collect::<Vec<_>>()
-				})
-				.fold(std::collections::HashMap::<_, std::collections::HashSet<_>>::new(), |mut acc,(allergen,ingredient)|
-				{
-					acc.entry(allergen).or_default().insert(ingredient);
-					acc
-				});
-			for (allergen,ingredients) in &mut map
-			{
-				for ingredient in &ingredients.clone()
-				{
-					let missing = recipes.iter()
-						.find(|recipe| recipe.allergenes.contains(allergen) && !recipe.ingredients.contains(ingredient));
-					if missing.is_some()
-					{
-						ingredients.remove(ingredient);
-					}
-				}
-			}
-			debug!("{:#?}",map);
-			allergenes.extend(map.iter()
-				.filter(|(_,map)| map.len() == 1)
-				.map(|(allergen,set)| (allergen.to_string(),set.iter().next().unwrap().to_string()))
-				.inspect(|tuple| debug!("add to allergens: {:?}", tuple)));
-		}
-
-		recipes.iter()
-			.flat_map(|recipe| recipe.allergenes.iter().filter(|&allergene| !allergenes.iter().any(|(_,a)| allergene == a)))
-			.for_each(|a| debug!("{}", a));
-
-		let mut allergenes = allergenes.into_iter().collect::<Vec<_>>();
-		allergenes.sort_by_key(|(_,allergene)| allergene.to_string());
-		let result = allergenes.into_iter().map(|(ingredient,_)| ingredient).collect::<Vec<_>>().join(",");
+		let allergenes = unique_matching(&candidates)?;
+		let result = allergenes.values().copied().collect::<Vec<_>>().join(",");
 
 		Ok(result)
 	}
 }
-
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::automaton::{ life, Automaton };
+
 /// # Examples
 ///
 /// ```
@@ -60,10 +62,10 @@ struct Position(isize,isize);
 
 impl Position
 {
-	fn neighbours(&self) -> Box<dyn Iterator<Item=Position>>
+	fn neighbours(&self) -> Vec<Position>
 	{
 		let &Position(my_x, my_y) = self;
-		let iter = [
+		[
 			(1,1),
 			(-1,1),
 			(2,0),
@@ -71,8 +73,8 @@ impl Position
 			(1,-1),
 			(-1,-1),
 		].iter()
-			.map(move |(x,y)| Position(my_x+x,my_y+y));
-		Box::new(iter)
+			.map(move |(x,y)| Position(my_x+x,my_y+y))
+			.collect()
 	}
 }
 
@@ -138,35 +140,13 @@ impl super::super::Solution for Solution
 				}
 				set
 			});
-		let result = std::iter::successors(Some(set),|set|
+		let mut automaton = Automaton::new(set, 6, Position::neighbours, life([2], [1, 2]));
+		for day in 1..=100
 		{
-			use rayon::prelude::*;
-			let all = set.iter()
-				.fold(std::collections::HashSet::new(),|mut set, pos|
-				{
-					set.extend(pos.neighbours());
-					set
-				});
-			Some(all.par_iter()
-				.cloned()
-				.filter(|pos|
-				{
-					let local = set.contains(pos);
-					let remote = pos.neighbours()
-						.filter(|pos| set.contains(pos))
-						.count();
-					match (local,remote)
-					{
-						(true, 1..=2) => true,
-						(false, 2) => true,
-						_ => false,
-					}
-				})
-				.collect())
-		})
-			.inspect(|set| debug!("length: {}", set.len()))
-			.nth(100).ok_or(Error::AocNoSolution)?;
-		Ok(format!("{}", result.len()))
+			automaton.step();
+			debug!("day {}: {} black tiles", day, automaton.cells().len());
+		}
+		Ok(format!("{}", automaton.population().ok_or(Error::AocNoSolution)?))
 	}
 }
 
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::number::{ discrete_log, mod_pow };
+
 /// # Examples
 ///
 /// ```
@@ -25,32 +27,7 @@ impl Solution
 	}
 }
 
-struct Crypto
-{
-	value: usize,
-	subject: usize,
-}
-
-impl Iterator for Crypto
-{
-	type Item = usize;
-
-	fn next(&mut self) -> Option<Self::Item>
-	{
-		let ret = Some(self.value);
-		self.value = (self.value * self.subject) % 20201227;
-		ret
-	}
-}
-
-fn transform(subject: usize) -> Crypto
-{
-	Crypto
-	{
-		subject,
-		value: 1,
-	}
-}
+const MODULUS: u64 = 20201227;
 
 impl super::super::Solution for Solution
 {
@@ -59,7 +36,7 @@ impl super::super::Solution for Solution
 		debug!("started with input: {}", self.input);
 
 		let mut lines = self.input.lines();
-		const SUBJECT: usize = 7;
+		const SUBJECT: u64 = 7;
 		let card_pub = lines.next().ok_or(Error::AocParsing)?.parse()?;
 		let door_pub = lines.next().ok_or(Error::AocParsing)?.parse()?;
 		if lines.next().is_some()
@@ -67,11 +44,12 @@ impl super::super::Solution for Solution
 			bail!(Error::AocParsing);
 		}
 
-		let card_loop = transform(SUBJECT).enumerate().find(|&(_,value)| value == card_pub).ok_or(Error::AocNoSolution)?.0;
-		let door_loop = transform(SUBJECT).enumerate().find(|&(_,value)| value == door_pub).ok_or(Error::AocNoSolution)?.0;
+		let card_loop = discrete_log(SUBJECT, card_pub, MODULUS).ok_or(Error::AocNoSolution)?;
+		let door_loop = discrete_log(SUBJECT, door_pub, MODULUS).ok_or(Error::AocNoSolution)?;
+		debug!("loop sizes: card {}, door {}", card_loop, door_loop);
 
-		let card_enc_key = transform(door_pub).nth(card_loop).ok_or(Error::AocNoSolution)?;
-		let door_enc_key = transform(card_pub).nth(door_loop).ok_or(Error::AocNoSolution)?;
+		let card_enc_key = mod_pow(door_pub, card_loop, MODULUS);
+		let door_enc_key = mod_pow(card_pub, door_loop, MODULUS);
 
 		if card_enc_key != door_enc_key
 		{
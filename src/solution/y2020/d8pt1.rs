@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::console::{ Console, Status, debug };
+
 /// # Examples
 ///
 /// ```
@@ -23,35 +25,50 @@ use crate::error::*;
 pub struct Solution
 {
 	input: String,
+	trace: bool,
+	debug: bool,
+	breakpoints: Vec<usize>,
 }
 
 impl Solution
 {
 	pub fn new(input: String) -> Self
 	{
-		Self { input, }
+		Self { input, trace: false, debug: false, breakpoints: Vec::new(), }
 	}
-}
-enum Instruction
-{
-	Acc(isize),
-	Jmp(isize),
-	Nop,
-}
 
-impl std::str::FromStr for Instruction
-{
-	type Err = Error;
-	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	pub fn trace(self, trace: bool) -> Self
 	{
-		let parts = input.split_whitespace().take(2).collect::<Vec<_>>();
-		Ok(match (parts[0],parts[1].parse()?)
-		{
-			("jmp",i) => Instruction::Jmp(i),
-			("acc",i) => Instruction::Acc(i),
-			("nop",_) => Instruction::Nop,
-			_ => return Err(Error::AocParsing),
-		})
+		Self { trace, ..self }
+	}
+
+	/// Runs the interactive debugger on stdin instead, stopping at the breakpoints.
+	pub fn debug(self, debug: bool, breakpoints: Vec<usize>) -> Self
+	{
+		Self { debug, breakpoints, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("trace")
+				.long("trace")
+				.help("print every executed instruction")
+				.action(clap::ArgAction::SetTrue),
+			clap::Arg::new("debug")
+				.long("debug")
+				.help("step through the program interactively")
+				.action(clap::ArgAction::SetTrue),
+			clap::Arg::new("break")
+				.value_name("ADDRESS")
+				.short('b')
+				.long("break")
+				.help("set a breakpoint for the debugger")
+				.requires("debug")
+				.value_parser(clap::value_parser!(usize))
+				.action(clap::ArgAction::Append),
+		]
 	}
 }
 
@@ -61,37 +78,30 @@ impl super::super::Solution for Solution
 	{
 		debug!("started with input: {}", self.input);
 
-		let code = self.input.lines()
-			.map(|line| line.parse::<Instruction>())
-			.collect::<std::result::Result<Vec<_>, Error>>()?;
-
-		let mut acc: isize = 0;
-		let mut pi: usize = 0;
-		let mut set = std::collections::BTreeSet::new();
+		let mut console: Console = self.input.parse()?;
+		if self.trace
+		{
+			console = console.with_trace();
+		}
 
-		loop
+		if self.debug
 		{
-			if !set.insert(pi)
+			for &address in self.breakpoints.iter()
 			{
-				return Ok(format!("{}", acc));
-			}
-			match code[pi]
-			{
-				Instruction::Acc(i) =>
-				{
-					acc += i;
-					pi += 1;
-				},
-				Instruction::Nop =>
-				{
-					pi += 1;
-				},
-				Instruction::Jmp(i) =>
-				{
-					pi = i.saturating_add(pi as isize) as usize;
-				},
+				console.add_breakpoint(address);
 			}
+			debug(&mut console, std::io::stdin().lock(), std::io::stdout())?;
+		}
+		else if console.run()? == Status::Terminated
+		{
+			bail!("the program terminated without looping");
+		}
+
+		for step in console.trace()
+		{
+			println!("{}", step);
 		}
+
+		Ok(format!("{}", console.accumulator()))
 	}
 }
-
@@ -1,11 +1,13 @@
 use crate::error::*;
 
+use super::console::{ Console, Status, debug, repair };
+
 /// # Examples
 ///
 /// ```
 /// # use adventofcode::solution::
 /// # {
-/// #     y2020::D8Pt1 as Solution,
+/// #     y2020::D8Pt2 as Solution,
 /// #     Solution as S,
 /// # };
 /// # env_logger::init();
@@ -18,44 +20,40 @@ use crate::error::*;
 ///     acc +1\n\
 ///     jmp -4\n\
 ///     acc +6";
-/// assert_eq!(Solution::new(input.to_string()).solve().expect("1"), "5");
+/// assert_eq!(Solution::new(input.to_string()).solve().expect("1"), "8");
 /// ```
 pub struct Solution
 {
 	input: String,
+	trace: bool,
+	debug: bool,
+	breakpoints: Vec<usize>,
 }
 
 impl Solution
 {
 	pub fn new(input: String) -> Self
 	{
-		Self { input, }
+		Self { input, trace: false, debug: false, breakpoints: Vec::new(), }
 	}
-}
-#[derive(Clone,Debug)]
-enum Instruction
-{
-	Acc(isize),
-	Jmp(isize),
-	Nop(isize),
-}
 
-impl std::str::FromStr for Instruction
-{
-	type Err = Error;
-	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	pub fn trace(self, trace: bool) -> Self
 	{
-		let parts = input.split_whitespace().take(2).collect::<Vec<_>>();
-		Ok(match (parts[0],parts[1].parse()?)
-		{
-			("jmp",i) => Instruction::Jmp(i),
-			("acc",i) => Instruction::Acc(i),
-			("nop",i) => Instruction::Nop(i),
-			_ => return Err(Error::AocParsing),
-		})
+		Self { trace, ..self }
 	}
-}
 
+	/// Runs the interactive debugger on stdin on the repaired program instead, stopping at the
+	/// breakpoints.
+	pub fn debug(self, debug: bool, breakpoints: Vec<usize>) -> Self
+	{
+		Self { debug, breakpoints, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		super::D8Pt1::args()
+	}
+}
 
 impl super::super::Solution for Solution
 {
@@ -63,57 +61,36 @@ impl super::super::Solution for Solution
 	{
 		debug!("started with input: {}", self.input);
 
-		let code = self.input.lines()
-			.map(|line| line.parse::<Instruction>())
-			.collect::<std::result::Result<Vec<_>, Error>>()?;
+		let console: Console = self.input.parse()?;
+		let mut program = console.program().to_vec();
+		let address = repair(&program)?;
+		program[address] = program[address].flipped().unwrap();
+		debug!("repaired by flipping {:04} to {}", address, program[address]);
 
-		for (idx,inst) in code.iter().enumerate()
+		let mut console = Console::new(program);
+		if self.trace
 		{
-			let mut acc: isize = 0;
-			let mut pi: isize = 0;
-			let mut set = std::collections::BTreeSet::new();
-
-			let inverted = match *inst
-			{
-				Instruction::Nop(i) => Instruction::Jmp(i),
-				Instruction::Jmp(i) => Instruction::Nop(i),
-				Instruction::Acc(_) => continue,
-			};
-			let mut code = code.clone();
-			code[idx] = inverted;
-			let code = code;
+			console = console.with_trace();
+		}
 
-			loop
+		if self.debug
+		{
+			for &address in self.breakpoints.iter()
 			{
-				if pi as usize >= code.len()
-				{
-					return Ok(format!("{}", acc));
-				}
-				if !set.insert(pi)
-				{
-					break;
-				}
-				match code[pi as usize]
-				{
-					Instruction::Acc(i) =>
-					{
-						acc += i;
-						pi += 1;
-					},
-					Instruction::Nop(_) =>
-					{
-						pi += 1;
-					},
-					Instruction::Jmp(i) =>
-					{
-						pi += i;
-					},
-				}
-				pi = pi.max(0);
+				console.add_breakpoint(address);
 			}
+			debug(&mut console, std::io::stdin().lock(), std::io::stdout())?;
+		}
+		else
+		{
+			anyhow::ensure!(console.run()? == Status::Terminated, "the repaired program still does not terminate");
 		}
 
-		bail!(Error::AocNoSolution);
+		for step in console.trace()
+		{
+			println!("{}", step);
+		}
+
+		Ok(format!("{}", console.accumulator()))
 	}
 }
-
@@ -1,3 +1,5 @@
+pub mod console;
+
 mod d1pt1;
 pub use d1pt1::Solution as D1Pt1;
 mod d1pt2;
@@ -60,18 +62,12 @@ mod d16pt1;
 pub use d16pt1::Solution as D16Pt1;
 mod d16pt2;
 pub use d16pt2::Solution as D16Pt2;
-mod d17pt1;
-pub use d17pt1::Solution as D17Pt1;
-mod d17pt2;
-pub use d17pt2::Solution as D17Pt2;
-mod d18pt1;
-pub use d18pt1::Solution as D18Pt1;
-mod d18pt2;
-pub use d18pt2::Solution as D18Pt2;
-mod d19pt1;
-pub use d19pt1::Solution as D19Pt1;
-mod d19pt2;
-pub use d19pt2::Solution as D19Pt2;
+mod d17;
+pub use d17::Solution as D17;
+pub mod d18;
+pub use d18::Solution as D18;
+pub mod d19;
+pub use d19::Solution as D19;
 mod d20pt1;
 pub use d20pt1::Solution as D20Pt1;
 mod d20pt2;
@@ -100,6 +96,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 	enum InputType<'a>
 	{
 		UrlInput(Box<dyn Fn(Option<String>) -> Box<dyn super::Solution + 'static>>),
+		UrlInputArgs(Vec<clap::Arg>, Box<dyn Fn(Option<String>, &clap::ArgMatches) -> Box<dyn super::Solution + 'static>>),
 		Parser(Box<dyn super::InputParser<'a>>),
 	}
 
@@ -119,8 +116,8 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D6Pt2::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D7Pt1::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D7Pt2::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D8Pt1::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D8Pt2::new(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(D8Pt1::args(), Box::new(|input, matches| Box::new(D8Pt1::new(input.expect("empty input received")).trace(matches.get_flag("trace")).debug(matches.get_flag("debug"), matches.get_many::<usize>("break").into_iter().flatten().copied().collect()))))),
+		Some(InputType::UrlInputArgs(D8Pt2::args(), Box::new(|input, matches| Box::new(D8Pt2::new(input.expect("empty input received")).trace(matches.get_flag("trace")).debug(matches.get_flag("debug"), matches.get_many::<usize>("break").into_iter().flatten().copied().collect()))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D9Pt1::new(input.expect("empty input received"), 25))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D9Pt2::new(input.expect("empty input received"), 25))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D10Pt1::new(input.expect("empty input received")))))),
@@ -137,16 +134,16 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 		Some(InputType::Parser(D15::parser_pt2())),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D16Pt1::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D16Pt2::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D17Pt1::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D17Pt2::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D18Pt1::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D18Pt2::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D19Pt1::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(D19Pt2::new(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(D17::args(1), Box::new(|input, matches| Box::new(D17::new(input.expect("empty input received"), *matches.get_one("dimensions").unwrap()))))),
+		Some(InputType::UrlInputArgs(D17::args(2), Box::new(|input, matches| Box::new(D17::new(input.expect("empty input received"), *matches.get_one("dimensions").unwrap()))))),
+		Some(InputType::UrlInputArgs(D18::args(1), Box::new(|input, matches| Box::new(D18::new(input.expect("empty input received"), matches.get_one::<d18::Precedence>("precedence").unwrap().clone()))))),
+		Some(InputType::UrlInputArgs(D18::args(2), Box::new(|input, matches| Box::new(D18::new(input.expect("empty input received"), matches.get_one::<d18::Precedence>("precedence").unwrap().clone()))))),
+		Some(InputType::UrlInputArgs(D19::args(1), Box::new(|input, matches| Box::new(D19::new(input.expect("empty input received"), matches.get_many::<String>("replace").into_iter().flatten().cloned().collect()))))),
+		Some(InputType::UrlInputArgs(D19::args(2), Box::new(|input, matches| Box::new(D19::new(input.expect("empty input received"), matches.get_many::<String>("replace").into_iter().flatten().cloned().collect()))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D20Pt1::new(input.expect("empty input received")))))),
-		None,
+		Some(InputType::UrlInputArgs(D20Pt2::args(), Box::new(|input, matches| Box::new(D20Pt2::new(input.expect("empty input received")).dump_image(matches.get_flag("dump-image")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D21Pt1::new(input.expect("empty input received")))))),
-		None,
+		Some(InputType::UrlInput(Box::new(|input| Box::new(D21Pt2::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D22Pt1::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(D22Pt2::new(input.expect("empty input received")))))),
 		Some(InputType::Parser(D23Pt1::parser())),
@@ -162,6 +159,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 			match parser
 			{
 				InputType::UrlInput(parser) => Box::new((2020usize,idx/2+1,idx%2+1,parser)) as Box<dyn super::InputParser>,
+				InputType::UrlInputArgs(args, parser) => Box::new((2020usize,idx/2+1,idx%2+1,args,parser)) as Box<dyn super::InputParser>,
 				InputType::Parser(parser) => parser,
 			}
 		)
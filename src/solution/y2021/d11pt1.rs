@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::grid::Grid;
+
 /// # Examples
 ///
 /// ```
@@ -36,73 +38,49 @@ impl super::super::Solution for Solution
 	{
 		debug!("called with input: {}", self.input);
 
-		let list = self.input.lines()
-			.enumerate()
-			.map(|(y, line)|
+		let mut energy = self.input.lines()
+			.map(|line|
 			{
 				line.chars()
-					.enumerate()
-					.map(|(x, ch)|
-					{
-						let num = ch.to_digit(10).ok_or(Error::AocParsing)?;
-						Ok(((x as isize, y as isize), (num as usize, false)))
-					})
+					.map(|ch| Ok(ch.to_digit(10).ok_or(Error::AocParsing)? as usize))
 					.collect::<Result<Vec<_>>>()
 			})
-			.collect::<Result<Vec<_>>>()?;
-		let mut map = list.into_iter().flatten().collect::<std::collections::BTreeMap<_, _>>();
+			.collect::<Result<Vec<_>>>()
+			.and_then(Grid::from_rows)?;
 
-		let surround =
-		[
-			(-1, -1),
-			(-1, 0),
-			(-1, 1),
-			(0, -1),
-			(0, 1),
-			(1, -1),
-			(1, 0),
-			(1, 1),
-		];
 		let mut num_flashes = 0;
 
 		for _ in 0..100
 		{
-			for (ref mut energy, _) in map.values_mut()
-			{
-				*energy += 1;
-			}
+			energy.iter_mut().for_each(|(_, energy)| *energy += 1);
 
-			while map.values().any(|&(energy, done)| energy > 9 && !done)
+			// flashing raises the energy of the neighbours, which might flash in turn
+			let mut flashed = std::collections::HashSet::new();
+			let mut flashing = energy.iter()
+				.filter(|(_, &energy)| energy > 9)
+				.map(|(pos, _)| pos)
+				.collect::<Vec<_>>();
+			while let Some(pos) = flashing.pop()
 			{
-				let flashes = map.iter()
-					.filter(|((_x, _y), (energy, done))| *energy > 9 && !done)
-					.map(|(&(x, y), _)| (x, y))
-					.collect::<Vec<_>>();
-
-				for (x, y) in flashes
+				if !flashed.insert(pos)
 				{
-					surround.iter()
-						.for_each(|(dx, dy)|
-						{
-							if let Some((ref mut energy, _done)) = map.get_mut(&(dx + x, dy + y))
-							{
-								*energy += 1;
-							}
-						});
-
-					map.get_mut(&(x, y)).unwrap().1 = true;
+					continue;
+				}
+				for neighbour in energy.neighbours8(pos).collect::<Vec<_>>()
+				{
+					energy[neighbour] += 1;
+					if energy[neighbour] > 9
+					{
+						flashing.push(neighbour);
+					}
 				}
 			}
 
-			for (ref mut energy, ref mut done) in map.values_mut()
+			for &pos in flashed.iter()
 			{
-				if *done
-				{
-					*done = false;
-					*energy = 0;
-					num_flashes += 1;
-				}
+				energy[pos] = 0;
 			}
+			num_flashes += flashed.len();
 		}
 
 		Ok(format!("{}", num_flashes))
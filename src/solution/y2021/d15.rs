@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::{ grid::Grid, search::dijkstra };
+
 use nom::
 {
 	character::complete::*,
@@ -54,12 +56,12 @@ impl Solution
 	}
 }
 
-fn line(input: &str) -> IResult<&str, Vec<isize>>
+fn line(input: &str) -> IResult<&str, Vec<usize>>
 {
-	terminated(many1(map(one_of("0123456789"), |b| (b as isize - b'0' as isize))), newline)(input)
+	terminated(many1(map(one_of("0123456789"), |b| b as usize - b'0' as usize)), newline)(input)
 }
 
-fn full_input(input: &str) -> IResult<&str, Vec<Vec<isize>>>
+fn full_input(input: &str) -> IResult<&str, Vec<Vec<usize>>>
 {
 	terminated(many1(line), eof)(input)
 }
@@ -69,121 +71,33 @@ impl super::super::Solution for Solution
 	fn solve(&self) -> Result<String>
 	{
 		debug!("called with input: {}", self.input);
-		let (_, mut vec) = full_input(&self.input)
+		let (_, rows) = full_input(&self.input)
 			.map_err(|err| anyhow!("{}", err))?;
+		let tile = Grid::from_rows(rows)?;
 
-		if self.moar_input
+		let risks = if self.moar_input
 		{
-			for inner in vec.iter_mut()
+			// the tile repeats five times in both directions, each repetition being one riskier
+			Grid::from_fn(tile.width() * 5, tile.height() * 5, |(x, y)|
 			{
-				let clone = inner.clone();
-				inner.extend(clone.iter().copied().map(|x| (x+1-1)%9+1));
-				inner.extend(clone.iter().copied().map(|x| (x+2-1)%9+1));
-				inner.extend(clone.iter().copied().map(|x| (x+3-1)%9+1));
-				inner.extend(clone.iter().copied().map(|x| (x+4-1)%9+1));
-			}
-			let clone = vec.clone();
-			vec.extend(clone.iter().cloned().map(|inner| inner.iter().map(|x| (x+1-1)%9+1).collect::<Vec<_>>()));
-			vec.extend(clone.iter().cloned().map(|inner| inner.iter().map(|x| (x+2-1)%9+1).collect::<Vec<_>>()));
-			vec.extend(clone.iter().cloned().map(|inner| inner.iter().map(|x| (x+3-1)%9+1).collect::<Vec<_>>()));
-			vec.extend(clone.iter().cloned().map(|inner| inner.iter().map(|x| (x+4-1)%9+1).collect::<Vec<_>>()));
+				let risk = tile[(x % tile.width(), y % tile.height())] + x / tile.width() + y / tile.height();
+				(risk - 1) % 9 + 1
+			})
 		}
-
-		let (max_x, max_y) = (vec.len() as isize, vec.len() as isize);
-
-		debug!("{}", vec.iter().map(|vec| vec.iter().copied().map(|i| char::from_digit(i as u32, 10).unwrap_or('?')).chain(std::iter::once('\n')).collect::<String>()).collect::<String>());
-
-		let risks = vec.into_iter()
-			.flat_map(|vec| vec.into_iter())
-			.collect::<Vec<isize>>();
-
-		let connections =
-		[
-			(-1, 0), // left
-			(0, 1), // top
-			(1, 0), // right
-			(0, -1), // bottom
-		];
-
-		debug!("max: ({}, {})", max_x, max_y);
-		let num_elems = (max_x * max_y) as usize;
-
-		let mut distance: Vec<Option<(bool, isize)>> = vec![None; num_elems];
-		let mut current = (0, 0);
-
-		distance[0] = Some((false, 0));
-
-		loop
+		else
 		{
-			let (x, y) = current;
-			let current_distance = distance.get((x+y*max_x) as usize)
-				.ok_or(Error::AocNoSolution).with_context(|| anyhow!("cannot find current ({}, {}) in distance map", x, y))?
-				.ok_or(Error::AocNoSolution).with_context(|| anyhow!("current ({}, {}) is None in distance map", x, y))?
-				.1;
+			tile
+		};
 
-			debug!("checking neighbours for ({}, {}) with distance {}", x, y, current_distance);
+		debug!("risks:\n{}", risks);
 
-			for (nx, ny) in connections.iter()
-				.copied()
-				.map(|(dx, dy)| (x + dx, y + dy))
-				.filter(|&(nx, ny)| nx >= 0 && ny >= 0 && nx < max_x && ny < max_y)
-			{
-				trace!("checking neighbour ({}, {})", nx, ny);
-				let risk = risks.get((nx+ny*max_x) as usize)
-					.ok_or(Error::AocNoSolution)
-					.with_context(|| anyhow!("cannot find neighbour ({}, {}) in risk map", x, y))?;
-				trace!("neighbour has risk {}", risk);
-				let entry = distance.get_mut((nx+ny*max_x) as usize)
-					.ok_or(Error::AocNoSolution)
-					.with_context(|| anyhow!("cannot find neighbour ({}, {}) in distance map", x, y))?;
-				if let Some((visited, value)) = entry
-				{
-					if *visited
-					{
-						continue;
-					}
-					*value = (current_distance + risk).min(*value);
-				}
-				else
-				{
-					*entry = Some((entry.map(|(visited, _)| visited).unwrap_or(false), current_distance + risk));
-				}
-				trace!("neighbour has distance entry {:?}", entry);
-			}
+		let end = (risks.width() - 1, risks.height() - 1);
+		let path = dijkstra([(0, 0)], |&pos| risks.neighbours(pos).map(|(_, next)| (next, risks[next])), |&pos| pos == end)
+			.ok_or(Error::AocNoSolution)?;
 
-			if current == (max_x - 1, max_y - 1)
-			{
-				break;
-			}
-			distance.get_mut((x+y*max_x) as usize).unwrap().as_mut().unwrap().0 = true;
-			current = distance.iter()
-				.enumerate()
-				.filter_map(|(pos, opt)|
-				{
-					if let Some((visited, distance)) = opt
-					{
-						if *visited
-						{
-							None
-						}
-						else
-						{
-							Some(((pos as isize % max_x, pos as isize / max_x), distance))
-						}
-					}
-					else
-					{
-						None
-					}
-				})
-				.inspect(|(pos, risk)| trace!("pos {:?} has risk {:?}", pos, risk))
-				.min_by_key(|(_, risk)| *risk)
-				.map(|(pos, _)| pos)
-				.ok_or(Error::AocNoSolution)
-				.context("cannot find next unvisited")?;
-		}
+		trace!("cheapest path: {:?}", path.states);
 
-		Ok(format!("{}", distance.last().ok_or(Error::AocNoSolution).context("last element does not have distance")?.unwrap().1))
+		Ok(format!("{}", path.cost))
 	}
 }
 
@@ -1,15 +1,5 @@
 use crate::error::*;
 
-use nom::
-{
-	bits::complete::*,
-	combinator::*,
-	sequence::*,
-	multi::*,
-	bits::*,
-	IResult,
-};
-
 #[derive(Debug,PartialEq,Eq)]
 enum Action
 {
@@ -39,6 +29,7 @@ pub struct Solution
 {
 	input: String,
 	action: Action,
+	ast: bool,
 }
 
 impl Solution
@@ -49,6 +40,7 @@ impl Solution
 		{
 			input,
 			action: Action::SumVersion,
+			ast: false,
 		}
 	}
 
@@ -58,12 +50,29 @@ impl Solution
 		{
 			input,
 			action: Action::Execute,
+			ast: false,
 		}
 	}
+
+	pub fn ast(self, ast: bool) -> Self
+	{
+		Self { ast, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("ast")
+				.long("ast")
+				.help("print the packet tree as an indented expression")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
 }
 
-#[derive(Debug,PartialEq,Eq,Clone)]
-enum Operator
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+pub enum Operator
 {
 	Sum,
 	Product,
@@ -74,193 +83,469 @@ enum Operator
 	Equal,
 }
 
-#[derive(Debug,PartialEq,Eq)]
-enum Content
+impl Operator
 {
-	Literal(u128),
-	Operator(Operator, std::collections::VecDeque<Packet>),
-}
+	const ALL: [Operator; 7] =
+	[
+		Operator::Sum,
+		Operator::Product,
+		Operator::Min,
+		Operator::Max,
+		Operator::GreaterThan,
+		Operator::LessThan,
+		Operator::Equal,
+	];
 
-#[derive(Debug,PartialEq,Eq)]
-struct Packet
-{
-	version: u8,
-	content: Content,
-}
-
-impl Packet
-{
-	fn calculate(&self) -> u128
+	/// The packet type ID, `4` being taken by literals.
+	pub fn id(&self) -> u8
 	{
-		match self.content
+		match self
 		{
-			Content::Literal(lit) => lit,
-			Content::Operator(Operator::Sum, ref vec) => vec.iter().map(Packet::calculate).sum(),
-			Content::Operator(Operator::Product, ref vec) => vec.iter().map(Packet::calculate).product(),
-			Content::Operator(Operator::Min, ref vec) => vec.iter().map(Packet::calculate).min().unwrap(),
-			Content::Operator(Operator::Max, ref vec) => vec.iter().map(Packet::calculate).max().unwrap(),
-			Content::Operator(Operator::GreaterThan, ref vec) => vec.iter().skip(1).map(Packet::calculate).all(|x| vec[0].calculate() > x) as u128,
-			Content::Operator(Operator::LessThan, ref vec) => vec.iter().skip(1).map(Packet::calculate).all(|x| vec[0].calculate() < x) as u128,
-			Content::Operator(Operator::Equal, ref vec) => vec.iter().skip(1).map(Packet::calculate).all(|x| vec[0].calculate() == x) as u128,
+			Operator::Sum => 0,
+			Operator::Product => 1,
+			Operator::Min => 2,
+			Operator::Max => 3,
+			Operator::GreaterThan => 5,
+			Operator::LessThan => 6,
+			Operator::Equal => 7,
 		}
 	}
-}
 
-fn literal(mut input: (&[u8], usize)) -> IResult<(&[u8], usize), Content>
-{
-	trace!("parsing literal");
-	let mut num: u128 = 0;
-	loop
+	pub fn name(&self) -> &'static str
 	{
-		trace!("parsing literal: another nibble");
-		let (rest, (cont, nibble)): (_, (u8, u8)) = tuple((take(1_usize), take(4_usize)))(input)?;
+		match self
+		{
+			Operator::Sum => "sum",
+			Operator::Product => "product",
+			Operator::Min => "min",
+			Operator::Max => "max",
+			Operator::GreaterThan => "gt",
+			Operator::LessThan => "lt",
+			Operator::Equal => "eq",
+		}
+	}
 
-		num <<= 4;
-		num |= nibble as u128;
-		input = rest;
-		if cont != 1
+	/// Whether the operator can be applied to that many sub-packets.
+	pub fn accepts(&self, count: usize) -> bool
+	{
+		match self
 		{
-			break;
+			Operator::GreaterThan | Operator::LessThan | Operator::Equal => count == 2,
+			_ => count >= 1,
 		}
 	}
+}
 
-	Ok((input, Content::Literal(num)))
+#[derive(Debug,PartialEq,Eq,Clone)]
+pub enum Content
+{
+	Literal(u128),
+	Operator(Operator, Vec<Packet>),
 }
 
-fn operator(type_: usize, input: (&[u8], usize)) -> IResult<(&[u8], usize), Content>
+/// A packet of the Buoyancy Interchange Transmission System.
+///
+/// Packets are decoded from and encoded to their hexadecimal transmission, besides that they are
+/// written as expressions like `v6:sum(v1:3, v0:4)`, the version defaulting to zero.
+/// The alternate format indents the expression over several lines.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2021::d16::{ Content, Operator, Packet };
+/// let packet = Packet::decode("D2FE28").unwrap();
+/// assert_eq!(packet, Packet { version: 6, content: Content::Literal(2021), });
+/// assert_eq!(packet.encode().unwrap(), "D2FE28");
+///
+/// let packet = Packet::decode("EE00D40C823060").unwrap();
+/// assert_eq!(packet.to_string(), "v7:max(v2:1, v4:2, v1:3)");
+/// assert_eq!(format!("{:#}", packet), "v7:max(\n  v2:1,\n  v4:2,\n  v1:3\n)");
+/// assert_eq!(packet.encode().unwrap(), "EE00D40C823060");
+///
+/// let packet: Packet = "gt(product(6, 7), v3:sum(20, 20))".parse().unwrap();
+/// assert_eq!(packet.calculate().unwrap(), 1);
+/// assert_eq!(Packet::decode(&packet.encode().unwrap()).unwrap(), packet);
+///
+/// // errors point at the offending bit
+/// let err = Packet::decode("D2FE").unwrap_err();
+/// assert!(format!("{:?}", err).contains("at bit 16"), "{:?}", err);
+/// let err = Packet::decode("D2FE29").unwrap_err();
+/// assert!(format!("{:?}", err).contains("at bit 23"), "{:?}", err);
+/// let comparison = "gt(1, 2, 3)".parse::<Packet>();
+/// assert!(comparison.is_err());
+/// assert!(Packet::decode("XY").is_err());
+/// assert!("v8:1".parse::<Packet>().unwrap().encode().is_err());
+///
+/// // round trips of random packet trees
+/// let mut seed = 5u64;
+/// let mut random = |max: u64|
+/// {
+///     seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+///     (seed >> 33) % max
+/// };
+/// fn generate(random: &mut impl FnMut(u64) -> u64, depth: usize) -> Packet
+/// {
+///     let version = random(8) as u8;
+///     let content = match random(4)
+///     {
+///         _ if depth == 0 => Content::Literal(random(1 << 20) as u128),
+///         0 => Content::Literal(random(u64::MAX) as u128 * random(u64::MAX) as u128),
+///         1 => Content::Operator(
+///             [Operator::GreaterThan, Operator::LessThan, Operator::Equal][random(3) as usize],
+///             vec![generate(random, depth - 1), generate(random, depth - 1)],
+///         ),
+///         _ => Content::Operator(
+///             [Operator::Sum, Operator::Product, Operator::Min, Operator::Max][random(4) as usize],
+///             (0..1 + random(4)).map(|_| generate(random, depth - 1)).collect(),
+///         ),
+///     };
+///     Packet { version, content, }
+/// }
+/// for _ in 0..200
+/// {
+///     let depth = random(5) as usize;
+///     let packet = generate(&mut random, depth);
+///     let hex = packet.encode().unwrap();
+///     assert_eq!(Packet::decode(&hex).unwrap(), packet, "{}", hex);
+///     assert_eq!(packet.to_string().parse::<Packet>().unwrap(), packet);
+///     assert_eq!(format!("{:#}", packet).parse::<Packet>().unwrap(), packet);
+/// }
+///
+/// // literals use all 128 bits, a 33rd group of four bits does not fit anymore
+/// for &literal in [u128::MAX, 1 << 124, (1 << 125) - 1].iter()
+/// {
+///     let packet = Packet { version: 1, content: Content::Literal(literal), };
+///     assert_eq!(Packet::decode(&packet.encode().unwrap()).unwrap(), packet);
+/// }
+/// let bits = format!("000100{}000000", "10001".repeat(32));
+/// let hex = (0..bits.len()).step_by(4).map(|idx| format!("{:X}", u8::from_str_radix(&bits[idx..idx + 4], 2).unwrap())).collect::<String>();
+/// let err = Packet::decode(&hex).unwrap_err();
+/// assert!(format!("{:?}", err).contains("exceeds 128 bits"), "{:?}", err);
+///
+/// // sub-packets exceeding what fits into a count use the length in bits
+/// let many = Packet { version: 0, content: Content::Operator(Operator::Sum, vec!["1".parse().unwrap(); 2500]), };
+/// assert_eq!(Packet::decode(&many.encode().unwrap()).unwrap(), many);
+/// assert_eq!(many.calculate().unwrap(), 2500);
+/// let too_many = Packet { version: 0, content: Content::Operator(Operator::Sum, vec!["1".parse().unwrap(); 3000]), };
+/// assert!(too_many.encode().is_err());
+/// ```
+#[derive(Debug,PartialEq,Eq,Clone)]
+pub struct Packet
 {
-	trace!("parsing operator");
-	let (input, len_type): (_, u8) = take(1_usize)(input)?;
+	pub version: u8,
+	pub content: Content,
+}
 
-	let (input, packets) = if len_type == 1
+/// Reads a transmission bit by bit, keeping track of the offset for error messages.
+struct Reader
+{
+	bits: Vec<bool>,
+	offset: usize,
+}
+
+impl Reader
+{
+	fn read(&mut self, count: usize) -> Result<u128>
 	{
-		let (input, packet_count) = take(11_usize)(input)?;
-		trace!("parsing operator: reading {} packets", packet_count);
-		let (input, packets) = count(packet, packet_count)(input)?;
-		(input, packets)
+		anyhow::ensure!(self.offset + count <= self.bits.len(), "unexpected end of transmission at bit {} reading {} bits", self.offset, count);
+		let value = self.bits[self.offset..self.offset + count].iter()
+			.fold(0, |value, &bit| value << 1 | bit as u128);
+		self.offset += count;
+		Ok(value)
 	}
-	else
-	{
-		let (input, bit_length): (_, usize) = take(15_usize)(input)?;
-		let (input, partial) = take(bit_length % 8)(input)?;
-		let (input, full_bytes): (_, Vec<u8>) = count(take(8_usize), bit_length / 8)(input)?;
-		let mut v = vec![partial];
-		v.extend(full_bytes);
-		trace!("parsing operator: split off {} bits: {:?}", bit_length, (&v, 8 - (bit_length % 8)));
-		let (slice, remaining_bits) = match 8 - (bit_length % 8)
-		{
-			8 => (&v[1..], 0),
-			remaining_bits => (&v[..], remaining_bits),
-		};
-		let (_, packets) = terminated(many0(packet), eof)((slice, remaining_bits)).map_err(|err| unimplemented!("{}", err))?;
-		(input, packets)
-	};
 
-	let type_ = match type_
+	fn packet(&mut self) -> Result<Packet>
 	{
-		0 => Operator::Sum,
-		1 => Operator::Product,
-		2 => Operator::Min,
-		3 => Operator::Max,
-		5 => Operator::GreaterThan,
-		6 => Operator::LessThan,
-		7 => Operator::Equal,
-		_ =>
+		let start = self.offset;
+		let version = self.read(3)? as u8;
+		let id = self.read(3)? as u8;
+		trace!("packet at bit {} has version {} and type {}", start, version, id);
+
+		let content = if id == 4
 		{
-			let _: (_, usize) = fail(input)?;
-			unreachable!();
-		},
-	};
+			let mut literal: u128 = 0;
+			loop
+			{
+				let more = self.read(1)? == 1;
+				anyhow::ensure!(literal >> 124 == 0, "literal at bit {} exceeds 128 bits at bit {}", start, self.offset);
+				literal = literal << 4 | self.read(4)?;
+				if !more
+				{
+					break Content::Literal(literal);
+				}
+			}
+		}
+		else
+		{
+			let operator = *Operator::ALL.iter().find(|operator| operator.id() == id).unwrap();
+			let mut packets = Vec::new();
+			if self.read(1)? == 1
+			{
+				for _ in 0..self.read(11)?
+				{
+					packets.push(self.packet()?);
+				}
+			}
+			else
+			{
+				let length = self.read(15)? as usize;
+				let end = self.offset + length;
+				anyhow::ensure!(end <= self.bits.len(), "sub-packets of {} bits at bit {} exceed the transmission", length, self.offset);
+				while self.offset < end
+				{
+					packets.push(self.packet()?);
+				}
+				anyhow::ensure!(self.offset == end, "sub-packets of the packet at bit {} end at bit {} instead of {}", start, self.offset, end);
+			}
+			anyhow::ensure!(operator.accepts(packets.len()), "{} packet at bit {} has {} sub-packets", operator.name(), start, packets.len());
+			Content::Operator(operator, packets)
+		};
 
-	Ok((input, Content::Operator(type_, packets.into())))
+		Ok(Packet { version, content, })
+	}
 }
 
-fn packet(input: (&[u8], usize)) -> IResult<(&[u8], usize), Packet>
+fn write(bits: &mut Vec<bool>, value: u128, count: usize)
 {
-	trace!("parsing packet: {:?}", input);
-	let (input, version) = take(3_usize)(input)?;
-	trace!("parsing packet, version: {}", version);
-	let (input, type_) = take(3_usize)(input)?;
-	trace!("parsing packet, type: {}", type_);
+	bits.extend((0..count).rev().map(|idx| value >> idx & 1 == 1));
+}
 
-	let (input, content) = match type_
+/// Parses a single expression and returns the remaining input.
+fn expression(input: &str) -> Result<(Packet, &str)>
+{
+	let mut input = input.trim_start();
+	let mut version = 0;
+	if let Some(rest) = input.strip_prefix('v')
 	{
-		4 => literal(input)?,
-		_ => operator(type_, input)?,
-	};
+		let (digits, rest) = rest.split_once(':').ok_or_else(|| anyhow!("missing ':' after the version in {:?}", input))?;
+		version = digits.trim().parse()?;
+		input = rest.trim_start();
+	}
 
-	trace!("parsing packet, content: {:?}", content);
+	let (word, rest) = input.split_at(input.find(|ch: char| !ch.is_ascii_alphanumeric()).unwrap_or(input.len()));
+	if word.starts_with(|ch: char| ch.is_ascii_digit())
+	{
+		let literal = word.parse().with_context(|| format!("invalid literal {:?}", word))?;
+		return Ok((Packet { version, content: Content::Literal(literal), }, rest.trim_start()));
+	}
 
-	Ok((input, Packet
+	let operator = *Operator::ALL.iter()
+		.find(|operator| operator.name() == word)
+		.ok_or_else(|| anyhow!("expected a literal or an operator, got {:?}", input))?;
+	let mut rest = rest.trim_start().strip_prefix('(').ok_or_else(|| anyhow!("missing '(' after {}", word))?;
+	let mut packets = Vec::new();
+	loop
 	{
-		version,
-		content,
-	}))
-}
+		let (packet, remaining) = expression(rest)?;
+		packets.push(packet);
+		if let Some(remaining) = remaining.strip_prefix(',')
+		{
+			rest = remaining;
+			continue;
+		}
+		rest = remaining.strip_prefix(')').ok_or_else(|| anyhow!("expected ',' or ')', got {:?}", remaining))?;
+		break;
+	}
+	anyhow::ensure!(operator.accepts(packets.len()), "{} cannot be applied to {} sub-packets", word, packets.len());
 
+	Ok((Packet { version, content: Content::Operator(operator, packets), }, rest.trim_start()))
+}
 
-impl super::super::Solution for Solution
+impl std::str::FromStr for Packet
 {
-	fn solve(&self) -> Result<String>
+	type Err = Error;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
-		debug!("called with input: {}", self.input);
+		let (packet, rest) = expression(input)?;
+		if !rest.is_empty()
+		{
+			Err(anyhow!("unexpected {:?} after the expression", rest))?;
+		}
+		Ok(packet)
+	}
+}
 
-		let input = self.input.trim()
-			.as_bytes()
-			.chunks(2)
-			.map(|v|
+impl Packet
+{
+	fn fmt_depth(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result
+	{
+		write!(f, "v{}:", self.version)?;
+		match self.content
+		{
+			Content::Literal(literal) => write!(f, "{}", literal),
+			Content::Operator(operator, ref packets) =>
 			{
-				let a = v[0];
-				let b = v[1];
-
-				let a = match a
+				write!(f, "{}(", operator.name())?;
+				for (idx, packet) in packets.iter().enumerate()
 				{
-					b'0'..=b'9' => a - b'0',
-					b'A'..=b'F' => a - b'A' + 10,
-					_ => Err(Error::AocParsing).context("non-hex-digit encountered")?,
-				};
-				let b = match b
+					match (f.alternate(), idx)
+					{
+						(true, 0) => write!(f, "\n{:1$}", "", 2 * (depth + 1))?,
+						(true, _) => write!(f, ",\n{:1$}", "", 2 * (depth + 1))?,
+						(false, 0) => {},
+						(false, _) => write!(f, ", ")?,
+					}
+					packet.fmt_depth(f, depth + 1)?;
+				}
+				if f.alternate()
 				{
-					b'0'..=b'9' => b - b'0',
-					b'A'..=b'F' => b - b'A' + 10,
-					_ => Err(Error::AocParsing).context("non-hex-digit encountered")?,
-				};
-				Ok(a << 4 | b)
-			})
-			.collect::<Result<Vec<_>>>()?;
+					write!(f, "\n{:1$}", "", 2 * depth)?;
+				}
+				write!(f, ")")
+			},
+		}
+	}
 
-		debug!("actual input: {:?}", input);
+	/// Decodes the hexadecimal transmission, which may only be followed by zero padding.
+	pub fn decode(hex: &str) -> Result<Self>
+	{
+		let bits = hex.trim()
+			.chars()
+			.enumerate()
+			.map(|(idx, ch)|
+			{
+				let nibble = ch.to_digit(16)
+					.ok_or(Error::AocParsing)
+					.with_context(|| format!("non-hex-digit {:?} encountered at bit {}", ch, 4 * idx))?;
+				Ok((0..4).rev().map(move |bit| nibble >> bit & 1 == 1))
+			})
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.flatten()
+			.collect();
 
-		let (_, packet) = bits::<_, _, _, nom::error::Error<_>, _>(terminated(packet, terminated(many0(tag(0, 1_usize)), eof)))(&input)
-			.map_err(|err| anyhow!("{}", err))?;
+		let mut reader = Reader { bits, offset: 0, };
+		let packet = reader.packet()?;
+		if let Some(idx) = reader.bits[reader.offset..].iter().position(|&bit| bit)
+		{
+			bail!("unexpected set bit at bit {} after the outermost packet", reader.offset + idx);
+		}
+		Ok(packet)
+	}
 
-		if self.action == Action::SumVersion
+	fn encode_into(&self, bits: &mut Vec<bool>) -> Result<()>
+	{
+		anyhow::ensure!(self.version < 8, "version {} does not fit into 3 bits", self.version);
+		write(bits, self.version as u128, 3);
+		match self.content
 		{
-			let mut vec = vec![packet];
-			let version_sum = std::iter::from_fn(move ||
+			Content::Literal(literal) =>
 			{
-				if vec.is_empty()
+				write(bits, 4, 3);
+				let groups = (0..32).rev().find(|idx| literal >> (4 * idx) != 0).unwrap_or(0) + 1;
+				for idx in (0..groups).rev()
 				{
-					return None;
+					write(bits, (idx > 0) as u128, 1);
+					write(bits, literal >> (4 * idx) & 0xf, 4);
 				}
-
-				let packet = vec.pop().unwrap();
-
+			},
+			Content::Operator(operator, ref packets) =>
+			{
+				anyhow::ensure!(operator.accepts(packets.len()), "{} cannot be applied to {} sub-packets", operator.name(), packets.len());
+				write(bits, operator.id() as u128, 3);
+				if packets.len() < 1 << 11
+				{
+					write(bits, 1, 1);
+					write(bits, packets.len() as u128, 11);
+					for packet in packets
+					{
+						packet.encode_into(bits)?;
+					}
+				}
+				else
 				{
-					if let Content::Operator(_, packets) = packet.content
+					let mut sub = Vec::new();
+					for packet in packets
 					{
-						vec.extend(packets);
+						packet.encode_into(&mut sub)?;
 					}
-					Some(packet.version as usize)
+					anyhow::ensure!(sub.len() < 1 << 15, "{} sub-packets taking {} bits fit neither a count nor a length", packets.len(), sub.len());
+					write(bits, 0, 1);
+					write(bits, sub.len() as u128, 15);
+					bits.extend(sub);
 				}
-			}).sum::<usize>();
+			},
+		}
+		Ok(())
+	}
+
+	/// Encodes the packet as a hexadecimal transmission padded to full bytes.
+	///
+	/// Sub-packets are given by their count whenever it fits into 11 bits.
+	pub fn encode(&self) -> Result<String>
+	{
+		let mut bits = Vec::new();
+		self.encode_into(&mut bits)?;
+		bits.resize(bits.len().div_ceil(8) * 8, false);
+		Ok(bits.chunks(4)
+			.map(|nibble| std::char::from_digit(nibble.iter().fold(0, |value, &bit| value << 1 | bit as u32), 16).unwrap().to_ascii_uppercase())
+			.collect())
+	}
 
-			Ok(format!("{}", version_sum))
+	pub fn version_sum(&self) -> usize
+	{
+		self.version as usize + match self.content
+		{
+			Content::Literal(_) => 0,
+			Content::Operator(_, ref packets) => packets.iter().map(Packet::version_sum).sum(),
+		}
+	}
+
+	pub fn calculate(&self) -> Result<u128>
+	{
+		let packets = match self.content
+		{
+			Content::Literal(literal) => return Ok(literal),
+			Content::Operator(operator, ref packets) =>
+			{
+				anyhow::ensure!(operator.accepts(packets.len()), "{} cannot be applied to {} sub-packets", operator.name(), packets.len());
+				packets
+			},
+		};
+		let values = packets.iter().map(Packet::calculate).collect::<Result<Vec<_>>>()?;
+		Ok(match self.content
+		{
+			Content::Operator(Operator::Sum, _) => values.into_iter().try_fold(0u128, u128::checked_add).context("sum overflows 128 bits")?,
+			Content::Operator(Operator::Product, _) => values.into_iter().try_fold(1u128, u128::checked_mul).context("product overflows 128 bits")?,
+			Content::Operator(Operator::Min, _) => values.into_iter().min().unwrap(),
+			Content::Operator(Operator::Max, _) => values.into_iter().max().unwrap(),
+			Content::Operator(Operator::GreaterThan, _) => (values[0] > values[1]) as u128,
+			Content::Operator(Operator::LessThan, _) => (values[0] < values[1]) as u128,
+			Content::Operator(Operator::Equal, _) => (values[0] == values[1]) as u128,
+			Content::Literal(_) => unreachable!(),
+		})
+	}
+}
+
+/// Written as expression, the alternate format indents the sub-packets.
+impl std::fmt::Display for Packet
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		self.fmt_depth(f, 0)
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let packet = Packet::decode(&self.input)?;
+		if self.ast
+		{
+			println!("{:#}", packet);
+		}
+
+		if self.action == Action::SumVersion
+		{
+			Ok(format!("{}", packet.version_sum()))
 		}
 		else
 		{
-			Ok(format!("{}", packet.calculate()))
+			Ok(format!("{}", packet.calculate()?))
 		}
 	}
 }
-
@@ -71,7 +71,7 @@ impl super::super::Solution for Solution
 					.scan(0, |a, b| { *a += b; Some(*a) })
 					.enumerate()
 					.filter(move |&(_, distance)| distance >= x_low && distance <= x_high)
-					.map(|(steps, _)| ((steps + 1) as isize))
+					.map(|(steps, _)| (steps + 1) as isize)
 					.max()
 					.unwrap_or(0);
 
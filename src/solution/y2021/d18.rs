@@ -3,9 +3,8 @@ use crate::error::*;
 use nom::
 {
 	character::complete::*,
-	number::complete::*,
+	combinator::*,
 	sequence::*,
-	multi::*,
 	IResult,
 };
 
@@ -39,6 +38,7 @@ pub struct Solution
 {
 	input: String,
 	part: Part,
+	trace: bool,
 }
 
 impl Solution
@@ -49,6 +49,7 @@ impl Solution
 		{
 			input,
 			part: Part::Part1,
+			trace: false,
 		}
 	}
 
@@ -58,46 +59,165 @@ impl Solution
 		{
 			input,
 			part: Part::Part2,
+			trace: false,
 		}
 	}
+
+	pub fn trace(self, trace: bool) -> Self
+	{
+		Self { trace, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("trace")
+				.long("trace")
+				.help("print every addition and reduction step")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
 }
 
-#[derive(Clone)]
-enum Value
+/// A single step of reducing a snailfish number.
+#[derive(Debug,Clone,Copy,Eq,PartialEq)]
+pub enum Reduction
 {
-	Number(usize),
-	Pair(Box<(Value, Value)>),
+	/// The leftmost pair of regular numbers nested inside four pairs exploded.
+	Explode,
+	/// The leftmost regular number of ten or more was split.
+	Split,
 }
 
-impl std::fmt::Debug for Value
+impl std::fmt::Display for Reduction
 {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
 	{
 		match self
 		{
-			Value::Number(num) => f.write_fmt(format_args!("{}", num)),
-			Value::Pair(inner) => f.debug_list().entries([&inner.0, &inner.1]).finish(),
+			Reduction::Explode => write!(f, "explode"),
+			Reduction::Split => write!(f, "split"),
 		}
 	}
 }
 
-impl Value
+/// The arithmetic shared by both representations of snailfish numbers.
+///
+/// Adding numbers pairs them and reduces the pair, there is no neutral element so the [`Sum`] of
+/// an iterator is an [`Option`].
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2021::d18::{ Reduction, Snailfish, SnailfishNumber };
+/// let explode = |input: &str|
+/// {
+///     let mut number: SnailfishNumber = input.parse().unwrap();
+///     assert_eq!(number.step(), Some(Reduction::Explode));
+///     number.to_string()
+/// };
+/// assert_eq!(explode("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]");
+/// assert_eq!(explode("[7,[6,[5,[4,[3,2]]]]]"), "[7,[6,[5,[7,0]]]]");
+/// assert_eq!(explode("[[6,[5,[4,[3,2]]]],1]"), "[[6,[5,[7,0]]],3]");
+/// assert_eq!(explode("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"), "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
+///
+/// let a: SnailfishNumber = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+/// let mut number = a.pair("[1,1]".parse().unwrap());
+/// let mut trace = Vec::new();
+/// number.reduce_traced(|reduction, number| trace.push(format!("after {}: {}", reduction, number)));
+/// assert_eq!(trace,
+/// [
+///     "after explode: [[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+///     "after explode: [[[[0,7],4],[15,[0,13]]],[1,1]]",
+///     "after split: [[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+///     "after split: [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+///     "after explode: [[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+/// ]);
+///
+/// let sum: Option<SnailfishNumber> = (1..=6).map(|n| format!("[{},{}]", n, n).parse().unwrap()).sum();
+/// assert_eq!(sum.unwrap().to_string(), "[[[[5,0],[7,4]],[5,5]],[6,6]]");
+/// assert_eq!("[[9,1],[1,9]]".parse::<SnailfishNumber>().unwrap().magnitude(), 129);
+/// assert!("[1,2".parse::<SnailfishNumber>().is_err());
+/// assert!("[1,2,3]".parse::<SnailfishNumber>().is_err());
+/// ```
+///
+/// Both representations agree on every step of the larger example homework:
+///
+/// ```
+/// # use adventofcode::solution::y2021::d18::{ FlatSnailfishNumber, Snailfish, SnailfishNumber };
+/// let homework =
+/// [
+///     "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
+///     "[[[5,[2,8]],4],[5,[[9,9],0]]]",
+///     "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]",
+///     "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
+///     "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]",
+///     "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]",
+///     "[[[[5,4],[7,7]],8],[[8,3],8]]",
+///     "[[9,3],[[9,9],[6,[4,9]]]]",
+///     "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]",
+///     "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]",
+/// ];
+/// let mut tree = homework[0].parse::<SnailfishNumber>().unwrap();
+/// let mut flat = homework[0].parse::<FlatSnailfishNumber>().unwrap();
+/// for number in homework[1..].iter()
+/// {
+///     tree = tree.pair(number.parse().unwrap());
+///     flat = flat.pair(number.parse().unwrap());
+///     assert_eq!(FlatSnailfishNumber::from(&tree), flat);
+///     assert_eq!(SnailfishNumber::from(&flat), tree);
+///
+///     let (mut tree_trace, mut flat_trace) = (Vec::new(), Vec::new());
+///     tree.reduce_traced(|reduction, number| tree_trace.push((reduction, number.to_string())));
+///     flat.reduce_traced(|reduction, number| flat_trace.push((reduction, number.to_string())));
+///     assert_eq!(tree_trace, flat_trace);
+/// }
+/// assert_eq!(flat.to_string(), "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]");
+/// assert_eq!((tree.magnitude(), flat.magnitude()), (4140, 4140));
+/// ```
+/// ```
+pub trait Snailfish: Sized + Clone + std::fmt::Display + std::str::FromStr<Err = Error> + std::ops::Add<Output = Self>
 {
-	fn magnitude(&self) -> usize
+	/// The pair of both numbers without reducing it.
+	fn pair(self, other: Self) -> Self;
+
+	/// Performs the first applicable reduction, exploding takes precedence over splitting.
+	fn step(&mut self) -> Option<Reduction>;
+
+	fn magnitude(&self) -> usize;
+
+	/// Reduces the number, calling `trace` with the number after every step.
+	fn reduce_traced<F: FnMut(Reduction, &Self)>(&mut self, mut trace: F)
 	{
-		match self
+		while let Some(reduction) = self.step()
 		{
-			&Value::Number(num) => num,
-			Value::Pair(inner) => inner.0.magnitude() * 3 + inner.1.magnitude() * 2,
+			trace(reduction, self);
 		}
 	}
 
+	fn reduce(&mut self)
+	{
+		self.reduce_traced(|_, _| {});
+	}
+}
+
+/// A snailfish number as a tree of pairs.
+#[derive(Debug,Clone,Eq,PartialEq)]
+pub enum SnailfishNumber
+{
+	Regular(usize),
+	Pair(Box<(SnailfishNumber, SnailfishNumber)>),
+}
+
+impl SnailfishNumber
+{
 	fn leftmost_mut(&mut self) -> &mut usize
 	{
 		match self
 		{
-			Value::Pair(inner) => inner.0.leftmost_mut(),
-			Value::Number(ref mut num) => num,
+			SnailfishNumber::Pair(inner) => inner.0.leftmost_mut(),
+			SnailfishNumber::Regular(ref mut num) => num,
 		}
 	}
 
@@ -105,162 +225,348 @@ impl Value
 	{
 		match self
 		{
-			Value::Pair(inner) => inner.1.rightmost_mut(),
-			Value::Number(ref mut num) => num,
+			SnailfishNumber::Pair(inner) => inner.1.rightmost_mut(),
+			SnailfishNumber::Regular(ref mut num) => num,
 		}
 	}
 
-	fn explode(&mut self, depth: usize, left: &mut usize, right: &mut usize)
+	/// Explodes the leftmost pair nested deep enough, returning the values still to be added to the
+	/// regular numbers left and right of it.
+	fn explode(&mut self, depth: usize) -> Option<(usize, usize)>
 	{
-		if let Value::Pair(ref mut inner) = self
+		let inner = match self
 		{
-			inner.0.explode(depth + 1, left, inner.1.leftmost_mut());
-			inner.1.explode(depth + 1, inner.0.rightmost_mut(), right);
-			if depth >= 4
-			{
-				if let (Value::Number(n1), Value::Number(n2)) = inner.as_ref()
-				{
-					*left += n1;
-					*right += n2;
-					*self = Value::Number(0);
-				}
-			}
+			SnailfishNumber::Regular(_) => return None,
+			SnailfishNumber::Pair(inner) => inner,
+		};
+		if let (true, SnailfishNumber::Regular(left), SnailfishNumber::Regular(right)) = (depth >= 4, &inner.0, &inner.1)
+		{
+			let carry = (*left, *right);
+			*self = SnailfishNumber::Regular(0);
+			return Some(carry);
 		}
+		if let Some((left, right)) = inner.0.explode(depth + 1)
+		{
+			*inner.1.leftmost_mut() += right;
+			return Some((left, 0));
+		}
+		if let Some((left, right)) = inner.1.explode(depth + 1)
+		{
+			*inner.0.rightmost_mut() += left;
+			return Some((0, right));
+		}
+		None
 	}
 
-	fn split(self) -> (Self, bool)
+	fn split(&mut self) -> bool
 	{
 		match self
 		{
-			Value::Number(num) =>
+			&mut SnailfishNumber::Regular(num) if num >= 10 =>
 			{
-				if num >= 10
-				{
-					(Value::Pair(Box::new((Value::Number(num/2), Value::Number((num+1)/2)))), true)
-				}
-				else
-				{
-					(Value::Number(num), false)
-				}
-			},
-			Value::Pair(mut inner) =>
-			{
-				let (new, cancel) = inner.0.split();
-				inner.0 = new;
-				if cancel
-				{
-					return (Value::Pair(inner), true);
-				}
-				let (new, cancel) = inner.1.split();
-				inner.1 = new;
-				if cancel
-				{
-					return (Value::Pair(inner), true);
-				}
-				(Value::Pair(inner), false)
+				*self = SnailfishNumber::Pair(Box::new((SnailfishNumber::Regular(num / 2), SnailfishNumber::Regular(num.div_ceil(2)))));
+				true
 			},
+			SnailfishNumber::Regular(_) => false,
+			SnailfishNumber::Pair(inner) => inner.0.split() || inner.1.split(),
 		}
 	}
 }
 
-fn value(input: &str) -> IResult<&str, Value>
+impl Snailfish for SnailfishNumber
+{
+	fn pair(self, other: Self) -> Self
+	{
+		SnailfishNumber::Pair(Box::new((self, other)))
+	}
+
+	fn step(&mut self) -> Option<Reduction>
+	{
+		if self.explode(0).is_some()
+		{
+			Some(Reduction::Explode)
+		}
+		else if self.split()
+		{
+			Some(Reduction::Split)
+		}
+		else
+		{
+			None
+		}
+	}
+
+	fn magnitude(&self) -> usize
+	{
+		match self
+		{
+			&SnailfishNumber::Regular(num) => num,
+			SnailfishNumber::Pair(inner) => inner.0.magnitude() * 3 + inner.1.magnitude() * 2,
+		}
+	}
+}
+
+fn value(input: &str) -> IResult<&str, SnailfishNumber>
 {
 	if input.starts_with('[')
 	{
 		let (input, tuple) = delimited(char('['), separated_pair(value, char(','), value), char(']'))(input)?;
-		Ok((input, Value::Pair(Box::new(tuple))))
+		Ok((input, SnailfishNumber::Pair(Box::new(tuple))))
 	}
 	else
 	{
-		let (input, num) = double(input)?;
-		Ok((input, Value::Number(num as usize)))
+		let (input, num) = map_res(digit1, str::parse)(input)?;
+		Ok((input, SnailfishNumber::Regular(num)))
 	}
 }
 
-impl super::super::Solution for Solution
+impl std::str::FromStr for SnailfishNumber
 {
-	fn solve(&self) -> Result<String>
+	type Err = Error;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
-		debug!("called with input: {}", self.input);
+		let (_, number) = terminated(value, eof)(input.trim())
+			.map_err(|err| anyhow!("{}", err))?;
+		Ok(number)
+	}
+}
+
+impl std::fmt::Display for SnailfishNumber
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			SnailfishNumber::Regular(num) => write!(f, "{}", num),
+			SnailfishNumber::Pair(inner) => write!(f, "[{},{}]", inner.0, inner.1),
+		}
+	}
+}
+
+/// A snailfish number as its regular numbers from left to right along with their depth.
+///
+/// Reducing never allocates beyond splitting, which makes it considerably faster than the tree.
+#[derive(Debug,Clone,Eq,PartialEq)]
+pub struct FlatSnailfishNumber
+{
+	/// The regular numbers and how many pairs they are nested in.
+	numbers: Vec<(usize, usize)>,
+}
+
+impl FlatSnailfishNumber
+{
+	/// Writes the pair or regular number at the given depth starting at `idx`, returning the index
+	/// after it.
+	fn fmt_at(&self, f: &mut std::fmt::Formatter<'_>, idx: usize, depth: usize) -> std::result::Result<usize, std::fmt::Error>
+	{
+		let (num, at) = self.numbers[idx];
+		if at == depth
+		{
+			write!(f, "{}", num)?;
+			return Ok(idx + 1);
+		}
+		write!(f, "[")?;
+		let idx = self.fmt_at(f, idx, depth + 1)?;
+		write!(f, ",")?;
+		let idx = self.fmt_at(f, idx, depth + 1)?;
+		write!(f, "]")?;
+		Ok(idx)
+	}
+
+	/// Rebuilds the tree of the pair or regular number at the given depth starting at `idx`.
+	fn tree_at(&self, idx: usize, depth: usize) -> (SnailfishNumber, usize)
+	{
+		let (num, at) = self.numbers[idx];
+		if at == depth
+		{
+			return (SnailfishNumber::Regular(num), idx + 1);
+		}
+		let (left, idx) = self.tree_at(idx, depth + 1);
+		let (right, idx) = self.tree_at(idx, depth + 1);
+		(left.pair(right), idx)
+	}
+}
+
+impl Snailfish for FlatSnailfishNumber
+{
+	fn pair(self, other: Self) -> Self
+	{
+		let numbers = self.numbers.into_iter()
+			.chain(other.numbers)
+			.map(|(num, depth)| (num, depth + 1))
+			.collect();
+		Self { numbers, }
+	}
+
+	/// The leftmost adjacent regular numbers of equal depth below four pairs always form a pair, as
+	/// the depth strictly increases towards the leftmost pair of regular numbers in a subtree.
+	fn step(&mut self) -> Option<Reduction>
+	{
+		if let Some(idx) = self.numbers.windows(2).position(|window| window[0].1 > 4 && window[0].1 == window[1].1)
+		{
+			let ((left, depth), (right, _)) = (self.numbers[idx], self.numbers[idx + 1]);
+			if idx > 0
+			{
+				self.numbers[idx - 1].0 += left;
+			}
+			if let Some(next) = self.numbers.get_mut(idx + 2)
+			{
+				next.0 += right;
+			}
+			self.numbers[idx] = (0, depth - 1);
+			self.numbers.remove(idx + 1);
+			return Some(Reduction::Explode);
+		}
 
-		let mut overflow_left = 0;
-		let mut overflow_right = 0;
+		let idx = self.numbers.iter().position(|&(num, _)| num >= 10)?;
+		let (num, depth) = self.numbers[idx];
+		self.numbers[idx] = (num / 2, depth + 1);
+		self.numbers.insert(idx + 1, (num.div_ceil(2), depth + 1));
+		Some(Reduction::Split)
+	}
 
-		let values = many1(terminated(value, newline))(&self.input)
-			.map_err(|err| anyhow!("{}", err))?
-			.1
-			.into_iter()
-			.map(|mut value|
+	/// Combines the regular numbers like a shift-reduce parser, the two topmost entries on the
+	/// stack forming a pair whenever their depth matches.
+	fn magnitude(&self) -> usize
+	{
+		let mut stack: Vec<(usize, usize)> = Vec::new();
+		for &number in self.numbers.iter()
+		{
+			stack.push(number);
+			while let [.., (left, depth), (right, other)] = stack[..]
 			{
-				loop
+				if depth != other
 				{
-					trace!("current: {:?}", value);
-					value.explode(0, &mut overflow_left, &mut overflow_right);
-					let (new, cancel) = value.split();
-					value = new;
-					if cancel
-					{
-						continue;
-					}
 					break;
 				}
+				stack.truncate(stack.len() - 2);
+				stack.push((3 * left + 2 * right, depth - 1));
+			}
+		}
+		stack[0].0
+	}
+}
 
-				value
-			})
-			.collect::<Vec<_>>();
+impl From<&SnailfishNumber> for FlatSnailfishNumber
+{
+	fn from(number: &SnailfishNumber) -> Self
+	{
+		fn flatten(number: &SnailfishNumber, depth: usize, numbers: &mut Vec<(usize, usize)>)
+		{
+			match number
+			{
+				&SnailfishNumber::Regular(num) => numbers.push((num, depth)),
+				SnailfishNumber::Pair(inner) =>
+				{
+					flatten(&inner.0, depth + 1, numbers);
+					flatten(&inner.1, depth + 1, numbers);
+				},
+			}
+		}
+
+		let mut numbers = Vec::new();
+		flatten(number, 0, &mut numbers);
+		Self { numbers, }
+	}
+}
+
+impl From<&FlatSnailfishNumber> for SnailfishNumber
+{
+	fn from(number: &FlatSnailfishNumber) -> Self
+	{
+		number.tree_at(0, 0).0
+	}
+}
+
+impl std::str::FromStr for FlatSnailfishNumber
+{
+	type Err = Error;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		Ok((&input.parse::<SnailfishNumber>()?).into())
+	}
+}
+
+impl std::fmt::Display for FlatSnailfishNumber
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		self.fmt_at(f, 0, 0).map(|_| ())
+	}
+}
+
+macro_rules! impl_arithmetic
+{
+	($type:ty) =>
+	{
+		impl std::ops::Add for $type
+		{
+			type Output = Self;
+
+			fn add(self, other: Self) -> Self
+			{
+				let mut number = self.pair(other);
+				number.reduce();
+				number
+			}
+		}
+
+		impl std::iter::Sum<$type> for Option<$type>
+		{
+			fn sum<I: Iterator<Item = $type>>(iter: I) -> Self
+			{
+				iter.reduce(std::ops::Add::add)
+			}
+		}
+	};
+}
+
+impl_arithmetic!(SnailfishNumber);
+impl_arithmetic!(FlatSnailfishNumber);
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
 
 		if self.part == Part::Part1
 		{
-			let value = values
-				.into_iter()
-				.reduce(|a, b|
+			let mut numbers = self.input.lines()
+				.map(str::parse::<SnailfishNumber>)
+				.collect::<std::result::Result<Vec<_>, _>>()?
+				.into_iter();
+
+			let number = if self.trace
+			{
+				let first = numbers.next().ok_or(Error::AocNoSolution)?;
+				numbers.fold(first, |sum, number|
 				{
-					let mut value = Value::Pair(Box::new((a, b)));
-
-					loop
-					{
-						trace!("current: {:?}", value);
-						value.explode(0, &mut overflow_left, &mut overflow_right);
-						let (new, cancel) = value.split();
-						value = new;
-						if cancel
-						{
-							continue;
-						}
-						break;
-					}
-
-					value
+					let mut sum = sum.pair(number);
+					println!("after addition: {}", sum);
+					sum.reduce_traced(|reduction, number| println!("after {}: {}", reduction, number));
+					sum
 				})
-				.ok_or(Error::AocNoSolution)?;
+			}
+			else
+			{
+				numbers.sum::<Option<_>>().ok_or(Error::AocNoSolution)?
+			};
 
-			Ok(format!("{}", value.magnitude()))
+			Ok(format!("{}", number.magnitude()))
 		}
 		else
 		{
-			let max_magnitude = values.iter()
-				.cloned()
-				.flat_map(|v1| values.iter().cloned().map(move |v2| (v1.clone(), v2)))
-				.map(|(a, b)|
-				{
-					let mut value = Value::Pair(Box::new((a, b)));
-
-					loop
-					{
-						trace!("current: {:?}", value);
-						value.explode(0, &mut overflow_left, &mut overflow_right);
-						let (new, cancel) = value.split();
-						value = new;
-						if cancel
-						{
-							continue;
-						}
-						break;
-					}
-
-					value
-				})
-				.map(|v| v.magnitude())
+			let numbers = self.input.lines()
+				.map(str::parse::<FlatSnailfishNumber>)
+				.collect::<std::result::Result<Vec<_>, _>>()?;
+
+			let max_magnitude = numbers.iter()
+				.enumerate()
+				.flat_map(|(i, a)| numbers.iter().enumerate().filter(move |&(j, _)| i != j).map(move |(_, b)| (a, b)))
+				.map(|(a, b)| (a.clone() + b.clone()).magnitude())
 				.max()
 				.ok_or(Error::AocNoSolution)?;
 
@@ -268,4 +574,3 @@ impl super::super::Solution for Solution
 		}
 	}
 }
-
@@ -13,6 +13,8 @@ use nom::
 	Parser,
 };
 
+use super::super::common::geometry::{ Rotation, Transform, Vec3 };
+
 #[derive(Debug,Eq,PartialEq)]
 enum Part
 {
@@ -20,12 +22,11 @@ enum Part
 	Part2,
 }
 
-// TODO: day 19 pt2 is missing so far
-
 /// # Examples
 ///
 /// ```
 /// # use adventofcode::solution::{ y2021::d19::Solution, Solution as S };
+/// # use adventofcode::solution::common::geometry::Vec3;
 /// # env_logger::init();
 /// let input = "--- scanner 0 ---\n\
 ///     404,-588,-901\n\
@@ -164,13 +165,26 @@ enum Part
 ///     -652,-548,-490\n\
 ///     30,-46,-14\n";
 /// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "79");
-/// # // part 2 is broken
-/// # //assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "3621");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "3621");
+///
+/// // positions relative to the first scanner
+/// let scanners = Solution::part1(input.to_string()).scanners().unwrap();
+/// let positions = scanners.iter().map(|transform| transform.translation).collect::<Vec<_>>();
+/// assert_eq!(positions, vec![
+///     Vec3::new(0, 0, 0),
+///     Vec3::new(68, -1246, -43),
+///     Vec3::new(1105, -1205, 1229),
+///     Vec3::new(-92, -2380, -20),
+///     Vec3::new(-20, -1133, 1061),
+/// ]);
+/// // scanner 1 faces the opposite direction in x and z
+/// assert_eq!(scanners[1].rotation.to_string(), "(-x, y, -z)");
 /// ```
 pub struct Solution
 {
 	input: String,
 	part: Part,
+	dump_scanners: bool,
 }
 
 impl Solution
@@ -181,6 +195,7 @@ impl Solution
 		{
 			input,
 			part: Part::Part1,
+			dump_scanners: false,
 		}
 	}
 
@@ -190,51 +205,110 @@ impl Solution
 		{
 			input,
 			part: Part::Part2,
+			dump_scanners: false,
 		}
 	}
+
+	/// Prints the position and orientation of every scanner once they are aligned.
+	pub fn dump_scanners(self, dump_scanners: bool) -> Self
+	{
+		Self { dump_scanners, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("dump-scanners")
+				.long("dump-scanners")
+				.help("print the position and orientation of every scanner relative to the first one")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
+
+	fn parse(&self) -> Result<Vec<Vec<Vec3<isize>>>>
+	{
+		let (_, scanners) = terminated(many1(terminated(scanner, alt((tag("\n"), eof)))), eof)
+			.parse(&self.input)
+			.map_err(|err| anyhow!("{}", err))?;
+		scanners.iter().for_each(|(id, beacons)| trace!("scanner {} has {} beacons", id, beacons.len()));
+		let scanners = scanners.into_iter().map(|(_, beacons)| beacons).collect::<Vec<_>>();
+		anyhow::ensure!(!scanners.is_empty(), Error::AocParsing);
+		Ok(scanners)
+	}
+
+	/// The transform of every scanner into the coordinates of the first one, its translation is the
+	/// position of the scanner and its rotation the orientation.
+	pub fn scanners(&self) -> Result<Vec<Transform<isize>>>
+	{
+		locate(&self.parse()?)
+	}
 }
 
 type Set<V> = std::collections::BTreeSet<V>;
 
-fn scanner(input: &str) -> IResult<&str, (usize, Vec<(isize, isize, isize)>)>
+fn scanner(input: &str) -> IResult<&str, (usize, Vec<Vec3<isize>>)>
 {
 	trace!("parsing scanner");
 	let (input, id) = delimited(tag("--- scanner "), map(double, |d| d as usize), tag(" ---\n"))(input)?;
 	trace!("parsing scanner {}", id);
-	let (input, vec) = many1(tuple((terminated(map(double, |d| d as isize), char(',')), terminated(map(double, |d| d as isize), char(',')), terminated(map(double, |d| d as isize), char('\n')))))(input)?;
+	let coordinate = || map(double, |d| d as isize);
+	let (input, vec) = many1(map(tuple((terminated(coordinate(), char(',')), terminated(coordinate(), char(',')), terminated(coordinate(), char('\n')))), |(x, y, z)| Vec3::new(x, y, z)))(input)?;
 	trace!("have scanner {}: {:?}", id, vec);
 
 	Ok((input, (id, vec)))
 }
 
-fn transform(num: usize, x: isize, y: isize, z: isize) -> (isize, isize, isize)
+/// The transform from the coordinates of scanner `b` into those of scanner `a`, if at least twelve
+/// beacons of both scanners overlap.
+fn align(a: &[Vec3<isize>], b: &[Vec3<isize>]) -> Option<Transform<isize>>
 {
-	[
-		( x,  y,  z),
-		( x, -z,  y),
-		( x, -y, -z),
-		( x,  z, -y),
-		(-y,  x,  z),
-		( y,  x, -z),
-		( z,  x,  y),
-		(-z,  x, -y),
-		(-x, -y,  z),
-		(-x,  z,  y),
-		(-x,  y, -z),
-		(-x, -z, -y),
-		( y, -x,  z),
-		(-y, -x, -z),
-		(-z, -x,  y),
-		( z, -x, -y),
-		(-z,  y,  x),
-		( z, -y,  x),
-		( y,  z,  x),
-		(-y, -z,  x),
-		( z,  y, -x),
-		(-z, -y, -x),
-		(-y,  z, -x),
-		( y, -z, -x),
-	][num]
+	Rotation::all()
+		.into_iter()
+		.find_map(|rotation|
+		{
+			// every pair of beacons votes for the translation mapping one onto the other
+			let mut votes = std::collections::HashMap::new();
+			a.iter()
+				.flat_map(|&pa| b.iter().map(move |&pb| pa - rotation.apply(pb)))
+				.find(|&translation|
+				{
+					let count = votes.entry(translation).or_insert(0);
+					*count += 1;
+					*count >= 12
+				})
+				.map(|translation| Transform::new(rotation, translation))
+		})
+}
+
+/// The transform of every scanner into the coordinates of the first one.
+fn locate(scanners: &[Vec<Vec3<isize>>]) -> Result<Vec<Transform<isize>>>
+{
+	let mut transforms = vec![None; scanners.len()];
+	transforms[0] = Some(Transform::identity());
+	let mut queue = vec![0];
+	while let Some(a) = queue.pop()
+	{
+		let to_first = transforms[a].unwrap();
+		for b in 0..scanners.len()
+		{
+			if transforms[b].is_some()
+			{
+				continue;
+			}
+			if let Some(transform) = align(&scanners[a], &scanners[b])
+			{
+				debug!("scanner {} overlaps scanner {} with {:?}", b, a, transform);
+				transforms[b] = Some(to_first.compose(&transform));
+				queue.push(b);
+			}
+		}
+	}
+
+	transforms.into_iter()
+		.enumerate()
+		.map(|(idx, transform)| transform.ok_or_else(|| anyhow!("scanner {} does not overlap any other scanner", idx)))
+		.collect()
 }
 
 impl super::super::Solution for Solution
@@ -243,126 +317,33 @@ impl super::super::Solution for Solution
 	{
 		debug!("called with input: {}", self.input);
 
-		let (_, scanners) = terminated(many1(terminated(scanner, alt((tag("\n"), eof)))), eof)
-			.parse(&self.input)
-			.map_err(|err| anyhow!("{}", err))?;
+		let scanners = self.parse()?;
+		let transforms = locate(&scanners)?;
 
-		let per_scanner_permutations = scanners.iter()
-			.inspect(|(id, beacons)| trace!("scanner {} has {} beacons", id, beacons.len()))
-			.map(|(_, beacons)|
-			{
-				beacons.iter()
-					.flat_map(|&(x, y, z)|
-					{
-						let vec = beacons.iter()
-							.map(|&(x_, y_, z_)|
-							{
-								(x_ - x, y_ - y, z_ - z)
-							})
-							.collect::<Vec<_>>();
-
-						(0..=23)
-							.map(move |n| ((transform(n, -x, -y, -z), n), vec.iter().map(|&(x, y, z)| transform(n, x, y, z)).collect::<Set<_>>()))
-					})
-					.collect::<Vec<_>>()
-			})
-			.collect::<Vec<Vec<(_, Set<(isize, isize, isize)>)>>>();
-
-		let beacons = (0..=(scanners.len()-1))
-			.map(|n|
+		if self.dump_scanners
+		{
+			for (id, transform) in transforms.iter().enumerate()
 			{
-				let mut found = std::iter::once(n).collect::<Set<_>>();
-				let mut beacons = per_scanner_permutations[n].iter().cloned().map(|(params, set)| (std::iter::once(params.0).collect::<Set<_>>(), set)).collect::<Vec<_>>();
-
-				while found.len() < scanners.len()
-				{
-					trace!("current beacons: {:?}", beacons);
-					debug!("found scanners: {:?}", found);
-					if let Some((scanner_id, (new, scanner, mut permutation))) = per_scanner_permutations.iter()
-						.enumerate()
-						.filter(|(i, _)| !found.contains(i))
-						.find_map(|(scanner_id, scanner)|
-						{
-							scanner.iter()
-								.enumerate()
-								.find_map(|(permutation_id, (params, permutation))|
-								{
-									beacons.iter()
-										.max_by_key(|(_scanners, perm)| permutation.intersection(perm).count())
-										.and_then(|(scanner, beacon_permutation)|
-										{
-											let intersections = permutation.intersection(beacon_permutation).count();
-											if intersections != 1
-											{
-												trace!("scanner {} permutation {} has {} intersections", scanner_id, permutation_id, intersections);
-											}
-											if intersections < 12
-											{
-												None
-											}
-											else
-											{
-												debug!("permutation {} params: {:?}", permutation_id, params);
-												let mut scanners = scanner.clone();
-												scanners.insert(params.0);
-												Some((permutation, scanners, beacon_permutation.clone()))
-											}
-										})
-								})
-								.map(|permutation| (scanner_id, permutation))
-						})
-					{
-						permutation.extend(new);
-						found.insert(scanner_id);
-						beacons = permutation
-							.iter()
-							.map(|&(x, y, z)|
-							{
-								let beacons = permutation.iter()
-									.map(|&(x_, y_, z_)|
-									{
-										(x_ - x, y_ - y, z_ - z)
-									})
-									.collect::<Set<_>>();
-								let scanners = scanner.iter()
-									.map(|&(x_, y_, z_)|
-									{
-										(x_ - x, y_ - y, z_ - z)
-									})
-									.collect::<Set<_>>();
-								(scanners, beacons)
-							})
-							.collect();
-					}
-					else
-					{
-						bail!(Error::AocNoSolution);
-					}
-				}
-
-				Ok(beacons)
-			})
-			.find_map(Result::ok)
-			.ok_or(Error::AocNoSolution)?;
+				let position = transform.translation;
+				println!("scanner {} at {},{},{} facing {}", id, position.x, position.y, position.z, transform.rotation);
+			}
+		}
 
 		if self.part == Part::Part1
 		{
+			let beacons = scanners.iter()
+				.zip(transforms.iter())
+				.flat_map(|(beacons, transform)| beacons.iter().map(move |&beacon| transform.apply(beacon)))
+				.collect::<Set<_>>();
 			Ok(format!("{}", beacons.len()))
 		}
 		else
 		{
-			// NOTE: part 2 is royally broken, just so you know
-			let scanners = &beacons[0].0;
-			trace!("scanners: {:?}", scanners);
-			let manhatten = scanners.iter()
-				.copied()
-				.flat_map(|b1| scanners.iter().copied().map(move |b2| (b1, b2)))
-				.map(|((x1, y1, z1), (x2, y2, z2))| (x1 + y1 + z1) - (x2 + y2 + z2))
+			let distance = transforms.iter()
+				.flat_map(|a| transforms.iter().map(move |b| a.translation.manhattan(b.translation)))
 				.max()
 				.ok_or(Error::AocNoSolution)?;
-
-			Ok(format!("{}", manhatten))
+			Ok(format!("{}", distance))
 		}
 	}
 }
-
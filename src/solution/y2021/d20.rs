@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::automaton::Automaton;
+
 use nom::
 {
 	character::complete::*,
@@ -97,62 +99,31 @@ impl super::super::Solution for Solution
 			.map_err(|err| anyhow!("{}", err))
 			.context(Error::AocParsing)?;
 
-		let invert = *mask.get(0).ok_or(Error::AocNoSolution).context("cannot retrieve invert bit")?;
-		let mut odd = true;
-
-		let mut min_x = input.iter().min_by_key(|(x, _y)| x).ok_or(Error::AocNoSolution).context("cannot retrieve any values")?.0;
-		let mut max_x = input.iter().max_by_key(|(x, _y)| x).ok_or(Error::AocNoSolution).context("cannot retrieve any values")?.0;
-		let mut min_y = input.iter().min_by_key(|(_x, y)| y).ok_or(Error::AocNoSolution).context("cannot retrieve any values")?.1;
-		let mut max_y = input.iter().max_by_key(|(_x, y)| y).ok_or(Error::AocNoSolution).context("cannot retrieve any values")?.1;
-
-		let mut iter = std::iter::successors(Some(input), |input|
+		// the surrounding pixels in reading order, the pixel itself goes in the middle
+		let neighbours = |&(x, y): &(isize, isize)|
 		{
-			min_x -= 1;
-			min_y -= 1;
-			max_x += 1;
-			max_y += 1;
-			odd = !odd;
-
-			Some((min_x..=max_x)
-				.flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
-				.filter(|&(x, y)|
-				{
-					let idx = [
-						(x+1, y+1),
-						(x  , y+1),
-						(x-1, y+1),
-						(x+1, y  ),
-						(x  , y  ),
-						(x-1, y  ),
-						(x+1, y-1),
-						(x  , y-1),
-						(x-1, y-1),
-					]
-						.iter()
-						.copied()
-						.enumerate()
-						.filter(|&(_, (x, y))|
-						{
-							if x <= min_x || x >= max_x || y <= min_y || y >= max_y
-							{
-								invert & odd
-							}
-							else
-							{
-								input.contains(&(x, y))
-							}
-						})
-						.fold(0, |acc, (idx, _)|
-						{
-							(1 << idx) | acc
-						});
-					mask[idx]
-				})
-				.collect())
-		});
+			[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)].iter()
+				.map(|(dx, dy)| (x + dx, y + dy))
+				.collect::<Vec<_>>()
+		};
+		let rule = |lit: bool, neighbours: &[bool]|
+		{
+			let idx = neighbours[..4].iter()
+				.chain(std::iter::once(&lit))
+				.chain(neighbours[4..].iter())
+				.fold(0, |acc, &lit| acc << 1 | lit as usize);
+			mask[idx]
+		};
+
+		let mut image = Automaton::new(input, 8, neighbours, rule);
+		for _ in 0..self.steps
+		{
+			image.step();
+			debug!("{} pixels differ from the background (lit: {})", image.cells().len(), image.background());
+		}
 
-		let set = iter.nth(self.steps).ok_or(Error::AocNoSolution).context("not enough elements")?;
-		Ok(format!("{}", set.len()))
+		let lit = image.population().ok_or(Error::AocNoSolution).context("infinitely many pixels are lit")?;
+		Ok(format!("{}", lit))
 	}
 }
 
@@ -130,7 +130,7 @@ impl super::super::Solution for Solution
 				let player1 = game.0;
 				let (score, pos) = if player1 { game.1 } else { game.2 };
 				for roll in (1..=3)
-					.flat_map(move |first| (1..=3).flat_map(move |second| (1..=3).map(move |third| (first + second + third))))
+					.flat_map(move |first| (1..=3).flat_map(move |second| (1..=3).map(move |third| first + second + third)))
 				{
 					let pos = (pos + roll - 1) % 10 + 1;
 					let score = score + pos;
@@ -2,6 +2,8 @@ use crate::error::*;
 
 use std::ops::Range;
 
+use super::super::common::interval::BoxSet;
+
 use nom::
 {
 	character::complete::*,
@@ -144,7 +146,7 @@ impl Solution
 	}
 }
 
-fn instruction(input: &str) -> IResult<&str, (bool, (Range<i128>, Range<i128>, Range<i128>))>
+fn instruction(input: &str) -> IResult<&str, (bool, [Range<i128>; 3])>
 {
 	let (input, state) = alt((tag("on"), tag("off")))(input)?;
 	let state = state == "on";
@@ -159,7 +161,7 @@ fn instruction(input: &str) -> IResult<&str, (bool, (Range<i128>, Range<i128>, R
 
 	let (input, (z_min, z_max)) = preceded(tag("z="), separated_pair(map(double, |d| d as i128), many1(char('.')), map(double, |d| d as i128)))(input)?;
 
-	Ok((input, (state, (x_min..(x_max + 1), y_min..(y_max + 1), z_min..(z_max + 1)))))
+	Ok((input, (state, [x_min..(x_max + 1), y_min..(y_max + 1), z_min..(z_max + 1)])))
 }
 
 impl super::super::Solution for Solution
@@ -168,112 +170,32 @@ impl super::super::Solution for Solution
 	{
 		debug!("called with input: {}", self.input);
 
-		let (_, mut instructions) = all_consuming(many1(terminated(instruction, newline)))
+		let (_, instructions) = all_consuming(many1(terminated(instruction, newline)))
 			.parse(&self.input)
 			.map_err(|err| anyhow!("{}", err))?;
 
-
-		if self.part == Part::Part1
-		{
-
-			instructions = instructions.into_iter()
-				.filter_map(|(b, (x, y, z))|
-				{
-					if x.start < -50 || x.end > 50 || y.start < -50 || y.end > 50 || z.start < -50 || z.end > 50
-					{
-						None
-					}
-					else
-					{
-						Some((b, (x.start.max(-50)..x.end.min(50), y.start.max(-50)..y.end.min(50), z.start.max(-50)..z.end.min(50))))
-					}
-				})
-				.collect();
-		}
-
 		debug!("instructions: {:#?}", instructions);
 
-		let mut x_blocks = instructions.iter()
-			.flat_map(|(_, (r, _, _))| [r.start, r.end])
-			.collect::<Vec<_>>();
-		x_blocks.sort_unstable();
-		x_blocks.dedup();
-
-		let mut y_blocks = instructions.iter()
-			.flat_map(|(_, (_, r, _))| [r.start, r.end])
-			.collect::<Vec<_>>();
-		y_blocks.sort_unstable();
-		y_blocks.dedup();
-
-		let mut z_blocks = instructions.iter()
-			.flat_map(|(_, (_, _, r))| [r.start, r.end])
-			.collect::<Vec<_>>();
-		z_blocks.sort_unstable();
-		z_blocks.dedup();
-
-		let mut states = vec![vec![vec![false; z_blocks.len()]; y_blocks.len()]; x_blocks.len()];
-
-		for (state, (x, y, z)) in instructions
+		let mut cubes = BoxSet::new();
+		for (state, cuboid) in instructions
 		{
-			let x_indices = x_blocks.iter()
-				.enumerate()
-				.skip_while(|&(_, b)| b < &x.start)
-				.take_while(|&(_, b)| b < &x.end)
-				.map(|(n, _)| n)
-				.collect::<Vec<_>>();
-
-			let y_indices = y_blocks.iter()
-				.enumerate()
-				.skip_while(|&(_, b)| b < &y.start)
-				.take_while(|&(_, b)| b < &y.end)
-				.map(|(n, _)| n)
-				.collect::<Vec<_>>();
-
-			let z_indices = z_blocks.iter()
-				.enumerate()
-				.skip_while(|&(_, b)| b < &z.start)
-				.take_while(|&(_, b)| b < &z.end)
-				.map(|(n, _)| n)
-				.collect::<Vec<_>>();
-
-			for x in &x_indices
+			if state
+			{
+				cubes.insert(cuboid);
+			}
+			else
 			{
-				for y in &y_indices
-				{
-					for z in &z_indices
-					{
-						states[*x][*y][*z] = state;
-					}
-				}
+				cubes.remove(&cuboid);
 			}
 		}
+		debug!("{} disjoint cuboids", cubes.boxes().len());
 
-		let mut count = 0;
-
-		states.iter()
-			.enumerate()
-			.for_each(|(xn, states)|
-			{
-				states.iter()
-					.enumerate()
-					.for_each(|(yn, states)|
-					{
-						states.iter()
-							.enumerate()
-							.for_each(|(zn, state)|
-							{
-								if *state
-								{
-									let x = x_blocks[xn + 1] - x_blocks[xn];
-									let y = y_blocks[yn + 1] - y_blocks[yn];
-									let z = z_blocks[zn + 1] - z_blocks[zn];
-									count += x * y * z;
-								}
-							})
-					})
-			});
+		if self.part == Part::Part1
+		{
+			cubes = cubes.intersection(&[-50..51, -50..51, -50..51]);
+		}
 
-		Ok(format!("{}", count))
+		Ok(format!("{}", cubes.volume()))
 	}
 }
 
@@ -0,0 +1,437 @@
+use crate::error::*;
+
+use std::rc::Rc;
+
+use super::super::common::search::astar;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2021::d23::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "#############\
+///     \n#...........#\
+///     \n###B#C#B#D###\
+///     \n  #A#D#C#A#\
+///     \n  #########\
+///     \n";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "12521");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "44169");
+/// ```
+pub struct Solution
+{
+	input: String,
+	unfold: bool,
+	moves: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { input, unfold: false, moves: false, }
+	}
+
+	/// Inserts the two folded rows of the diagram before organising the amphipods.
+	pub fn part2(input: String) -> Self
+	{
+		Self { input, unfold: true, moves: false, }
+	}
+
+	pub fn moves(self, moves: bool) -> Self
+	{
+		Self { moves, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("moves")
+				.long("moves")
+				.help("print the cheapest sequence of moves with the energy each one takes")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
+}
+
+/// Where an amphipod is, rooms counting their depth from the top.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Place
+{
+	Hallway(usize),
+	Room(usize, usize),
+}
+
+impl std::fmt::Display for Place
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Place::Hallway(position) => write!(f, "hallway {}", position),
+			Place::Room(room, depth) => write!(f, "room {} depth {}", room, depth),
+		}
+	}
+}
+
+/// A single amphipod going from a room into the hallway or from the hallway into its room.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Move
+{
+	pub amphipod: char,
+	pub from: Place,
+	pub to: Place,
+	pub energy: usize,
+}
+
+impl std::fmt::Display for Move
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		write!(f, "{} from {} to {}: {} energy", self.amphipod, self.from, self.to, self.energy)
+	}
+}
+
+/// The amphipods in the hallway and the rooms below it.
+///
+/// Parsed from the diagram, which may have any hallway length, number of rooms and room depth.
+/// Amphipods are named by letters, `A` belonging into the leftmost room, `B` into the next one
+/// and so on, each step costing ten times the energy of the previous letter.
+/// Beyond `T` a single step would cost more energy than fits into a `usize`, so there are at most
+/// 20 rooms.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2021::d23::Burrow;
+/// let burrow: Burrow = "#######\
+///     \n#.....#\
+///     \n###B#A#\
+///     \n  #A#B#\
+///     \n  #####".parse().unwrap();
+/// let moves = burrow.organise().unwrap();
+/// assert_eq!(moves.iter().map(|(step, _)| step.to_string()).collect::<Vec<_>>(), vec!
+/// [
+///     "A from room 1 depth 0 to hallway 1: 4 energy",
+///     "B from room 0 depth 0 to hallway 3: 20 energy",
+///     "A from hallway 1 to room 0 depth 0: 2 energy",
+///     "B from hallway 3 to room 1 depth 0: 20 energy",
+/// ]);
+/// assert!(moves.last().unwrap().1.is_organised());
+///
+/// let unfolded: Burrow = "#############\
+///     \n#...........#\
+///     \n###B#C#B#D###\
+///     \n  #A#D#C#A#\
+///     \n  #########".parse::<Burrow>().unwrap().unfold().unwrap();
+/// assert_eq!(unfolded.to_string(), "#############\
+///     \n#...........#\
+///     \n###B#C#B#D###\
+///     \n  #D#C#B#A#\
+///     \n  #D#B#A#C#\
+///     \n  #A#D#C#A#\
+///     \n  #########");
+///
+/// // every room needs as many amphipods as it is deep
+/// assert!("#####\n#...#\n###A#\n  #A#\n  #B#\n  ###".parse::<Burrow>().is_err());
+/// // there is no room for C
+/// assert!("#######\n#.....#\n###C#A#\n  ###".parse::<Burrow>().is_err());
+/// assert!("#######\n#.....#\n###B#x#\n  ###".parse::<Burrow>().is_err());
+/// assert!("#######\n#.....#\n###B#A#\nA#.#B#\n  ###".parse::<Burrow>().is_err());
+/// // amphipods cannot float above an empty spot
+/// assert!("#######\n#.B...#\n###A#B#\n  #.#A#\n  ###".parse::<Burrow>().is_err());
+/// // too many rooms
+/// let burrow = |rooms: u8|
+/// {
+///     let letters = (b'A'..b'A' + rooms).map(|letter| (letter as char).to_string()).collect::<Vec<_>>();
+///     let width = 2 * rooms as usize + 5;
+///     format!("{}\n#{}#\n###{}###\n  {}", "#".repeat(width), ".".repeat(width - 2), letters.join("#"), "#".repeat(width - 4))
+/// };
+/// assert!(burrow(20).parse::<Burrow>().unwrap().is_organised());
+/// assert!(burrow(21).parse::<Burrow>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Burrow
+{
+	/// The hallway position above every room.
+	columns: Rc<[usize]>,
+	hallway: Vec<Option<usize>>,
+	/// Every room from the top down, holding amphipods by the index of the room they belong into.
+	rooms: Vec<Vec<Option<usize>>>,
+}
+
+fn energy(amphipod: usize) -> usize
+{
+	10usize.pow(amphipod as u32)
+}
+
+fn name(amphipod: usize) -> char
+{
+	(b'A' + amphipod as u8) as char
+}
+
+impl std::str::FromStr for Burrow
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let lines = input.lines()
+			.map(str::trim_end)
+			.filter(|line| !line.is_empty())
+			.collect::<Vec<_>>();
+		if lines.len() < 4
+		{
+			Err(anyhow!("expected a wall, the hallway, rooms and another wall, got {} lines", lines.len()))?;
+		}
+		let cell = |ch: char| -> Result<Option<usize>>
+		{
+			match ch
+			{
+				'.' => Ok(None),
+				'A'..='Z' => Ok(Some((ch as u8 - b'A') as usize)),
+				ch => bail!("unexpected {:?} in the burrow", ch),
+			}
+		};
+		let walls = |line: &str| line.chars().all(|ch| ch == '#' || ch == ' ');
+
+		if !walls(lines[0])
+		{
+			Err(anyhow!("expected the wall above the hallway, got {:?}", lines[0]))?;
+		}
+		let hallway = lines[1].strip_prefix('#')
+			.and_then(|line| line.strip_suffix('#'))
+			.ok_or_else(|| anyhow!("hallway {:?} is not enclosed by walls", lines[1]))?
+			.chars()
+			.map(cell)
+			.collect::<Result<Vec<_>>>()?;
+
+		let cells = |line: &str| line.char_indices()
+			.filter(|&(_, ch)| ch != '#' && ch != ' ')
+			.map(|(idx, ch)| Ok((idx, cell(ch)?)))
+			.collect::<Result<Vec<_>>>();
+		let columns = cells(lines[2])?.into_iter()
+			.map(|(idx, _)| idx.checked_sub(1).filter(|&column| column < hallway.len()).ok_or_else(|| anyhow!("room at {} is not below the hallway", idx)))
+			.collect::<Result<Rc<[usize]>>>()?;
+		if columns.is_empty()
+		{
+			Err(anyhow!("there are no rooms below the hallway"))?;
+		}
+		if columns.len() > 20
+		{
+			Err(anyhow!("there are {} rooms, the energy of amphipods beyond the 20th one would overflow", columns.len()))?;
+		}
+
+		let mut rooms = vec![Vec::new(); columns.len()];
+		for line in lines[2..lines.len() - 1].iter()
+		{
+			let cells = cells(line)?;
+			if cells.iter().map(|&(idx, _)| idx.checked_sub(1)).ne(columns.iter().map(|&column| Some(column)))
+			{
+				Err(anyhow!("rooms in {:?} do not line up with the ones above", line))?;
+			}
+			for (room, (_, cell)) in rooms.iter_mut().zip(cells)
+			{
+				room.push(cell);
+			}
+		}
+		if !walls(lines[lines.len() - 1])
+		{
+			Err(anyhow!("expected the wall below the rooms, got {:?}", lines[lines.len() - 1]))?;
+		}
+
+		if let Some(room) = rooms.iter().position(|room| room.windows(2).any(|cells| cells[0].is_some() && cells[1].is_none()))
+		{
+			Err(anyhow!("room {} has an empty spot below an amphipod", room))?;
+		}
+
+		let mut counts = vec![0; rooms.len()];
+		for &amphipod in hallway.iter().chain(rooms.iter().flatten()).flatten()
+		{
+			*counts.get_mut(amphipod).ok_or_else(|| anyhow!("there is no room for {:?}", name(amphipod)))? += 1;
+		}
+		if let Some(amphipod) = counts.iter().position(|&count| count != rooms[0].len())
+		{
+			Err(anyhow!("there are {} amphipods {:?} for rooms of depth {}", counts[amphipod], name(amphipod), rooms[0].len()))?;
+		}
+
+		Ok(Self { columns, hallway, rooms, })
+	}
+}
+
+impl std::fmt::Display for Burrow
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		let cell = |cell: Option<usize>| cell.map_or('.', name);
+		let room = |column: usize| self.columns.iter().position(|&other| other + 1 == column);
+		let (first, last) = (self.columns[0], self.columns[self.columns.len() - 1]);
+
+		writeln!(f, "{}", "#".repeat(self.hallway.len() + 2))?;
+		writeln!(f, "#{}#", self.hallway.iter().map(|&amphipod| cell(amphipod)).collect::<String>())?;
+		for depth in 0..self.rooms[0].len()
+		{
+			let width = if depth == 0 { self.hallway.len() + 2 } else { last + 3 };
+			let line = (0..width)
+				.map(|column| match room(column)
+				{
+					Some(room) => cell(self.rooms[room][depth]),
+					None if depth > 0 && column < first => ' ',
+					None => '#',
+				})
+				.collect::<String>();
+			writeln!(f, "{}", line)?;
+		}
+		write!(f, "{}{}", " ".repeat(first), "#".repeat(last + 3 - first))
+	}
+}
+
+impl Burrow
+{
+	/// Inserts the rows `#D#C#B#A#` and `#D#B#A#C#` below the first one, only possible with four rooms.
+	pub fn unfold(&self) -> Result<Self>
+	{
+		anyhow::ensure!(self.rooms.len() == 4, "can only unfold four rooms, not {}", self.rooms.len());
+		let mut rooms = self.rooms.clone();
+		for (room, inserted) in rooms.iter_mut().zip([[3, 3], [2, 1], [1, 0], [0, 2]])
+		{
+			room.splice(1..1, inserted.iter().map(|&amphipod| Some(amphipod)));
+		}
+		Ok(Self { rooms, ..self.clone() })
+	}
+
+	pub fn is_organised(&self) -> bool
+	{
+		self.rooms.iter()
+			.enumerate()
+			.all(|(room, amphipods)| amphipods.iter().all(|&amphipod| amphipod == Some(room)))
+	}
+
+	/// Whether the room only holds amphipods belonging there, so others may enter and none must leave.
+	fn is_ready(&self, room: usize) -> bool
+	{
+		self.rooms[room].iter().flatten().all(|&amphipod| amphipod == room)
+	}
+
+	/// Whether the hallway is empty between both positions, apart from the starting one.
+	fn is_clear(&self, from: usize, to: usize) -> bool
+	{
+		let range = if from < to { from + 1..to + 1 } else { to..from };
+		self.hallway[range].iter().all(Option::is_none)
+	}
+
+	/// All possible moves with the burrow after them.
+	///
+	/// Moving an amphipod into its room never hurts, so when possible that is the only move.
+	/// Going from one room directly into another passes a free spot in the hallway anyway.
+	pub fn moves(&self) -> Vec<(Move, Self)>
+	{
+		for (position, amphipod) in self.hallway.iter().enumerate().filter_map(|(position, amphipod)| Some((position, (*amphipod)?)))
+		{
+			let column = self.columns[amphipod];
+			if !self.is_ready(amphipod) || !self.is_clear(position, column)
+			{
+				continue;
+			}
+			if let Some(depth) = self.rooms[amphipod].iter().rposition(Option::is_none)
+			{
+				let mut next = self.clone();
+				next.hallway[position] = None;
+				next.rooms[amphipod][depth] = Some(amphipod);
+				let energy = (position.abs_diff(column) + depth + 1) * energy(amphipod);
+				return vec![(Move { amphipod: name(amphipod), from: Place::Hallway(position), to: Place::Room(amphipod, depth), energy, }, next)];
+			}
+		}
+
+		let mut moves = Vec::new();
+		for (room, &column) in self.columns.iter().enumerate()
+		{
+			if self.is_ready(room)
+			{
+				continue;
+			}
+			let (depth, amphipod) = match self.rooms[room].iter().enumerate().find_map(|(depth, amphipod)| Some((depth, (*amphipod)?)))
+			{
+				Some(top) => top,
+				None => continue,
+			};
+			for position in (0..self.hallway.len()).filter(|position| !self.columns.contains(position))
+			{
+				if !self.is_clear(column, position)
+				{
+					continue;
+				}
+				let mut next = self.clone();
+				next.rooms[room][depth] = None;
+				next.hallway[position] = Some(amphipod);
+				let energy = (depth + 1 + column.abs_diff(position)) * energy(amphipod);
+				moves.push((Move { amphipod: name(amphipod), from: Place::Room(room, depth), to: Place::Hallway(position), energy, }, next));
+			}
+		}
+		moves
+	}
+
+	/// The energy every amphipod still needs at least, moving straight into the top of its room.
+	fn heuristic(&self) -> usize
+	{
+		let hallway = self.hallway.iter()
+			.enumerate()
+			.filter_map(|(position, amphipod)| Some((position, (*amphipod)?)))
+			.map(|(position, amphipod)| (position.abs_diff(self.columns[amphipod]) + 1) * energy(amphipod));
+		let rooms = self.rooms.iter()
+			.enumerate()
+			.flat_map(|(room, amphipods)| amphipods.iter()
+				.enumerate()
+				.filter_map(move |(depth, amphipod)| Some((room, depth, (*amphipod)?)))
+				.filter(move |&(_, depth, _)| amphipods[depth..].iter().any(|&amphipod| amphipod != Some(room))))
+			.map(|(room, depth, amphipod)|
+			{
+				let sideways = self.columns[room].abs_diff(self.columns[amphipod]).max(2);
+				(depth + 1 + sideways + 1) * energy(amphipod)
+			});
+		hallway.chain(rooms).sum()
+	}
+
+	/// The cheapest sequence of moves organising all amphipods, [`None`] when they cannot be.
+	pub fn organise(&self) -> Option<Vec<(Move, Self)>>
+	{
+		let path = astar([self.clone()], |burrow| burrow.moves().into_iter().map(|(step, next)| (next, step.energy)), Self::heuristic, Self::is_organised)?;
+		trace!("organised with {} energy", path.cost);
+		Some(path.states.windows(2)
+			.map(|burrows| burrows[0].moves().into_iter()
+				.find(|(_, next)| next == &burrows[1])
+				.expect("every step of the path is a move"))
+			.collect())
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut burrow = self.input.parse::<Burrow>()?;
+		if self.unfold
+		{
+			burrow = burrow.unfold()?;
+		}
+
+		let moves = burrow.organise().ok_or(Error::AocNoSolution)?;
+		let mut total = 0;
+		if self.moves
+		{
+			println!("{}", burrow);
+		}
+		for (step, burrow) in moves.iter()
+		{
+			total += step.energy;
+			if self.moves
+			{
+				println!("\n{} (total {})\n{}", step, total, burrow);
+			}
+		}
+
+		Ok(format!("{}", total))
+	}
+}
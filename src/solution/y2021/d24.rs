@@ -1,5 +1,3 @@
-#![allow(unused)]
-
 use crate::error::*;
 
 use nom::
@@ -24,12 +22,40 @@ enum Part
 
 /// # Examples
 ///
-/// Remember when unit tests were a thing?
-/// Yeah, fuck day 24.
+/// A MONAD program consists of one block per digit, each one either pushing the digit plus an
+/// offset onto `z` (as a base 26 number) or popping the top and comparing it to the digit.
+///
+/// ```
+/// # use adventofcode::solution::{ y2021::d24::Solution, Solution as S };
+/// # env_logger::init();
+/// let block = |div: isize, check: isize, offset: isize|
+/// {
+///     format!("inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+///         mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y\n",
+///         div, check, offset)
+/// };
+/// // push d1 + 4, push d2 + 11, pop requiring d3 = d2 + 11 - 14, pop requiring d4 = d1 + 4 - 6
+/// let input = [(1, 12, 4), (1, 11, 11), (26, -14, 2), (26, -6, 5)].iter()
+///     .map(|&(div, check, offset)| block(div, check, offset))
+///     .collect::<String>();
+/// assert_eq!(Solution::part1(input.clone()).solve().unwrap(), "9967");
+/// assert_eq!(Solution::part2(input.clone()).solve().unwrap(), "3411");
+///
+/// let constraints = Solution::part1(input.clone()).constraints().unwrap();
+/// let constraints = constraints.iter().map(ToString::to_string).collect::<Vec<_>>();
+/// assert_eq!(constraints, vec!["d3 = d2 - 3", "d4 = d1 - 2"]);
+///
+/// // a pop which can never match leaves something on the stack
+/// let input = [(1, 12, 4), (26, -20, 2)].iter()
+///     .map(|&(div, check, offset)| block(div, check, offset))
+///     .collect::<String>();
+/// assert!(Solution::part1(input).solve().is_err());
+/// ```
 pub struct Solution
 {
 	input: String,
 	part: Part,
+	explain: bool,
 }
 
 impl Solution
@@ -40,6 +66,7 @@ impl Solution
 		{
 			input,
 			part: Part::Part1,
+			explain: false,
 		}
 	}
 
@@ -49,8 +76,32 @@ impl Solution
 		{
 			input,
 			part: Part::Part2,
+			explain: false,
 		}
 	}
+
+	/// Prints the constraints between the digits derived from the program.
+	pub fn explain(self, explain: bool) -> Self
+	{
+		Self { explain, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("explain")
+				.long("explain")
+				.help("print the constraints between the digits derived from the program")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
+
+	/// The constraints every valid model number has to satisfy.
+	pub fn constraints(&self) -> Result<Vec<Constraint>>
+	{
+		Alu::parse(&self.input)?.constraints()
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -86,22 +137,6 @@ enum Instruction
 	Eql(usize, Data),
 }
 
-impl Instruction
-{
-	fn reg(&self) -> Vec<usize>
-	{
-		match *self
-		{
-			Instruction::Add(reg, data) => match data { Data::Register(reg2) => vec![reg, reg2], _ => vec![reg], },
-			Instruction::Mul(reg, data) => match data { Data::Register(reg2) => vec![reg, reg2], _ => vec![reg], },
-			Instruction::Div(reg, data) => match data { Data::Register(reg2) => vec![reg, reg2], _ => vec![reg], },
-			Instruction::Mod(reg, data) => match data { Data::Register(reg2) => vec![reg, reg2], _ => vec![reg], },
-			Instruction::Eql(reg, data) => match data { Data::Register(reg2) => vec![reg, reg2], _ => vec![reg], },
-			Instruction::Inp(reg) => vec![reg],
-		}
-	}
-}
-
 fn register(input: &str) -> IResult<&str, usize>
 {
 	let (input, ch) = one_of("wxyz")(input)?;
@@ -126,257 +161,367 @@ fn instruction(input: &str) -> IResult<&str, Instruction>
 	))(input)
 }
 
-fn run(mut regs: [isize; 4], instructions: &[Instruction], num: usize) -> Option<usize>
+/// The arithmetic logic unit of the submarine.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2021::d24::Alu;
+/// // stores the lowest four bits of the input in w, x, y and z, lowest first
+/// let alu = Alu::parse("inp z\nadd w z\nmod w 2\ndiv z 2\nadd x z\nmod x 2\ndiv z 2\nadd y z\nmod y 2\ndiv z 2\nmod z 2\n").unwrap();
+/// assert_eq!(alu.run(&[11]).unwrap(), [1, 1, 0, 1]);
+/// assert_eq!(alu.run(&[4]).unwrap(), [0, 0, 1, 0]);
+/// assert!(alu.run(&[]).is_err());
+/// assert!(Alu::parse("inp x\nmod x 0\n").unwrap().run(&[3]).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Alu
+{
+	instructions: Vec<Instruction>,
+}
+
+impl Alu
 {
-	if num < 999999
+	pub fn parse(input: &str) -> Result<Self>
 	{
-		trace!("{}", num);
+		let (_, instructions) = all_consuming(many1(terminated(instruction, newline)))
+			.parse(input)
+			.map_err(|err| anyhow!("{}", err))?;
+		Ok(Self { instructions, })
 	}
 
-	if let Some((instruction, rest)) = instructions.split_first()
+	/// The registers `w`, `x`, `y` and `z` after running the program on the inputs.
+	pub fn run(&self, inputs: &[isize]) -> Result<[isize; 4]>
 	{
-		match *instruction
+		let mut regs = [0; 4];
+		let mut inputs = inputs.iter();
+		for instruction in self.instructions.iter()
 		{
-			Instruction::Add(reg, data) => regs[reg] += data.resolve(&regs),
-			Instruction::Mul(reg, data) => regs[reg] *= data.resolve(&regs),
-			Instruction::Div(reg, data) => regs[reg] /= data.resolve(&regs),
-			Instruction::Mod(reg, data) => regs[reg] %= data.resolve(&regs),
-			Instruction::Eql(reg, data) => regs[reg] = (regs[reg] == data.resolve(&regs)) as isize,
-			Instruction::Inp(reg) =>
+			match *instruction
 			{
-				use rayon::prelude::*;
-				return (1..=9).rev()
-					.par_bridge()
-					.find_map_first(|i|
-					{
-						let mut regs = regs;
-						regs[reg] = i as isize;
-						run(regs, rest, num * 10 + i)
-					});
-			},
+				Instruction::Inp(reg) => regs[reg] = *inputs.next().ok_or_else(|| anyhow!("program reads more inputs than given"))?,
+				Instruction::Add(reg, data) => regs[reg] += data.resolve(&regs),
+				Instruction::Mul(reg, data) => regs[reg] *= data.resolve(&regs),
+				Instruction::Div(reg, data) =>
+				{
+					let divisor = data.resolve(&regs);
+					anyhow::ensure!(divisor != 0, "division by zero in {:?}", instruction);
+					regs[reg] /= divisor;
+				},
+				Instruction::Mod(reg, data) =>
+				{
+					let modulus = data.resolve(&regs);
+					anyhow::ensure!(regs[reg] >= 0 && modulus > 0, "modulo of {} by {} in {:?}", regs[reg], modulus, instruction);
+					regs[reg] %= modulus;
+				},
+				Instruction::Eql(reg, data) => regs[reg] = (regs[reg] == data.resolve(&regs)) as isize,
+			}
 		}
-
-		run(regs, rest, num)
+		Ok(regs)
 	}
-	else
+
+	/// Runs the program symbolically on unknown digits, collecting the constraints needed for `z` to
+	/// end up zero.
+	///
+	/// `z` is tracked as a stack of base 26 digits, each of them an input digit plus an offset.
+	/// Whenever an input is compared to a value it might equal (judging by the ranges of both), the
+	/// comparison is assumed to succeed, since every failed comparison pushes onto the stack where
+	/// a successful one pops.
+	pub fn constraints(&self) -> Result<Vec<Constraint>>
 	{
-		if regs[3] == 0
+		let mut regs = [Sym::Const(0), Sym::Const(0), Sym::Const(0), Sym::Const(0)];
+		let mut constraints = Vec::new();
+		let mut digits = 0;
+		for instruction in self.instructions.iter()
 		{
-			debug!("found: {}", num);
-			Some(num)
+			let (reg, value) = match *instruction
+			{
+				Instruction::Inp(reg) =>
+				{
+					digits += 1;
+					(reg, Some(Sym::Digit(digits - 1, 0)))
+				},
+				Instruction::Add(reg, data) => (reg, regs[reg].add(&Sym::resolve(data, &regs))),
+				Instruction::Mul(reg, data) => (reg, regs[reg].mul(&Sym::resolve(data, &regs))),
+				Instruction::Div(reg, data) => (reg, regs[reg].div(&Sym::resolve(data, &regs))),
+				Instruction::Mod(reg, data) => (reg, regs[reg].rem(&Sym::resolve(data, &regs))),
+				Instruction::Eql(reg, data) => (reg, regs[reg].eql(&Sym::resolve(data, &regs), &mut constraints)),
+			};
+			regs[reg] = value.ok_or_else(|| anyhow!("cannot analyse {:?} on {:?}", instruction, regs))?;
 		}
-		else
+
+		if regs[3] != Sym::Const(0)
 		{
-			None
+			bail!("z ends up as {:?} instead of zero", regs[3]);
 		}
+		Ok(constraints)
+	}
+
+	fn inputs(&self) -> usize
+	{
+		self.instructions.iter().filter(|i| matches!(i, Instruction::Inp(_))).count()
 	}
 }
 
-struct ModelNumbers
+/// A relation between digits of the model number, which are numbered from one starting at the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constraint
 {
-	nums: [usize; 14],
+	/// The index of the constrained digit.
+	pub digit: usize,
+	/// The index of the digit it depends on, if any.
+	pub other: Option<usize>,
+	pub offset: isize,
 }
 
-impl ModelNumbers
+impl std::fmt::Display for Constraint
 {
-	fn new() -> Self
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
 	{
-		Self
+		match (self.other, self.offset)
 		{
-			nums: [9; 14],
+			(None, offset) => write!(f, "d{} = {}", self.digit + 1, offset),
+			(Some(other), 0) => write!(f, "d{} = d{}", self.digit + 1, other + 1),
+			(Some(other), offset) if offset < 0 => write!(f, "d{} = d{} - {}", self.digit + 1, other + 1, -offset),
+			(Some(other), offset) => write!(f, "d{} = d{} + {}", self.digit + 1, other + 1, offset),
 		}
 	}
 }
 
-impl Iterator for ModelNumbers
+/// A symbolic register value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Sym
 {
-	type Item = (usize, [usize; 14]);
+	Const(isize),
+	/// An input digit plus an offset.
+	Digit(usize, isize),
+	/// A base 26 number, the last entry being the least significant one.
+	Stack(Vec<Sym>),
+}
 
-	fn next(&mut self) -> Option<Self::Item>
+impl Sym
+{
+	fn resolve(data: Data, regs: &[Sym]) -> Sym
 	{
-		if self.nums.iter_mut().rev().fold(true, |overflow, num|
+		match data
+		{
+			Data::Register(r) => regs[r].clone(),
+			Data::Value(v) => Sym::Const(v),
+		}
+	}
+
+	fn range(&self) -> Option<(isize, isize)>
+	{
+		match *self
+		{
+			Sym::Const(c) => Some((c, c)),
+			Sym::Digit(_, offset) => Some((1 + offset, 9 + offset)),
+			Sym::Stack(_) => None,
+		}
+	}
+
+	/// The value as a base 26 number, if all of its digits are known to fit.
+	fn stack(&self) -> Option<Vec<Sym>>
+	{
+		match self
+		{
+			Sym::Const(0) => Some(Vec::new()),
+			Sym::Stack(stack) => Some(stack.clone()),
+			_ => self.range()
+				.filter(|&(min, max)| min >= 0 && max < 26)
+				.map(|_| vec![self.clone()]),
+		}
+	}
+
+	fn from_stack(stack: Vec<Sym>) -> Sym
+	{
+		if stack.is_empty() { Sym::Const(0) } else { Sym::Stack(stack) }
+	}
+
+	fn add(&self, other: &Sym) -> Option<Sym>
+	{
+		match (self, other)
+		{
+			(&Sym::Const(a), &Sym::Const(b)) => Some(Sym::Const(a + b)),
+			(value, Sym::Const(0)) | (Sym::Const(0), value) => Some(value.clone()),
+			(&Sym::Digit(digit, offset), &Sym::Const(c)) | (&Sym::Const(c), &Sym::Digit(digit, offset)) => Some(Sym::Digit(digit, offset + c)),
+			(Sym::Stack(_), value) =>
 			{
-				if overflow
+				// only adding to a freshly shifted stack keeps every digit below 26
+				let mut stack = self.stack()?;
+				let (min, max) = value.range()?;
+				if stack.last() != Some(&Sym::Const(0)) || min < 0 || max >= 26
 				{
-					*num -= 1;
-					if *num == 0
-					{
-						*num = 9;
-						return true;
-					}
+					return None;
 				}
-				false
-			})
-		{
-			return None;
+				*stack.last_mut().unwrap() = value.clone();
+				Some(Sym::Stack(stack))
+			},
+			_ => None,
 		}
-		Some((self.nums.iter().copied().reduce(|a, b| a*10 + b).unwrap(), self.nums))
 	}
-}
 
+	fn mul(&self, other: &Sym) -> Option<Sym>
+	{
+		match (self, other)
+		{
+			(&Sym::Const(a), &Sym::Const(b)) => Some(Sym::Const(a * b)),
+			(_, Sym::Const(0)) | (Sym::Const(0), _) => Some(Sym::Const(0)),
+			(value, Sym::Const(1)) | (Sym::Const(1), value) => Some(value.clone()),
+			(value, Sym::Const(26)) =>
+			{
+				let mut stack = value.stack()?;
+				stack.push(Sym::Const(0));
+				Some(Sym::Stack(stack))
+			},
+			_ => None,
+		}
+	}
 
-impl super::super::Solution for Solution
-{
-	fn solve(&self) -> Result<String>
+	fn div(&self, other: &Sym) -> Option<Sym>
 	{
-		debug!("called with input: {}", self.input);
+		match (self, other)
+		{
+			(_, Sym::Const(0)) => None,
+			(&Sym::Const(a), &Sym::Const(b)) => Some(Sym::Const(a / b)),
+			(value, Sym::Const(1)) => Some(value.clone()),
+			(value, Sym::Const(26)) =>
+			{
+				let mut stack = value.stack()?;
+				stack.pop();
+				Some(Sym::from_stack(stack))
+			},
+			_ => None,
+		}
+	}
 
-		let (_, mut instructions) = all_consuming(many1(terminated(instruction, newline)))
-			.parse(&self.input)
-			.map_err(|err| anyhow!("{}", err))?;
+	fn rem(&self, other: &Sym) -> Option<Sym>
+	{
+		match (self, other)
+		{
+			(&Sym::Const(a), &Sym::Const(b)) if a >= 0 && b > 0 => Some(Sym::Const(a % b)),
+			(value, Sym::Const(26)) => Some(value.stack()?.pop().unwrap_or(Sym::Const(0))),
+			_ => None,
+		}
+	}
 
-		let inp = instructions.iter().filter(|i| match i { Instruction::Inp(_) => true, _ => false, }).count();
-		let mut off = 0;
-		for _ in 0..inp
+	fn eql(&self, other: &Sym, constraints: &mut Vec<Constraint>) -> Option<Sym>
+	{
+		let ((min_a, max_a), (min_b, max_b)) = (self.range()?, other.range()?);
+		if max_a < min_b || max_b < min_a
 		{
-			let mut pos = instructions.iter().skip(off).position(|i| match i { Instruction::Inp(_) => true, _ => false, }).unwrap();
-			let reg = instructions[off + pos].reg()[0];
-			while instructions[off + pos + 1].reg().iter().all(|&r| r != reg)
+			return Some(Sym::Const(0));
+		}
+		match (self, other)
+		{
+			(&Sym::Const(a), &Sym::Const(b)) => Some(Sym::Const((a == b) as isize)),
+			(&Sym::Digit(a, offset_a), &Sym::Digit(b, offset_b)) if a == b => Some(Sym::Const((offset_a == offset_b) as isize)),
+			(&Sym::Digit(a, offset_a), &Sym::Digit(b, offset_b)) =>
 			{
-				instructions.swap(off + pos, off + pos + 1);
-				pos += 1;
-			}
-			off += pos;
+				let (digit, other, offset) = if a > b { (a, b, offset_b - offset_a) } else { (b, a, offset_a - offset_b) };
+				constraints.push(Constraint { digit, other: Some(other), offset, });
+				Some(Sym::Const(1))
+			},
+			(&Sym::Digit(digit, offset), &Sym::Const(c)) | (&Sym::Const(c), &Sym::Digit(digit, offset)) =>
+			{
+				constraints.push(Constraint { digit, other: None, offset: c - offset, });
+				Some(Sym::Const(1))
+			},
+			_ => None,
 		}
+	}
+}
 
-		if self.part == Part::Part1
+/// The largest or smallest digits satisfying all constraints.
+///
+/// Digits linked by constraints form groups whose digits are all fixed offsets from the first one,
+/// so each group only has a single free digit.
+fn solve(digits: usize, constraints: &[Constraint], largest: bool) -> Option<Vec<isize>>
+{
+	let mut relative: Vec<Option<(usize, isize)>> = vec![None; digits];
+	for root in 0..digits
+	{
+		if relative[root].is_some()
 		{
-			use rayon::prelude::*;
-			let first = ModelNumbers::new()
-				.par_bridge()
-				.find_map_first(|(num, digits)|
+			continue;
+		}
+		relative[root] = Some((root, 0));
+		let mut queue = vec![root];
+		while let Some(digit) = queue.pop()
+		{
+			let (_, offset) = relative[digit].unwrap();
+			let linked = constraints.iter()
+				.filter_map(|c| match c.other
 				{
-					if digits[6..].iter().all(|&i| i == 9)
+					Some(other) if c.digit == digit => Some((other, offset - c.offset)),
+					Some(other) if other == digit => Some((c.digit, offset + c.offset)),
+					_ => None,
+				});
+			for (next, next_offset) in linked.collect::<Vec<_>>()
+			{
+				match relative[next]
+				{
+					Some((_, known)) if known != next_offset => return None,
+					Some(_) => {},
+					None =>
 					{
-						trace!("{}", num);
-					}
-					let mut iter = digits.iter();
+						relative[next] = Some((root, next_offset));
+						queue.push(next);
+					},
+				}
+			}
+		}
+	}
 
-					let mut w: isize = *iter.next().unwrap() as isize;
-					let mut z: isize = 0;
+	let mut bounds = vec![(1, 9); digits];
+	for relation in relative.iter()
+	{
+		let (root, offset) = relation.unwrap();
+		let (min, max) = bounds[root];
+		bounds[root] = (min.max(1 - offset), max.min(9 - offset));
+	}
+	for constraint in constraints.iter().filter(|c| c.other.is_none())
+	{
+		let (root, offset) = relative[constraint.digit].unwrap();
+		let (min, max) = bounds[root];
+		let value = constraint.offset - offset;
+		bounds[root] = (min.max(value), max.min(value));
+	}
 
-					if w != 11
-					{
-						z = w + 8;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 12
-					{
-						z *= 26 + (w + 8);
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 10
-					{
-						z *= 26;
-						z += w + 12;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 - 8
-					{
-						z += w + 10;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 15
-					{
-						z *= 26;
-						z += w + 2;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 15
-					{
-						z *= 26;
-						z += w + 8;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 11
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 4
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 10
-					{
-						z *= 26;
-						z += w + 9;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 3
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 10;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w != z % 26 + 15
-					{
-						z *= 26;
-						z += w + 3;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 3
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 7;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 1
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 7;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 10
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 2;
-					}
-					w = *iter.next().unwrap() as isize;
-					if w == z % 26 - 16
-					{
-						z /= 26;
-					}
-					else
-					{
-						z -= z % 26;
-						z += w + 1;
-					}
+	relative.iter()
+		.map(|relation|
+		{
+			let (root, offset) = relation.unwrap();
+			let (min, max) = bounds[root];
+			(min <= max).then(|| offset + if largest { max } else { min })
+		})
+		.collect()
+}
 
-					if z == 0
-					{
-						Some(num)
-					}
-					else
-					{
-						None
-					}
-				})
-				.ok_or(Error::AocNoSolution)?;
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
 
-			Ok(format!("{}", first))
-		}
-		else
+		let alu = Alu::parse(&self.input)?;
+		let constraints = alu.constraints()?;
+		debug!("constraints: {:?}", constraints);
+
+		if self.explain
 		{
-			unimplemented!()
+			for constraint in constraints.iter()
+			{
+				println!("{}", constraint);
+			}
 		}
+
+		let digits = solve(alu.inputs(), &constraints, self.part == Part::Part1).ok_or(Error::AocNoSolution)?;
+		let model = digits.iter().map(ToString::to_string).collect::<String>();
+
+		let [_, _, _, z] = alu.run(&digits)?;
+		anyhow::ensure!(z == 0, "the program rejects model number {} with z = {}", model, z);
+
+		Ok(model)
 	}
 }
-
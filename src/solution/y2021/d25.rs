@@ -1,5 +1,11 @@
 use crate::error::*;
 
+use super::super::common::
+{
+	cycle::fixpoint,
+	grid::{ Direction, Grid },
+};
+
 use nom::
 {
 	character::complete::*,
@@ -60,13 +66,6 @@ impl Solution
 	}
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction
-{
-	East,
-	South,
-}
-
 fn direction(input: &str) -> IResult<&str, Option<Direction>>
 {
 	map(one_of(".>v"), |ch|
@@ -74,8 +73,8 @@ fn direction(input: &str) -> IResult<&str, Option<Direction>>
 		match ch
 		{
 			'.' => None,
-			'v' => Some(Direction::South),
-			'>' => Some(Direction::East),
+			'v' => Some(Direction::Down),
+			'>' => Some(Direction::Right),
 			_ => unreachable!(),
 		}
 	})(input)
@@ -95,90 +94,35 @@ impl super::super::Solution for Solution
 		let (_, field) = all_consuming(input)
 			.parse(&self.input)
 			.map_err(|err| anyhow!("{}", err))?;
+		let field = Grid::from_rows(field)?;
 
 		if self.part == Part::Part1
 		{
-			let count = std::iter::successors(Some(field), |oldfield|
+			let (steps, field) = fixpoint(field, |oldfield|
 			{
 				let mut field = oldfield.clone();
-				let mut changed = false;
-				for pass in [Direction::East, Direction::South]
+				for pass in [Direction::Right, Direction::Down]
 				{
 					let oldfield = field.clone();
-					for y in 0..field.len()
+					for pos in oldfield.positions()
 					{
-						for x in 0..field[y].len()
+						if oldfield[pos] == Some(pass)
 						{
-							let ylen = field.len();
-							let xlen = field[y].len();
-
-							match oldfield[y][x]
+							if let Some(npos) = oldfield.step_wrapping(pos, pass).filter(|&npos| oldfield[npos].is_none())
 							{
-								Some(Direction::East) if pass == Direction::East =>
-								{
-									if oldfield[y][(x + 1) % xlen].is_none()
-									{
-										trace!("moving {}/{} to {}/{}", x, y, (x + 1) % xlen, y);
-										let value = field[y][x].take();
-										field[y][(x + 1) % xlen] = value;
-										changed = true;
-									}
-									else
-									{
-										field[y][x] = oldfield[y][x];
-									}
-								},
-								Some(Direction::South) if pass == Direction::South =>
-								{
-									if oldfield[(y + 1) % ylen][x].is_none()
-									{
-										trace!("moving {}/{} to {}/{}", x, y, x, (y + 1) % ylen);
-										let value = field[y][x].take();
-										field[(y + 1) % ylen][x] = value;
-										changed = true;
-									}
-									else
-									{
-										field[y][x] = oldfield[y][x];
-									}
-								},
-								_ => {},
+								trace!("moving {:?} to {:?}", pos, npos);
+								let value = field[pos].take();
+								field[npos] = value;
 							}
 						}
 					}
 				}
-				if changed
-				{
-					Some(field)
-				}
-				else
-				{
-					None
-				}
-			})
-				.inspect(|field|
-				{
-					let s = field.iter()
-						.flat_map(|row|
-						{
-							std::iter::once('\n')
-								.chain(
-									row.iter()
-										.map(|o|
-										{
-											match o
-											{
-												None => '.',
-												Some(Direction::East) => '>',
-												Some(Direction::South) => 'v',
-											}
-										})
-								)
-						})
-						.collect::<String>();
-					debug!("{}", s);
-				})
-				.count();
+				field
+			});
+			debug!("\n{}", field.map(|o| o.map(|dir| dir.symbol()).unwrap_or('.')));
+
+			// the step on which nothing moves counts as well
+			let count = steps + 1;
 
 			Ok(format!("{}", count))
 		}
@@ -32,8 +32,7 @@ pub mod d19;
 pub mod d20;
 pub mod d21;
 pub mod d22;
-pub mod d23pt1;
-pub mod d23pt2;
+pub mod d23;
 pub mod d24;
 pub mod d25;
 
@@ -43,6 +42,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 	enum InputType<'a>
 	{
 		UrlInput(Box<dyn Fn(Option<String>) -> Box<dyn super::Solution + 'static>>),
+		UrlInputArgs(Vec<clap::Arg>, Box<dyn Fn(Option<String>, &clap::ArgMatches) -> Box<dyn super::Solution + 'static>>),
 		Parser(Box<dyn super::InputParser<'a>>),
 	}
 
@@ -78,24 +78,24 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d14::Solution::with_steps(40, input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part2(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d16::Solution::part1(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d16::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(d16::Solution::args(), Box::new(|input, matches| Box::new(d16::Solution::part1(input.expect("empty input received")).ast(matches.get_flag("ast")))))),
+		Some(InputType::UrlInputArgs(d16::Solution::args(), Box::new(|input, matches| Box::new(d16::Solution::part2(input.expect("empty input received")).ast(matches.get_flag("ast")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d17pt1::Solution::new(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d17pt2::Solution::new(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(d18::Solution::args(), Box::new(|input, matches| Box::new(d18::Solution::part1(input.expect("empty input received")).trace(matches.get_flag("trace")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part2(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part1(input.expect("empty input received")))))),
-		None,
+		Some(InputType::UrlInputArgs(d19::Solution::args(), Box::new(|input, matches| Box::new(d19::Solution::part1(input.expect("empty input received")).dump_scanners(matches.get_flag("dump-scanners")))))),
+		Some(InputType::UrlInputArgs(d19::Solution::args(), Box::new(|input, matches| Box::new(d19::Solution::part2(input.expect("empty input received")).dump_scanners(matches.get_flag("dump-scanners")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::part2(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::part2(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part2(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d23pt1::Solution::new(input.expect("empty input received")))))),
-		None,
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d24::Solution::part1(input.expect("empty input received")))))),
-		None,
+		Some(InputType::UrlInputArgs(d23::Solution::args(), Box::new(|input, matches| Box::new(d23::Solution::part1(input.expect("empty input received")).moves(matches.get_flag("moves")))))),
+		Some(InputType::UrlInputArgs(d23::Solution::args(), Box::new(|input, matches| Box::new(d23::Solution::part2(input.expect("empty input received")).moves(matches.get_flag("moves")))))),
+		Some(InputType::UrlInputArgs(d24::Solution::args(), Box::new(|input, matches| Box::new(d24::Solution::part1(input.expect("empty input received")).explain(matches.get_flag("explain")))))),
+		Some(InputType::UrlInputArgs(d24::Solution::args(), Box::new(|input, matches| Box::new(d24::Solution::part2(input.expect("empty input received")).explain(matches.get_flag("explain")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d25::Solution::part1(input.expect("empty input received")))))),
 	];
 
@@ -106,6 +106,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 			match parser
 			{
 				InputType::UrlInput(parser) => Box::new((2021usize,idx/2+1,idx%2+1,parser)) as Box<dyn super::InputParser>,
+				InputType::UrlInputArgs(args, parser) => Box::new((2021usize,idx/2+1,idx%2+1,args,parser)) as Box<dyn super::InputParser>,
 				InputType::Parser(parser) => parser,
 			}
 		)
@@ -2,6 +2,8 @@ use crate::error::*;
 
 use std::convert::TryFrom;
 
+use super::super::common::polygon::Polygon;
+
 /// # Examples
 ///
 /// ```
@@ -113,13 +115,6 @@ fn pos_plus_dir(pos: (usize, usize), dir: (isize, isize)) -> Option<(usize, usiz
 		.and_then(|x| pos.1.checked_add_signed(dir.1).map(|y| (x, y)))
 }
 
-fn pos_minus_pos(dest: (usize, usize), source: (usize, usize)) -> (isize, isize)
-{
-	let x = dest.0.checked_sub(source.0).map(|i| i as isize).unwrap_or(-1);
-	let y = dest.1.checked_sub(source.1).map(|i| i as isize).unwrap_or(-1);
-	(x, y)
-}
-
 impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
@@ -215,103 +210,11 @@ impl super::super::Solution for Solution
 
 		trace!("path found:\n{:#?}", path);
 
-		let pos_path = path.iter().map(|(pos, _)| pos).copied().collect::<Vec<_>>();
-
-		let start = pos_path.last().copied().unwrap();
-		let first = pos_path.first().copied().unwrap();
-		let dir = pos_minus_pos(first, start);
-
-		let dir = pos_path.iter()
-			.skip(1)
-			.copied()
-			.fold((dir, first, 0isize), |(dir, last, count), next|
-			{
-				let new_dir = pos_minus_pos(next, last);
-				// positive is left, negative is right
-				let lr = match (new_dir, dir)
-				{
-					((1, 0), (0, 1)) => 1,
-					((1, 0), (0, -1)) => -1,
-					((0, 1), (1, 0)) => -1,
-					((0, 1), (-1, 0)) => 1,
-					((-1, 0), (0, 1)) => -1,
-					((-1, 0), (0, -1)) => 1,
-					((0, -1), (1, 0)) => 1,
-					((0, -1), (-1, 0)) => -1,
-					_ => 0,
-				};
-				if lr != 0
-				{
-					trace!("path is going {}", if lr > 0 { "left" } else { "right" });
-				}
-				(new_dir, next, count + lr)
-			}).2.signum();
-
-		debug!("path is {} bound", if dir > 0 { "left" } else { "right" });
-
-		let enclosed = pos_path.iter()
-			.skip(1)
-			.chain(std::iter::once(&first))
-			.copied()
-			.fold((first, Vec::new()), |(last, mut tiles), next|
-			{
-				let new_dir = pos_minus_pos(next, last);
-				// positive is left, negative is right
-				trace!("evaluating new_dir={:?}, dir={}", new_dir, dir);
-				let offset = match (new_dir, dir)
-				{
-					((1, 0), 1) => (0, -1),
-					((-1, 0), 1) => (0, 1),
-					((0, 1), 1) => (1, 0),
-					((0, -1), 1) => (-1, 0),
-					((1, 0), -1) => (0, 1),
-					((-1, 0), -1) => (0, -1),
-					((0, 1), -1) => (-1, 0),
-					((0, -1), -1) => (1, 0),
-					_ => unreachable!(),
-				};
-				if let Some(pos) = pos_plus_dir(last, offset)
-				{
-					trace!("adding tile {:?}", pos);
-					tiles.push(pos);
-				}
-				if let Some(pos) = pos_plus_dir(next, offset)
-				{
-					trace!("adding tile {:?}", pos);
-					tiles.push(pos);
-				}
-				(next, tiles)
-
-			}).1;
-
-		let mut enclosed = enclosed.into_iter()
-			.filter(|pos| !pos_path.contains(pos))
-			.collect::<std::collections::BTreeSet<_>>();
-
-		debug!("found {} adjacent enclosed tiles", enclosed.len());
-
-		loop
-		{
-			let len = enclosed.len();
-			let new = enclosed.iter()
-				.flat_map(|pos|
-					directions.iter().filter_map(move |dir| pos_plus_dir(*pos, *dir))
-				)
-				.filter(|pos| !pos_path.contains(pos))
-				.collect::<Vec<_>>();
-			enclosed.extend(new);
-			if len == enclosed.len()
-			{
-				break;
-			}
-		}
-
-		trace!("enclosed tiles:\n{:#?}", enclosed);
-		debug!("found {} total enclosed tiles", enclosed.len());
-
-		let result: usize = enclosed.len();
+		// the enclosed tiles are the lattice points strictly inside the loop
+		let pipe = Polygon::new(path.iter().map(|&((x, y), _)| (x as i64, y as i64)).collect());
+		debug!("loop of length {} encloses an area of {}/2", pipe.boundary_points(), pipe.double_area());
 
-		Ok(format!("{}", result))
+		Ok(format!("{}", pipe.interior_points()))
 	}
 }
 
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::cycle::nth;
+
 /// # Examples
 ///
 /// ```
@@ -162,76 +164,40 @@ impl super::super::Solution for Solution
 			.map(|(idx, _)| idx)
 			.collect::<std::collections::BTreeSet<usize>>();
 
-		let mut memo: std::collections::HashMap<std::collections::BTreeSet<usize>, std::collections::BTreeSet<usize>> = Default::default();
-
-		let target = 1000000000;
-
-		let (idx, last) = std::iter::repeat(Dir::sequence())
-			.take(target)
-			.scan((rocks, Vec::new()), |(ref mut old, ref mut cache), dirs|
-			{
-				if let Some(new) = memo.get(&old.clone())
-				{
-					debug!("cached!");
-					if cache.contains(old)
-					{
-						debug!("loop detected");
-						return None;
-					}
-					cache.push(old.clone());
-					return Some(new.clone());
-				}
-				let new: std::collections::BTreeSet<usize> = dirs.iter()
-					.fold(old.clone(), |old, dir|
-					{
-						statics.iter()
-							.flat_map(|&stat|
-							{
-								let found = dir.uphill(len, stat)
-									.take_while(|pos| !statics.contains(pos))
-									.filter(|pos| old.contains(pos))
-									.count();
-								dir.uphill(len, stat).take(found)
-							})
-							.chain(
-							{
-								dir.border(len)
-									.flat_map(|border|
-									{
-										let found = std::iter::once(border).chain(dir.uphill(len, border))
-											.take_while(|pos| !statics.contains(pos))
-											.filter(|pos| old.contains(pos))
-											.count();
-										std::iter::once(border).chain(dir.uphill(len, border)).take(found)
-									})
-							})
-							.collect()
-					});
-				trace!("state:\n{}", state_to_str(len, statics, &new));
-				memo.insert(old.clone(), new.clone());
-				*cache = Vec::new();
-				*old = new.clone();
-				Some(new)
-			})
-			.enumerate()
-			.last()
-			.unwrap();
-
-		let rocks = if idx == target - 1
+		let spin = |old: &std::collections::BTreeSet<usize>|
 		{
-			last
-		}
-		else
-		{
-			let looping = std::iter::successors(Some(&last), |prev|
+			let new = Dir::sequence().iter()
+				.fold(old.clone(), |old, dir|
 				{
-					memo.get(prev)
-						.filter(|next| next != &&last)
-				})
-				.collect::<Vec<_>>();
-			(*looping.get((target - (idx + 1)) % looping.len()).unwrap()).clone()
+					statics.iter()
+						.flat_map(|&stat|
+						{
+							let found = dir.uphill(len, stat)
+								.take_while(|pos| !statics.contains(pos))
+								.filter(|pos| old.contains(pos))
+								.count();
+							dir.uphill(len, stat).take(found)
+						})
+						.chain(
+						{
+							dir.border(len)
+								.flat_map(|border|
+								{
+									let found = std::iter::once(border).chain(dir.uphill(len, border))
+										.take_while(|pos| !statics.contains(pos))
+										.filter(|pos| old.contains(pos))
+										.count();
+									std::iter::once(border).chain(dir.uphill(len, border)).take(found)
+								})
+						})
+						.collect()
+				});
+			trace!("state:\n{}", state_to_str(len, statics, &new));
+			new
 		};
 
+		let rocks = nth(rocks, spin, 1000000000);
+
 		debug!("end state:\n{}", state_to_str(len, statics, &rocks));
 
 		let result: usize = rocks.into_iter()
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::grid::{ Direction, Grid, Position };
+
 use std::convert::TryFrom;
 
 /// # Examples
@@ -67,56 +69,6 @@ enum Part
 	Two,
 }
 
-type Position = (usize, usize);
-
-#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
-enum Direction
-{
-	Up,
-	Down,
-	Left,
-	Right,
-}
-
-impl Direction
-{
-	fn vector(&self) -> (isize, isize)
-	{
-		match &self
-		{
-			Direction::Up => (0, -1),
-			Direction::Down => (0, 1),
-			Direction::Left => (-1, 0),
-			Direction::Right => (1, 0),
-		}
-	}
-}
-
-impl std::ops::Add<Direction> for Position
-{
-	type Output = Option<Position>;
-
-	fn add(self, dir: Direction) -> Option<Position>
-	{
-		let (dx, dy) = dir.vector();
-		let x = match dx
-		{
-			-1 => self.0.checked_sub(1),
-			0 => Some(self.0),
-			1 => self.0.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		let y = match dy
-		{
-			-1 => self.1.checked_sub(1),
-			0 => Some(self.1),
-			1 => self.1.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		Some((x, y))
-	}
-}
-
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
 enum Tile
 {
@@ -172,12 +124,10 @@ impl TryFrom<char> for Tile
 	}
 }
 
-fn calculate(field: &[&[Tile]], (pos, dir): ((usize, usize), Direction)) -> std::collections::BTreeSet<(Position, Direction)>
+fn calculate(field: &Grid<Tile>, (pos, dir): (Position, Direction)) -> std::collections::BTreeSet<(Position, Direction)>
 {
 	let mut memo: std::collections::BTreeSet<(Position, Direction)> = Default::default();
-	let mut positions = field.get(pos.1)
-		.and_then(|row| row.get(pos.0))
-		.unwrap()
+	let mut positions = field[pos]
 		.interact(dir)
 		.iter()
 		.map(|&dir| (pos, dir))
@@ -189,15 +139,10 @@ fn calculate(field: &[&[Tile]], (pos, dir): ((usize, usize), Direction)) -> std:
 		{
 			continue;
 		}
-		if let Some(npos) = pos + dir
+		if let Some(npos) = field.step(pos, dir)
 		{
 			trace!("going from {:?} {:?} to {:?}", pos, dir, npos);
-			let pos = npos;
-			if let Some(next) = field.get(pos.1)
-				.and_then(|row| row.get(pos.0))
-			{
-				positions.extend(next.interact(dir).iter().map(|&dir| (pos, dir)))
-			}
+			positions.extend(field[npos].interact(dir).iter().map(|&dir| (npos, dir)))
 		}
 	}
 
@@ -210,36 +155,26 @@ impl super::super::Solution for Solution
 	{
 		debug!("called with input: {}", self.input);
 
-		let field = self.input.lines()
-			.map(|line|
-			{
-				let res = line.chars().map(Tile::try_from).collect::<std::result::Result<Vec<Tile>, Error>>();
-				Result::from(res).context(Error::AocParsing)
-			})
-			.collect::<Result<Vec<Vec<Tile>>>>()?;
+		let field: Grid<Tile> = self.input.parse()?;
 
 		debug!("field:\n{:?}", field);
 
-		let field: &[&[Tile]] = &field.iter()
-			.map(|row| row.as_slice())
-			.collect::<Vec<_>>();
-
 		let result: usize = match self.part
 		{
-			Part::One => calculate(field, ((0, 0), Direction::Right)).into_iter()
+			Part::One => calculate(&field, ((0, 0), Direction::Right)).into_iter()
 				.map(|(pos, _)| pos)
 				.collect::<std::collections::BTreeSet<_>>()
 				.len(),
 			Part::Two =>
 			{
 				std::iter::empty()
-					.chain((0..field.len()).map(|y| ((0, y), Direction::Right)))
-					.chain((0..field.len()).map(|y| ((field.len() - 1, y), Direction::Left)))
-					.chain((0..field.len()).map(|x| ((x, 0), Direction::Down)))
-					.chain((0..field.len()).map(|x| ((x, field.len() - 1), Direction::Up)))
+					.chain((0..field.height()).map(|y| ((0, y), Direction::Right)))
+					.chain((0..field.height()).map(|y| ((field.width() - 1, y), Direction::Left)))
+					.chain((0..field.width()).map(|x| ((x, 0), Direction::Down)))
+					.chain((0..field.width()).map(|x| ((x, field.height() - 1), Direction::Up)))
 					.map(|entry|
 					{
-						let len = calculate(field, entry).into_iter()
+						let len = calculate(&field, entry).into_iter()
 							.map(|(pos, _)| pos)
 							.collect::<std::collections::BTreeSet<_>>()
 							.len();
@@ -4,11 +4,14 @@ use ::
 {
 	std::
 	{
-		ops::RangeInclusive,
+		collections::HashMap,
+		ops::{ Range, RangeInclusive },
 		convert::TryFrom,
 	},
 };
 
+use super::super::common::interval::{ BoxSet, IntervalSet };
+
 /// # Examples
 ///
 /// Part 1:
@@ -61,11 +64,16 @@ use ::
 ///     {x=2461,m=1339,a=466,s=291}\n\
 ///     {x=2127,m=1623,a=2188,s=1013}";
 /// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "167409079868000");
+/// // only ratings up to 2000
+/// assert_eq!(Solution::part2(input.to_string()).range(1..=2000).solve().unwrap(), "12011610400000");
 /// ```
 pub struct Solution
 {
 	input: String,
 	part: AocPart,
+	range: RangeInclusive<usize>,
+	queries: Vec<String>,
+	boxes: bool,
 }
 
 impl Solution
@@ -76,6 +84,9 @@ impl Solution
 		{
 			part: AocPart::One,
 			input,
+			range: 1..=4000,
+			queries: Vec::new(),
+			boxes: false,
 		}
 	}
 
@@ -85,8 +96,60 @@ impl Solution
 		{
 			part: AocPart::Two,
 			input,
+			range: 1..=4000,
+			queries: Vec::new(),
+			boxes: false,
 		}
 	}
+
+	/// The ratings every category may take, used for part two and the printed boxes.
+	pub fn range(self, range: RangeInclusive<usize>) -> Self
+	{
+		Self { range, ..self }
+	}
+
+	/// Parts like `{x=787,m=2655,a=1222,s=2876}` whose path through the workflows is printed.
+	pub fn queries(self, queries: Vec<String>) -> Self
+	{
+		Self { queries, ..self }
+	}
+
+	pub fn boxes(self, boxes: bool) -> Self
+	{
+		Self { boxes, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("range")
+				.value_name("MIN..=MAX")
+				.long("range")
+				.help("the ratings every category may take")
+				.value_parser(|s: &str| -> std::result::Result<RangeInclusive<usize>, String>
+				{
+					let (min, max) = s.split_once("..=").ok_or_else(|| format!("expected MIN..=MAX, got {:?}", s))?;
+					let max = max.parse::<usize>().map_err(|err| format!("{}", err))?;
+					if max == usize::MAX
+					{
+						return Err(format!("the range must end below {}", usize::MAX));
+					}
+					Ok(min.parse().map_err(|err| format!("{}", err))?..=max)
+				})
+				.default_value("1..=4000"),
+			clap::Arg::new("query")
+				.value_name("PART")
+				.short('q')
+				.long("query")
+				.help("print the workflows the part passes, like {x=787,m=2655,a=1222,s=2876}")
+				.action(clap::ArgAction::Append),
+			clap::Arg::new("boxes")
+				.long("boxes")
+				.help("print the accepted ratings as disjoint boxes")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
 }
 
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
@@ -118,6 +181,19 @@ impl<S> From<S> for Goto
 	}
 }
 
+impl std::fmt::Display for Goto
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Goto::Accept => write!(f, "A"),
+			Goto::Reject => write!(f, "R"),
+			Goto::Target(name) => write!(f, "{}", name),
+		}
+	}
+}
+
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
 enum Var
 {
@@ -161,12 +237,13 @@ impl CondOp
 		}
 	}
 
-	fn num(&self) -> &usize
+	/// All values matching the condition.
+	fn range(self) -> Range<usize>
 	{
 		match self
 		{
-			CondOp::Left(cond) => cond,
-			CondOp::Right(cond) => cond,
+			CondOp::Left(cond) => 0..cond,
+			CondOp::Right(cond) => cond.saturating_add(1)..usize::MAX,
 		}
 	}
 }
@@ -226,12 +303,12 @@ impl std::str::FromStr for Condition
 }
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
-struct Part
+pub struct Part
 {
-	x: usize,
-	m: usize,
-	a: usize,
-	s: usize,
+	pub x: usize,
+	pub m: usize,
+	pub a: usize,
+	pub s: usize,
 }
 
 impl std::str::FromStr for Part
@@ -250,6 +327,14 @@ impl std::str::FromStr for Part
 	}
 }
 
+impl std::fmt::Display for Part
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
+	}
+}
+
 impl Part
 {
 	fn get(&self, v: Var) -> usize
@@ -279,12 +364,16 @@ struct Rule
 
 impl Rule
 {
-	fn process(&self, part: &Part) -> Goto
+	fn process(&self, part: &Part) -> &Goto
 	{
 		self.condition.iter()
 			.find_map(|condition| condition.process(part))
 			.unwrap_or(&self.fallback)
-			.clone()
+	}
+
+	fn targets(&self) -> impl Iterator<Item=&Goto>
+	{
+		self.condition.iter().map(|condition| &condition.goto).chain([&self.fallback])
 	}
 }
 
@@ -309,81 +398,240 @@ impl std::str::FromStr for Rule
 	}
 }
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq, Default)]
-struct Restrictions
+/// The workflows every part runs through, starting at `in`.
+///
+/// Parsing makes sure every workflow sent to exists and no part can loop forever.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2023::d19::{ Part, Workflows };
+/// let workflows: Workflows = "in{x<10:A,s>5:lo,R}\nlo{m>2:A,R}".parse().unwrap();
+/// let part: Part = "{x=12,m=3,a=1,s=7}".parse().unwrap();
+/// assert_eq!(workflows.path(&part), vec!["in", "lo", "A"]);
+/// assert!(workflows.accepts(&part));
+///
+/// let accepted = workflows.compile(1..=20).unwrap();
+/// assert_eq!(accepted.to_string(), "\
+///     x=1..=9 m=1..=20 a=1..=20 s=1..=20\n\
+///     x=10..=20 m=3..=20 a=1..=20 s=6..=20\n");
+/// assert_eq!(accepted.combinations().unwrap(), 9 * 20 * 20 * 20 + 11 * 18 * 20 * 15);
+/// assert!(workflows.compile(1..=70000).unwrap().combinations().is_err());
+/// assert!(workflows.compile(1..=usize::MAX).is_err());
+///
+/// assert!("in{x<10:A,lo}".parse::<Workflows>().is_err());
+/// assert!("in{x<10:A,lo}\nlo{m>2:in,R}".parse::<Workflows>().is_err());
+/// assert!("start{x<10:A,R}".parse::<Workflows>().is_err());
+/// ```
+///
+/// The example of the puzzle, and a small workflow compared with following every single part
+/// through it:
+///
+/// ```
+/// # use adventofcode::solution::y2023::d19::{ Part, Workflows };
+/// let workflows: Workflows = "\
+///     px{a<2006:qkq,m>2090:A,rfg}\n\
+///     pv{a>1716:R,A}\n\
+///     lnx{m>1548:A,A}\n\
+///     rfg{s<537:gd,x>2440:R,A}\n\
+///     qs{s>3448:A,lnx}\n\
+///     qkq{x<1416:A,crn}\n\
+///     crn{x>2662:A,R}\n\
+///     in{s<1351:px,qqz}\n\
+///     qqz{s>2770:qs,m<1801:hdj,R}\n\
+///     gd{a>3333:R,R}\n\
+///     hdj{m>838:A,pv}".parse().unwrap();
+/// let accepted = workflows.compile(1..=4000).unwrap();
+/// for (part, expected) in [("{x=787,m=2655,a=1222,s=2876}", true), ("{x=1679,m=44,a=2067,s=496}", false),
+///     ("{x=2036,m=264,a=79,s=2244}", true), ("{x=2461,m=1339,a=466,s=291}", false), ("{x=2127,m=1623,a=2188,s=1013}", true)].iter()
+/// {
+///     let part: Part = part.parse().unwrap();
+///     assert_eq!(workflows.accepts(&part), *expected);
+///     assert_eq!(accepted.contains(&part), *expected);
+/// }
+/// assert_eq!(accepted.combinations().unwrap(), 167409079868000);
+///
+/// let workflows: Workflows = "in{x<4:lo,m>5:A,hi}\nlo{a>2:hi,s<3:R,A}\nhi{s>4:A,x>2:R,A}".parse().unwrap();
+/// let accepted = workflows.compile(1..=7).unwrap();
+/// let mut count = 0;
+/// for x in 1..=7 { for m in 1..=7 { for a in 1..=7 { for s in 1..=7
+/// {
+///     let part = Part { x, m, a, s, };
+///     assert_eq!(accepted.contains(&part), workflows.accepts(&part));
+///     count += workflows.accepts(&part) as usize;
+/// } } } }
+/// assert_eq!(accepted.combinations().unwrap(), count);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflows
 {
-	x: Option<RangeInclusive<usize>>,
-	m: Option<RangeInclusive<usize>>,
-	a: Option<RangeInclusive<usize>>,
-	s: Option<RangeInclusive<usize>>,
+	rules: HashMap<String, Rule>,
 }
 
-impl Restrictions
+impl std::str::FromStr for Workflows
 {
-	fn get(&self, v: Var) -> &Option<RangeInclusive<usize>>
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
-		match v
+		let rules = input.lines()
+			.map(|line|
+			{
+				let rule = line.parse::<Rule>().with_context(|| anyhow!("cannot parse rule from {:?}", line))?;
+				Ok((rule.name.clone(), rule))
+			})
+			.inspect(|res| trace!("parsed rule: {:?}", res))
+			.collect::<Result<HashMap<String, Rule>>>().context(Error::AocParsing)?;
+
+		if !rules.contains_key("in")
 		{
-			Var::Xcool => &self.x,
-			Var::Musical => &self.m,
-			Var::Aerodynamic => &self.a,
-			Var::Shiny => &self.s,
+			Err(anyhow!("there is no workflow named \"in\""))?;
+		}
+		for rule in rules.values()
+		{
+			for goto in rule.targets()
+			{
+				if let Goto::Target(name) = goto
+				{
+					if !rules.contains_key(name)
+					{
+						Err(anyhow!("workflow {:?} sends to missing workflow {:?}", rule.name, name))?;
+					}
+				}
+			}
 		}
-	}
 
-	fn get_mut(&mut self, v: Var) -> &mut Option<RangeInclusive<usize>>
-	{
-		match v
+		// depth first search, a workflow still on the path (false) being reached again means a loop
+		fn visit<'a>(name: &'a str, rules: &'a HashMap<String, Rule>, visited: &mut HashMap<&'a str, bool>) -> Result<()>
 		{
-			Var::Xcool => &mut self.x,
-			Var::Musical => &mut self.m,
-			Var::Aerodynamic => &mut self.a,
-			Var::Shiny => &mut self.s,
+			match visited.get(name)
+			{
+				Some(true) => return Ok(()),
+				Some(false) => bail!("workflows loop through {:?}", name),
+				None => {},
+			}
+			visited.insert(name, false);
+			for goto in rules[name].targets()
+			{
+				if let Goto::Target(next) = goto
+				{
+					visit(next, rules, visited)?;
+				}
+			}
+			visited.insert(name, true);
+			Ok(())
 		}
+		visit("in", &rules, &mut HashMap::new())?;
+
+		Ok(Self { rules, })
 	}
+}
 
-	fn combinations(&self) -> usize
+impl Workflows
+{
+	/// The names of the workflows the part passes through, ending with `A` or `R`.
+	pub fn path(&self, part: &Part) -> Vec<String>
 	{
-		self.x.clone().map(|r| r.count()).unwrap_or(4000)
-			* self.m.clone().map(|r| r.count()).unwrap_or(4000)
-			* self.a.clone().map(|r| r.count()).unwrap_or(4000)
-			* self.s.clone().map(|r| r.count()).unwrap_or(4000)
+		std::iter::successors(Some(Goto::Target("in".into())), |goto|
+			{
+				trace!("part {:?}: checking {:?}", part, goto);
+				match goto
+				{
+					Goto::Target(goto) => Some(self.rules[goto].process(part).clone()),
+					_ => None,
+				}
+			})
+			.map(|goto| goto.to_string())
+			.collect()
 	}
 
-	// left: matches, right: does not match
-	fn merge(mut self, condition: &Condition) -> (Option<Restrictions>, Option<Restrictions>)
+	pub fn accepts(&self, part: &Part) -> bool
 	{
-		let r = self.get(condition.var).clone().unwrap_or(1..=4000);
-		if r.contains(condition.op.num())
-		{
-			let low = *r.start();
-			let high = *r.end();
-			let (lc, rc) = match condition.op
-			{
-				CondOp::Left(num) => (low..=(num - 1), num..=high),
-				CondOp::Right(num) => ((num + 1)..=high, low..=num),
-			};
-			let mut left = self.clone();
-			*left.get_mut(condition.var) = Some(lc);
-			*self.get_mut(condition.var) = Some(rc);
-			return (Some(left), Some(self))
-		}
-		if r.start() > condition.op.num()
+		self.path(part).last().map(String::as_str) == Some("A")
+	}
+
+	/// All accepted parts within the range of ratings as disjoint boxes, found by splitting the
+	/// box of all parts along the conditions of every workflow.
+	///
+	/// Fails when the range ends at [`usize::MAX`], which cannot be represented as a half-open range.
+	pub fn compile(&self, range: RangeInclusive<usize>) -> Result<Accepted>
+	{
+		let end = range.end().checked_add(1).context("range ends at the largest rating possible")?;
+		let all = *range.start()..end;
+		let mut accepted = BoxSet::new();
+		let mut memo = vec![(Goto::from("in"), [all.clone(), all.clone(), all.clone(), all])];
+		'outer: while let Some((goto, mut restrictions)) = memo.pop()
 		{
-			match condition.op
+			trace!("checking {:?}: restrictions {:?}", goto, restrictions);
+			match goto
 			{
-				CondOp::Left(_) => (None, Some(self)),
-				CondOp::Right(_) => (Some(self), None),
+				Goto::Reject => {},
+				Goto::Accept => accepted.insert(restrictions),
+				Goto::Target(next) =>
+				{
+					let rule = &self.rules[&next];
+					for condition in rule.condition.iter()
+					{
+						let values = IntervalSet::from(restrictions[condition.var as usize].clone());
+						let matching = IntervalSet::from(condition.op.range());
+						if let Some(yes) = values.intersection(&matching).hull()
+						{
+							let mut yes_restrictions = restrictions.clone();
+							yes_restrictions[condition.var as usize] = yes;
+							memo.push((condition.goto.clone(), yes_restrictions));
+						}
+						match values.difference(&matching).hull()
+						{
+							Some(no) => restrictions[condition.var as usize] = no,
+							None => continue 'outer,
+						}
+					}
+					memo.push((rule.fallback.clone(), restrictions));
+				},
 			}
 		}
-		else
+		Ok(Accepted { boxes: accepted, })
+	}
+}
+
+/// The accepted parts as disjoint boxes of ratings, printed one box per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accepted
+{
+	boxes: BoxSet<usize, 4>,
+}
+
+impl Accepted
+{
+	pub fn contains(&self, part: &Part) -> bool
+	{
+		self.boxes.contains(&[part.x, part.m, part.a, part.s])
+	}
+
+	/// The number of accepted parts, failing if it does not fit into a `usize`.
+	pub fn combinations(&self) -> Result<usize>
+	{
+		self.boxes.boxes().iter()
+			.map(|cuboid| cuboid.iter().try_fold(1usize, |size, range| size.checked_mul(range.end - range.start)))
+			.try_fold(0usize, |total, size| size.and_then(|size| total.checked_add(size)))
+			.context("the number of combinations overflows")
+	}
+}
+
+impl std::fmt::Display for Accepted
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		let mut boxes = self.boxes.boxes().to_vec();
+		boxes.sort_by_key(|cuboid| cuboid.iter().map(|range| (range.start, range.end)).collect::<Vec<_>>());
+		for cuboid in boxes
 		{
-			match condition.op
-			{
-				CondOp::Left(_) => (Some(self), None),
-				CondOp::Right(_) => (None, Some(self)),
-			}
+			writeln!(f, "x={}..={} m={}..={} a={}..={} s={}..={}",
+				cuboid[0].start, cuboid[0].end - 1,
+				cuboid[1].start, cuboid[1].end - 1,
+				cuboid[2].start, cuboid[2].end - 1,
+				cuboid[3].start, cuboid[3].end - 1)?;
 		}
+		Ok(())
 	}
 }
 
@@ -393,15 +641,8 @@ impl super::super::Solution for Solution
 	{
 		debug!("called with input: {}", self.input);
 
-		let (rules, parts) = self.input.split_once("\n\n").ok_or(anyhow!("cannot split on double newline"))?;
-		let rules = rules.lines()
-			.map(|line|
-			{
-				let rule = line.parse::<Rule>().with_context(|| anyhow!("cannot parse rule from {:?}", line))?;
-				Ok((rule.name.clone(), rule))
-			})
-			.inspect(|res| trace!("parsed rule: {:?}", res))
-			.collect::<Result<std::collections::HashMap<String, Rule>>>().context(Error::AocParsing)?;
+		let (workflows, parts) = self.input.split_once("\n\n").ok_or(anyhow!("cannot split on double newline"))?;
+		let workflows = workflows.parse::<Workflows>()?;
 
 		let parts = parts.lines()
 			.map(|line|
@@ -412,73 +653,35 @@ impl super::super::Solution for Solution
 			.inspect(|res| trace!("parsed part: {:?}", res))
 			.collect::<Result<Vec<_>>>().context(Error::AocParsing)?;
 
+		for query in self.queries.iter()
+		{
+			let part = query.parse::<Part>().with_context(|| anyhow!("cannot parse part from {:?}", query))?;
+			println!("{}: {}", part, workflows.path(&part).join(" -> "));
+		}
 
-		let result: usize = match self.part
+		let accepted = match (self.part, self.boxes)
 		{
-			AocPart::One =>
+			(AocPart::One, false) => None,
+			_ => Some(workflows.compile(self.range.clone())?),
+		};
+		if let (Some(accepted), true) = (&accepted, self.boxes)
+		{
+			print!("{}", accepted);
+		}
+
+		let result: usize = match (self.part, accepted)
+		{
+			(AocPart::One, _) =>
 			{
 				parts.iter()
-					.filter(|part|
-					{
-						Goto::Accept == std::iter::successors(Some(Goto::Target("in".into())), |goto|
-							{
-								trace!("part {:?}: checking {:?}", part, goto);
-								if let Goto::Target(goto) = goto
-								{
-									Some(rules.get(goto).unwrap().process(part))
-								}
-								else
-								{
-									None
-								}
-							})
-							.last()
-							.unwrap()
-					})
+					.filter(|part| workflows.accepts(part))
 					.map(Part::score)
 					.sum()
 			},
-			AocPart::Two =>
-			{
-				let mut done = Vec::new();
-				let mut memo = vec![("in".into(), Restrictions::default())];
-				'outer: while let Some((goto, mut restrictions)) = memo.pop()
-				{
-					trace!("checking {:?}: restrictions {:?}", goto, goto);
-					match goto
-					{
-						Goto::Reject => {},
-						Goto::Accept => done.push(restrictions),
-						Goto::Target(next) =>
-						{
-							let rule = rules.get(&next).unwrap();
-							for condition in rule.condition.iter()
-							{
-								let (yes, no) = restrictions.merge(condition);
-								if let Some(yes) = yes
-								{
-									memo.push((condition.goto.clone(), yes));
-								}
-								if let Some(no) = no
-								{
-									restrictions = no;
-								}
-								else
-								{
-									break 'outer;
-								}
-							}
-							memo.push((rule.fallback.clone(), restrictions));
-						},
-					}
-				}
-				done.iter()
-					.map(Restrictions::combinations)
-					.sum()
-			},
+			(AocPart::Two, Some(accepted)) => accepted.combinations()?,
+			(AocPart::Two, None) => unreachable!("part two always compiles the workflows"),
 		};
 
 		Ok(format!("{}", result))
 	}
 }
-
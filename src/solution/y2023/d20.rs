@@ -1,5 +1,13 @@
 use crate::error::*;
 
+use std::collections::{ BTreeMap, BTreeSet };
+
+use super::super::common::
+{
+	cycle::{ find_cycle, Period },
+	number::first_coincidence,
+};
+
 /// # Examples
 ///
 /// Part 1:
@@ -15,10 +23,36 @@ use crate::error::*;
 ///     &inv -> a";
 /// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "32000000");
 /// ```
+///
+/// Part 2 with counters of period three and five:
+///
+/// ```
+/// # use adventofcode::solution::{ y2023::d20::Solution, Solution as S };
+/// let input = "\
+///     broadcaster -> a0, b0\n\
+///     %a0 -> a1, ha\n\
+///     %a1 -> ha\n\
+///     &ha -> a0, ia\n\
+///     &ia -> final\n\
+///     %b0 -> b1, hb\n\
+///     %b1 -> b2\n\
+///     %b2 -> hb\n\
+///     &hb -> b0, b1, ib\n\
+///     &ib -> final\n\
+///     &final -> rx";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "15");
+///
+/// // the counters have to be independent of each other
+/// let coupled = input.replace("&ia -> final", "&ia -> final, b1");
+/// assert!(Solution::part2(coupled).solve().is_err());
+/// let inverted = input.replace("&final -> rx", "%final -> rx");
+/// assert!(Solution::part2(inverted).solve().is_err());
+/// ```
 pub struct Solution
 {
 	input: String,
 	part: AocPart,
+	dot: bool,
 }
 
 impl Solution
@@ -29,6 +63,7 @@ impl Solution
 		{
 			part: AocPart::One,
 			input,
+			dot: false,
 		}
 	}
 
@@ -38,8 +73,25 @@ impl Solution
 		{
 			part: AocPart::Two,
 			input,
+			dot: false,
 		}
 	}
+
+	pub fn dot(self, dot: bool) -> Self
+	{
+		Self { dot, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("dot")
+				.long("dot")
+				.help("print the module network as Graphviz DOT")
+				.action(clap::ArgAction::SetTrue),
+		]
+	}
 }
 
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
@@ -57,7 +109,7 @@ enum ModuleType
 	Conjunction,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Module<'a>
 {
 	Broadcast
@@ -72,7 +124,7 @@ enum Module<'a>
 	Conjunction
 	{
 		target: Vec<&'a String>,
-		state: std::collections::BTreeMap<&'a String, bool>,
+		state: BTreeMap<&'a String, bool>,
 	},
 }
 
@@ -108,22 +160,13 @@ impl<'a> Module<'a>
 		}
 	}
 
-	fn has_target(&self, name: &String) -> bool
+	fn targets(&self) -> &[&'a String]
 	{
 		match self
 		{
-			Module::Broadcast { target, } =>
-			{
-				target.iter().any(|&s| s == name)
-			},
-			Module::FlipFlop { target, .. } =>
-			{
-				target.iter().any(|&s| s == name)
-			},
-			Module::Conjunction { target, .. } =>
-			{
-				target.iter().any(|&s| s == name)
-			},
+			Module::Broadcast { target, } => target,
+			Module::FlipFlop { target, .. } => target,
+			Module::Conjunction { target, .. } => target,
 		}
 	}
 
@@ -190,7 +233,7 @@ impl<'a> From<(ModuleType, Vec<&'a String>, Vec<&'a String>)> for Module<'a>
 	}
 }
 
-fn simulate(modules: &mut std::collections::BTreeMap<&String, Module>) -> Vec<(String, bool, String)>
+fn simulate(modules: &mut BTreeMap<&String, Module>) -> Vec<(String, bool, String)>
 {
 	let broadcaster = "broadcaster".to_string();
 	let button = "button".to_string();
@@ -219,6 +262,168 @@ fn simulate(modules: &mut std::collections::BTreeMap<&String, Module>) -> Vec<(S
 	out
 }
 
+/// The network as Graphviz DOT, flip-flops drawn as boxes and conjunctions as diamonds.
+fn dot(modules: &[(ModuleType, String, Vec<String>)]) -> String
+{
+	let mut dot = String::from("digraph modules {\n");
+	for (mtype, name, _) in modules.iter()
+	{
+		let (shape, prefix) = match mtype
+		{
+			ModuleType::Broadcast => ("oval", ""),
+			ModuleType::FlipFlop => ("box", "%"),
+			ModuleType::Conjunction => ("diamond", "&"),
+		};
+		dot += &format!("\t{:?} [shape={}, label=\"{}{}\"];\n", name, shape, prefix, name);
+	}
+	for (_, name, target) in modules.iter()
+	{
+		for target in target.iter()
+		{
+			dot += &format!("\t{:?} -> {:?};\n", name, target);
+		}
+	}
+	dot += "}";
+	dot
+}
+
+/// A sub-circuit driven by the broadcaster and sending to the conjunction in front of `rx`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Counter
+{
+	/// The module of the sub-circuit sending to the conjunction.
+	output: String,
+	/// The presses after which the sub-circuit sent a high pulse to the conjunction, the ones from
+	/// `period.start` on repeating forever.
+	firing: Vec<usize>,
+	period: Period,
+}
+
+impl Counter
+{
+	fn fires_at(&self, press: usize) -> bool
+	{
+		self.firing.binary_search(&self.period.reduce(press)).is_ok()
+	}
+}
+
+/// Splits the network into the independent sub-circuits between the broadcaster and the
+/// conjunction in front of `rx`, simulating each of them on its own until its state repeats.
+fn counters(modules: &BTreeMap<&String, Module>) -> Result<Vec<Counter>>
+{
+	let rx = "rx".to_string();
+	let (&broadcaster, _) = modules.get_key_value(&"broadcaster".to_string()).ok_or_else(|| anyhow!("no broadcaster in the network"))?;
+	let feeding = modules.iter()
+		.filter(|(_, module)| module.targets().contains(&&rx))
+		.collect::<Vec<_>>();
+	let last = match feeding[..]
+	{
+		[(&last, Module::Conjunction { target, .. })] if target == &[&rx] => last,
+		_ => bail!("expected a single conjunction sending only to rx, found {:?}", feeding),
+	};
+
+	let inner = |name: &String| name != broadcaster && name != last && *name != rx;
+	let mut neighbours = BTreeMap::<&String, Vec<&String>>::new();
+	for (&name, module) in modules.iter().filter(|(name, _)| inner(name))
+	{
+		neighbours.entry(name).or_default();
+		for &target in module.targets().iter().filter(|target| inner(target))
+		{
+			neighbours.entry(name).or_default().push(target);
+			neighbours.entry(target).or_default().push(name);
+		}
+	}
+
+	let mut seen = BTreeSet::new();
+	let mut counters = Vec::new();
+	for &start in neighbours.keys()
+	{
+		if !seen.insert(start)
+		{
+			continue;
+		}
+		let mut component = BTreeSet::from([start]);
+		let mut queue = vec![start];
+		while let Some(name) = queue.pop()
+		{
+			for &other in neighbours[name].iter()
+			{
+				if seen.insert(other)
+				{
+					component.insert(other);
+					queue.push(other);
+				}
+			}
+		}
+
+		let outputs = component.iter()
+			.filter(|name| modules.get(*name).map(|module| module.targets().contains(&last)).unwrap_or(false))
+			.collect::<Vec<_>>();
+		let output = match outputs[..]
+		{
+			[&output] => output,
+			_ => bail!("sub-circuit of {:?} sends to {} through {:?} instead of a single module", component, last, outputs),
+		};
+		let target = modules[broadcaster].targets().iter()
+			.copied()
+			.filter(|target| component.contains(target))
+			.collect::<Vec<_>>();
+		anyhow::ensure!(!target.is_empty(), "sub-circuit of {} is not driven by the broadcaster", output);
+
+		let network = modules.iter()
+			.filter(|(name, _)| component.contains(*name))
+			.map(|(&name, module)| (name, module.clone()))
+			.chain([(broadcaster, Module::Broadcast { target, })])
+			.collect::<BTreeMap<_, _>>();
+		let cycle = find_cycle((network, false), |(network, _)|
+		{
+			let mut network = network.clone();
+			let fired = simulate(&mut network)
+				.into_iter()
+				.any(|(target, pulse, source)| pulse && target == *last && source == *output);
+			(network, fired)
+		});
+		let firing = cycle.states().iter()
+			.enumerate()
+			.filter(|(_, (_, fired))| *fired)
+			.map(|(press, _)| press)
+			.collect::<Vec<_>>();
+		let counter = Counter { output: output.to_string(), firing, period: cycle.period(), };
+		debug!("counter {:?} fires after {:?}, repeating with {:?}", counter.output, counter.firing, counter.period);
+		counters.push(counter);
+	}
+
+	anyhow::ensure!(!counters.is_empty(), "no sub-circuits in front of {}", last);
+	Ok(counters)
+}
+
+/// The first press after which every counter fired, combining the repeating presses by the
+/// chinese remainder theorem.
+fn first_press(counters: &[Counter]) -> Option<usize>
+{
+	let once = counters.iter()
+		.flat_map(|counter| counter.firing.iter().copied().filter(move |&press| press < counter.period.start))
+		.filter(|&press| counters.iter().all(|counter| counter.fires_at(press)));
+	let repeating = counters.iter()
+		.map(|counter|
+		{
+			counter.firing.iter()
+				.filter(|&&press| press >= counter.period.start)
+				.map(|&press| (press as i128, counter.period.length as i128))
+				.collect::<Vec<_>>()
+		})
+		.fold(vec![Vec::new()], |combinations, options|
+		{
+			combinations.iter()
+				.flat_map(|combination| options.iter().map(move |&option| combination.iter().copied().chain([option]).collect::<Vec<_>>()))
+				.collect()
+		})
+		.into_iter()
+		.filter_map(first_coincidence)
+		.map(|press| press as usize);
+	once.chain(repeating).min()
+}
+
 impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
@@ -245,9 +450,17 @@ impl super::super::Solution for Solution
 				let target = target.split(", ").map(str::to_string).collect::<Vec<String>>();
 				Ok((mtype, name, target))
 			})
-			.chain([Ok((ModuleType::Broadcast, "rx".to_string(), Vec::new()))])
 			.collect::<Result<Vec<_>>>()?;
 
+		if self.dot
+		{
+			println!("{}", dot(&modules));
+		}
+
+		let modules = modules.into_iter()
+			.chain([(ModuleType::Broadcast, "rx".to_string(), Vec::new())])
+			.collect::<Vec<_>>();
+
 		let mut modules = modules.iter()
 			.map(|(mtype, name, target)|
 			{
@@ -260,7 +473,7 @@ impl super::super::Solution for Solution
 				trace!("new module {:?}: {:?}", name, module);
 				(name, module)
 			})
-			.collect::<std::collections::BTreeMap<&String, Module>>();
+			.collect::<BTreeMap<&String, Module>>();
 
 		let result: usize = match self.part
 		{
@@ -281,32 +494,9 @@ impl super::super::Solution for Solution
 					.map(|(a, b)| a * b)
 					.unwrap()
 			},
-			AocPart::Two =>
-			{
-				let prerx = modules.iter().find_map(|(n, m)| m.has_target(&"rx".to_string()).then_some(n)).unwrap().to_string();
-				let len = modules.get(&prerx).unwrap().bits().len();
-				let mut freqs = std::collections::BTreeMap::<String, usize>::new();
-				for i in 1..
-				{
-					trace!("run {}", i);
-					simulate(&mut modules)
-						.into_iter()
-						.filter(|(target, pulse, _)| *target == prerx && *pulse)
-						.for_each(|(_, _, source)|
-						{
-							freqs.entry(source.clone()).or_insert(i);
-							debug!("found bit at {}", i);
-						});
-					if freqs.len() == len
-					{
-						break;
-					}
-				}
-				freqs.into_values().reduce(num::integer::lcm).unwrap()
-			},
+			AocPart::Two => first_press(&counters(&modules)?).ok_or(Error::AocNoSolution)?,
 		};
 
 		Ok(format!("{}", result))
 	}
 }
-
@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::grid::{ Direction, Position };
+
 /// # Examples
 ///
 /// Part 1:
@@ -67,85 +69,6 @@ impl Solution
 	}
 }
 
-type Position = (usize, usize);
-
-#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
-enum Direction
-{
-	Up,
-	Down,
-	Left,
-	Right,
-}
-
-#[allow(unused)]
-impl Direction
-{
-	fn vector(&self) -> (isize, isize)
-	{
-		match &self
-		{
-			Direction::Up => (-1, 0),
-			Direction::Down => (1, 0),
-			Direction::Left => (0, -1),
-			Direction::Right => (0, 1),
-		}
-	}
-
-	fn slice() -> &'static [Direction]
-	{
-		&[
-			Direction::Right,
-			Direction::Down,
-			Direction::Left,
-			Direction::Up,
-		]
-	}
-
-	fn left(&self) -> Direction
-	{
-		Direction::slice().iter().rev().cycle().skip_while(|&dir| dir != self).nth(1).copied().unwrap()
-	}
-
-	fn right(&self) -> Direction
-	{
-		Direction::slice().iter().cycle().skip_while(|&dir| dir != self).nth(1).copied().unwrap()
-	}
-}
-
-impl std::default::Default for Direction
-{
-	fn default() -> Self
-	{
-		Direction::slice().first().copied().unwrap()
-	}
-}
-
-impl std::ops::Add<Direction> for Position
-{
-	type Output = Option<Position>;
-
-	fn add(self, dir: Direction) -> Option<Position>
-	{
-		let (dy, dx) = dir.vector();
-		let x = match dx
-		{
-			-1 => self.1.checked_sub(1),
-			0 => Some(self.1),
-			1 => self.1.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		let y = match dy
-		{
-			-1 => self.0.checked_sub(1),
-			0 => Some(self.0),
-			1 => self.0.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		Some((y, x))
-	}
-}
-
 fn simulate(garden: std::collections::BTreeSet<Position>, start: Position) -> Box<impl Iterator<Item=std::collections::BTreeSet<Position>>>
 {
 	let len = garden.last().unwrap().0 + 1;
@@ -230,10 +153,10 @@ impl super::super::Solution for Solution
 
 		anyhow::ensure!((self.steps - len / 2) % len == 0, "part 2 optimization failed");
 		anyhow::ensure!((0..len).map(|x| (x, len / 2)).all(|pos| garden.contains(&pos)), "input must contain clear horizontal line");
-		anyhow::ensure!(std::iter::repeat([Direction::Left, Direction::Up]).flatten().scan((len - 1, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, 0)).all(|pos| garden.contains(&pos)), "input must have clear line from right middle to top middle");
-		//anyhow::ensure!(std::iter::repeat([Direction::Left, Direction::Down]).flatten().scan((len - 1, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, len - 1)).all(|pos| garden.contains(&pos)), "input must have clear line from right middle to bottom middle");
-		anyhow::ensure!(std::iter::repeat([Direction::Right, Direction::Up]).flatten().scan((0, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, 0)).all(|pos| garden.contains(&pos)), "input must have clear line from left middle to top middle");
-		anyhow::ensure!(std::iter::repeat([Direction::Right, Direction::Down]).flatten().scan((0, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, len - 1)).all(|pos| garden.contains(&pos)), "input must have clear line from left middle to bottom middle");
+		anyhow::ensure!(std::iter::repeat([Direction::Up, Direction::Left]).flatten().scan((len - 1, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, 0)).all(|pos| garden.contains(&pos)), "input must have clear line from right middle to top middle");
+		//anyhow::ensure!(std::iter::repeat([Direction::Down, Direction::Left]).flatten().scan((len - 1, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, len - 1)).all(|pos| garden.contains(&pos)), "input must have clear line from right middle to bottom middle");
+		anyhow::ensure!(std::iter::repeat([Direction::Up, Direction::Right]).flatten().scan((0, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, 0)).all(|pos| garden.contains(&pos)), "input must have clear line from left middle to top middle");
+		anyhow::ensure!(std::iter::repeat([Direction::Down, Direction::Right]).flatten().scan((0, len / 2), |state, dir| { (*state + dir).map(|res| { *state = res; res }) }).take_while(|&pos| pos != (len / 2, len - 1)).all(|pos| garden.contains(&pos)), "input must have clear line from left middle to bottom middle");
 
 		let even =
 		{
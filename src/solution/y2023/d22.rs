@@ -1,5 +1,9 @@
 use crate::error::*;
 
+use std::convert::TryInto;
+
+use super::super::common::geometry::{ Cuboid, Vec3 };
+
 /// # Examples
 ///
 /// ```
@@ -20,18 +24,36 @@ pub struct Solution
 {
 	input: String,
 	part: Part,
+	chain: bool,
 }
 
 impl Solution
 {
 	pub fn part1(input: String) -> Self
 	{
-		Self { part: Part::One, input, }
+		Self { part: Part::One, input, chain: false, }
 	}
 
 	pub fn part2(input: String) -> Self
 	{
-		Self { part: Part::Two, input, }
+		Self { part: Part::Two, input, chain: false, }
+	}
+
+	/// Prints how many other bricks fall when each brick is disintegrated.
+	pub fn chain(self, chain: bool) -> Self
+	{
+		Self { chain, ..self }
+	}
+
+	pub fn args() -> Vec<clap::Arg>
+	{
+		vec!
+		[
+			clap::Arg::new("chain")
+				.long("chain")
+				.help("print how many other bricks fall when disintegrating each brick")
+				.action(clap::ArgAction::SetTrue),
+		]
 	}
 }
 
@@ -42,34 +64,27 @@ enum Part
 	Two,
 }
 
+/// A brick, spanning all cubes between both corners.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Block
+pub struct Block
 {
-	parts: Vec<(usize, usize, usize)>,
+	cuboid: Cuboid<i64>,
 }
 
 impl Block
 {
-	fn fallable<'a, 'b, I>(&'b self, other: I) -> bool
-		where
-			I: Copy + IntoIterator<Item=&'a Block>,
-			'a: 'b,
+	pub fn cuboid(&self) -> &Cuboid<i64>
 	{
-		self.parts.iter()
-			.map(|&(x, y, z)| (x, y, z - 1))
-			.all(|pos| pos.2 > 0 && !other.into_iter().filter(|&o| o != self).flat_map(|b| b.parts.iter()).any(|opos| pos.eq(opos)))
+		&self.cuboid
 	}
+}
 
-	fn fall<'a, 'b, I>(&'b mut self, other: I)
-		where
-			I: Copy + IntoIterator<Item=&'a Block>,
-			'a: 'b,
+impl std::fmt::Display for Block
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
 	{
-		if self.fallable(other)
-		{
-			trace!("falling {} blocks", self.parts.len());
-			self.parts.iter_mut().for_each(|(_, _, ref mut z)| *z -= 1);
-		}
+		let (min, max) = (self.cuboid.min(), self.cuboid.max());
+		write!(f, "{},{},{}~{},{},{}", min.x, min.y, min.z, max.x, max.y, max.z)
 	}
 }
 
@@ -79,126 +94,277 @@ impl std::str::FromStr for Block
 	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
 		let (from, to) = input.split_once('~').ok_or_else(|| anyhow!("cannot split {:?} by '~'", input))?;
-		let from = from.split(',')
-			.map(|s| s.parse::<usize>())
-			.collect::<std::result::Result<Vec<_>, _>>()?;
-		let from = &from;
-		let to = to.split(',')
-			.map(|s| s.parse::<usize>())
-			.collect::<std::result::Result<Vec<_>, _>>()?;
-		let to = &to;
-
-		let parts = (from[0].min(to[0])..=from[0].max(to[0]))
-			.flat_map(|x|
-			{
-				(from[1].min(to[1])..=from[1].max(to[1]))
-					.flat_map(move |y|
-					{
-						(from[2].min(to[2])..=from[2].max(to[2]))
-							.map(move |z|
-							{
-								(x, y, z)
-							})
-					})
-			})
-			.collect::<Vec<_>>();
-
-		if parts.is_empty()
+		let corner = |s: &str| -> Result<Vec3<i64>>
 		{
-			Err(anyhow!("empty blocks are not allowed"))?;
+			let coordinates: [i64; 3] = s.split(',')
+				.map(|s| s.parse::<i64>())
+				.collect::<std::result::Result<Vec<_>, _>>()?
+				.try_into()
+				.map_err(|v| anyhow!("could not convert {:?} to a corner", v))?;
+			Ok(coordinates.into())
+		};
+		let cuboid = Cuboid::new(corner(from)?, corner(to)?);
+
+		if cuboid.min().z < 1
+		{
+			Err(anyhow!("block {:?} reaches into the ground", input))?;
 		}
 		Ok(Block
 		{
-			parts,
+			cuboid,
 		})
 	}
 }
 
-impl super::super::Solution for Solution
+/// The bricks after they all settled, knowing which bricks rest on which.
+///
+/// Seen as a graph from the ground upwards, removing a brick makes exactly the bricks fall it
+/// dominates, i.e. those every path from the ground passes through it. Going from the bottom,
+/// the immediate dominator of a brick is the nearest common dominator of all bricks it rests on.
+///
+/// Bricks are numbered in the order they are given.
+///
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::y2023::d22::Stack;
+/// let stack: Stack = "\
+///     1,0,1~1,2,1\n\
+///     0,0,2~2,0,2\n\
+///     0,2,3~2,2,3\n\
+///     0,0,4~0,2,4\n\
+///     2,0,5~2,2,5\n\
+///     0,1,6~2,1,6\n\
+///     1,1,8~1,1,9".parse().unwrap();
+/// assert_eq!(stack.block(6).to_string(), "1,1,5~1,1,6");
+/// assert_eq!(stack.supports(0), &[1, 2]);
+/// assert_eq!(stack.supported_by(3), &[1, 2]);
+/// assert_eq!(stack.dominator(3), Some(0));
+/// assert_eq!(stack.dominator(0), None);
+/// assert_eq!(stack.falling(0), vec![1, 2, 3, 4, 5, 6]);
+/// assert_eq!(stack.falling(1), vec![]);
+/// assert_eq!(stack.falling(5), vec![6]);
+/// assert_eq!((0..stack.len()).map(|idx| stack.chain_reaction(idx)).collect::<Vec<_>>(), vec![6, 0, 0, 0, 0, 1, 0]);
+/// ```
+///
+/// A brick resting on two bricks which both rest on the same brick is only held up by that one,
+/// while a brick also resting on a tower of its own stays put:
+///
+/// ```
+/// # use adventofcode::solution::y2023::d22::Stack;
+/// let stack: Stack = "\
+///     0,0,1~2,0,1\n\
+///     0,0,2~0,0,2\n\
+///     2,0,2~2,0,2\n\
+///     0,0,3~2,0,3\n\
+///     1,0,4~1,0,4\n\
+///     0,2,1~0,2,3\n\
+///     0,0,10~0,2,10".parse().unwrap();
+/// assert_eq!(stack.block(6).to_string(), "0,0,4~0,2,4");
+/// assert_eq!(stack.supported_by(3), &[1, 2]);
+/// assert_eq!(stack.supported_by(6), &[3, 5]);
+/// assert_eq!(stack.dominator(3), Some(0));
+/// assert_eq!(stack.dominator(4), Some(3));
+/// assert_eq!(stack.dominator(6), None);
+/// assert_eq!(stack.falling(0), vec![1, 2, 3, 4]);
+/// assert_eq!(stack.falling(3), vec![4]);
+/// assert_eq!(stack.falling(5), vec![]);
+/// assert_eq!((0..stack.len()).map(|idx| stack.chain_reaction(idx)).collect::<Vec<_>>(), vec![4, 0, 0, 1, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack
 {
-	fn solve(&self) -> Result<String>
+	blocks: Vec<Block>,
+	supports: Vec<Vec<usize>>,
+	supported_by: Vec<Vec<usize>>,
+	/// The immediate dominator of every brick, [`None`] for the ground.
+	dominators: Vec<Option<usize>>,
+	/// The bricks every brick immediately dominates.
+	dominated: Vec<Vec<usize>>,
+	/// The number of bricks every brick dominates, including itself.
+	sizes: Vec<usize>,
+}
+
+impl Stack
+{
+	/// Drops all bricks from the lowest upwards, keeping the highest brick over every column.
+	pub fn new(mut blocks: Vec<Block>) -> Self
 	{
-		debug!("called with input:\n{}", self.input);
+		let mut order = (0..blocks.len()).collect::<Vec<_>>();
+		order.sort_by_key(|&idx| blocks[idx].cuboid.min().z);
 
-		let mut blocks = self.input.lines()
-			.map(|line| Ok(line.parse()?))
-			.collect::<Result<std::collections::VecDeque<Block>>>()?;
+		let mut heights = std::collections::HashMap::<(i64, i64), (i64, usize)>::new();
+		let mut supports = vec![Vec::new(); blocks.len()];
+		let mut supported_by = vec![Vec::new(); blocks.len()];
+		for &idx in order.iter()
+		{
+			let (min, max) = (blocks[idx].cuboid.min(), blocks[idx].cuboid.max());
+			let columns = (min.x..=max.x)
+				.flat_map(|x| (min.y..=max.y).map(move |y| (x, y)))
+				.collect::<Vec<_>>();
+			let floor = columns.iter()
+				.filter_map(|column| heights.get(column))
+				.map(|&(height, _)| height)
+				.max()
+				.unwrap_or(0);
+			let mut below = columns.iter()
+				.filter_map(|column| heights.get(column))
+				.filter_map(|&(height, other)| (height == floor).then_some(other))
+				.collect::<Vec<_>>();
+			below.sort_unstable();
+			below.dedup();
+
+			trace!("falling {} from {} onto {:?}", min.z - floor - 1, min.z, below);
+			blocks[idx].cuboid = blocks[idx].cuboid.translate(Vec3::new(0, 0, floor + 1 - min.z));
+			let top = blocks[idx].cuboid.max().z;
+			for column in columns
+			{
+				heights.insert(column, (top, idx));
+			}
+			for &other in below.iter()
+			{
+				supports[other].push(idx);
+			}
+			supported_by[idx] = below;
+		}
+		for above in supports.iter_mut()
+		{
+			above.sort_unstable();
+		}
+
+		// the ground has depth 0 in the dominator tree
+		let mut dominators = vec![None; blocks.len()];
+		let mut depths = vec![0; blocks.len()];
+		for &idx in order.iter()
+		{
+			let depth = |node: Option<usize>| node.map_or(0, |node| depths[node]);
+			let dominator = supported_by[idx].iter()
+				.map(|&other| Some(other))
+				.reduce(|mut a, mut b|
+				{
+					while a != b
+					{
+						if depth(a) >= depth(b)
+						{
+							a = a.and_then(|a| dominators[a]);
+						}
+						else
+						{
+							b = b.and_then(|b| dominators[b]);
+						}
+					}
+					a
+				})
+				.flatten();
+			depths[idx] = depth(dominator) + 1;
+			dominators[idx] = dominator;
+		}
 
-		while blocks.iter().any(|block| block.fallable(&blocks))
+		let mut dominated = vec![Vec::new(); blocks.len()];
+		let mut sizes = vec![1; blocks.len()];
+		for &idx in order.iter().rev()
 		{
-			trace!("blocks are fallable");
-			for _ in 0..blocks.len()
+			if let Some(dominator) = dominators[idx]
 			{
-				let mut block = blocks.pop_front().unwrap();
-				block.fall(&blocks);
-				blocks.push_back(block);
+				dominated[dominator].push(idx);
+				sizes[dominator] += sizes[idx];
 			}
 		}
 
+		Self { blocks, supports, supported_by, dominators, dominated, sizes, }
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.blocks.len()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.blocks.is_empty()
+	}
+
+	/// The brick where it came to rest.
+	pub fn block(&self, idx: usize) -> &Block
+	{
+		&self.blocks[idx]
+	}
+
+	/// The bricks resting directly on top of the brick.
+	pub fn supports(&self, idx: usize) -> &[usize]
+	{
+		&self.supports[idx]
+	}
+
+	/// The bricks the brick rests directly on, empty when lying on the ground.
+	pub fn supported_by(&self, idx: usize) -> &[usize]
+	{
+		&self.supported_by[idx]
+	}
+
+	/// The closest brick whose removal makes this one fall, [`None`] when only the ground holds it.
+	pub fn dominator(&self, idx: usize) -> Option<usize>
+	{
+		self.dominators[idx]
+	}
+
+	/// All other bricks falling when the brick is disintegrated, in ascending order.
+	pub fn falling(&self, idx: usize) -> Vec<usize>
+	{
+		let mut falling = Vec::new();
+		let mut stack = self.dominated[idx].clone();
+		while let Some(other) = stack.pop()
+		{
+			falling.push(other);
+			stack.extend(self.dominated[other].iter().copied());
+		}
+		falling.sort_unstable();
+		falling
+	}
+
+	/// The number of other bricks falling when the brick is disintegrated.
+	pub fn chain_reaction(&self, idx: usize) -> usize
+	{
+		self.sizes[idx] - 1
+	}
+}
+
+impl std::str::FromStr for Stack
+{
+	type Err = Error;
+	fn from_str(input: &str) -> std::result::Result<Self, Error>
+	{
+		let blocks = input.lines()
+			.map(|line| Ok(line.parse()?))
+			.collect::<Result<Vec<Block>>>()?;
+		Ok(Self::new(blocks))
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input:\n{}", self.input);
+
+		let stack = self.input.parse::<Stack>()?;
 		debug!("all blocks have fallen");
-		debug!("blocks:\n{:?}", blocks);
+		debug!("stack:\n{:?}", stack);
 
-		let hierarchy = blocks.iter()
-			.enumerate()
-			.map(|(idx, block)|
+		if self.chain
+		{
+			for idx in 0..stack.len()
 			{
-				let holding = blocks.iter()
-					.enumerate()
-					.filter(|(iidx, _)| idx != *iidx)
-					.filter_map(|(idx, inner)|
-					{
-						inner.parts.iter()
-							.map(|&(x, y, z)| (x, y, z + 1))
-							.any(|part| block.parts.iter().any(|&p| p == part))
-							.then_some(idx)
-					})
-					.collect();
-				(idx, holding)
-			})
-			.collect::<std::collections::BTreeMap<usize, std::collections::BTreeSet<usize>>>();
-
-		debug!("hierarchy:\n{:?}", hierarchy);
+				println!("{:>5}: {} -> {}", idx, stack.block(idx), stack.chain_reaction(idx));
+			}
+		}
 
 		let result: usize = match self.part
 		{
-			Part::One =>
-			{
-				let unstable = hierarchy.values()
-					.flat_map(|v| (v.len() == 1).then_some(v))
-					.flatten()
-					.collect::<std::collections::BTreeSet<_>>();
-				hierarchy.len() - unstable.len()
-			},
-			Part::Two =>
-			{
-				hierarchy.keys()
-					.map(|&name|
-					{
-						trace!("starting with {}", name);
-						let mut falling = std::collections::BTreeSet::<usize>::from([name]);
-						loop
-						{
-							let len = falling.len();
-							for (i, holding) in hierarchy.iter()
-							{
-								if !holding.is_empty() && falling.is_superset(holding)
-								{
-									trace!("adding {}", i);
-									falling.insert(*i);
-								}
-							}
-							if len == falling.len()
-							{
-								trace!("returning {}", len - 1);
-								return len - 1;
-							}
-						}
-					})
-					.sum()
-			},
+			Part::One => (0..stack.len()).filter(|&idx| stack.chain_reaction(idx) == 0).count(),
+			Part::Two => (0..stack.len()).map(|idx| stack.chain_reaction(idx)).sum(),
 		};
 
 		Ok(format!("{}", result))
 	}
 }
-
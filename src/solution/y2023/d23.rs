@@ -1,5 +1,11 @@
 use crate::error::*;
 
+use super::super::common::
+{
+	grid::{ Direction, Position },
+	search::bfs,
+};
+
 /// This makes some assumptions:
 ///
 /// - an intersection will never only have one destination (i.e. an intersection will not be a slope itself)
@@ -65,96 +71,6 @@ enum AocPart
 	Two,
 }
 
-type Position = (usize, usize);
-
-#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
-enum Direction
-{
-	Up,
-	Down,
-	Left,
-	Right,
-}
-
-#[allow(unused)]
-impl Direction
-{
-	fn vector(&self) -> (isize, isize)
-	{
-		match &self
-		{
-			Direction::Up => (0, -1),
-			Direction::Down => (0, 1),
-			Direction::Left => (-1, 0),
-			Direction::Right => (1, 0),
-		}
-	}
-
-	fn slice() -> &'static [Direction]
-	{
-		&[
-			Direction::Right,
-			Direction::Down,
-			Direction::Left,
-			Direction::Up,
-		]
-	}
-
-	fn left(&self) -> Direction
-	{
-		Direction::slice().iter().rev().cycle().skip_while(|&dir| dir != self).nth(1).copied().unwrap()
-	}
-
-	fn right(&self) -> Direction
-	{
-		Direction::slice().iter().cycle().skip_while(|&dir| dir != self).nth(1).copied().unwrap()
-	}
-
-	fn symbol(&self) -> char
-	{
-		match &self
-		{
-			Direction::Up => '^',
-			Direction::Down => 'v',
-			Direction::Left => '<',
-			Direction::Right => '>',
-		}
-	}
-}
-
-impl std::default::Default for Direction
-{
-	fn default() -> Self
-	{
-		Direction::slice().first().copied().unwrap()
-	}
-}
-
-impl std::ops::Add<Direction> for Position
-{
-	type Output = Option<Position>;
-
-	fn add(self, dir: Direction) -> Option<Position>
-	{
-		let (dx, dy) = dir.vector();
-		let x = match dx
-		{
-			-1 => self.0.checked_sub(1),
-			0 => Some(self.0),
-			1 => self.0.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		let y = match dy
-		{
-			-1 => self.1.checked_sub(1),
-			0 => Some(self.1),
-			1 => self.1.checked_add(1),
-			_ => unreachable!(),
-		}?;
-		Some((x, y))
-	}
-}
-
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
 enum Tile
 {
@@ -199,93 +115,73 @@ impl super::super::Solution for Solution
 		let exit = *basic_grid.last_key_value().unwrap().0;
 		debug!("exit: {:?}", exit);
 
-		let grid: std::collections::BTreeMap<Position, Vec<(usize, Position)>> = basic_grid.iter()
-			.filter_map(|(&pos, &tile)| (tile == Tile::Path).then_some(pos))
-			// get intersections only
-			.filter(|&pos|
+		// tiles which can be entered from a position, slopes cannot be climbed
+		let basic_grid = &basic_grid;
+		let neighbours = |&pos: &Position|
+		{
+			let candidates = match basic_grid.get(&pos)
 			{
-				Direction::slice().iter()
-					.filter_map(|&dir| pos + dir)
-					.filter(|npos| basic_grid.contains_key(npos))
-					.count() > 2
-			})
-			.chain([entry])
-			.map(|pos|
+				Some(&Tile::Slope(dir)) => vec![dir],
+				_ => Direction::slice().to_vec(),
+			};
+			candidates.into_iter()
+				.filter_map(move |dir| pos + dir)
+				.filter(move |npos|
+				{
+					match basic_grid.get(npos)
+					{
+						Some(&Tile::Slope(dir)) => *npos + dir != Some(pos),
+						Some(Tile::Path) => true,
+						None => false,
+					}
+				})
+		};
+
+		let nodes: Vec<Position> = basic_grid.keys()
+			.copied()
+			.filter(|&pos| Direction::slice().iter().filter_map(|&dir| pos + dir).filter(|npos| basic_grid.contains_key(npos)).count() > 2)
+			.chain([entry, exit])
+			.collect();
+		anyhow::ensure!(nodes.len() <= 64, "too many intersections: {}", nodes.len());
+		let index: std::collections::BTreeMap<Position, usize> = nodes.iter().enumerate().map(|(idx, &pos)| (pos, idx)).collect();
+
+		// follow every passage leaving an intersection to the next one
+		let graph: Vec<Vec<(usize, usize)>> = nodes.iter()
+			.map(|&from|
 			{
-				trace!("tracing from {:?}", pos);
-				let v = Direction::slice().iter()
-					.filter_map(|&dir| pos + dir)
-					.filter(|&npos|
+				neighbours(&from)
+					.filter_map(|first|
 					{
-						if let Some(tile) = basic_grid.get(&npos)
-						{
-							match tile
-							{
-								Tile::Slope(dir) => (npos + *dir) != Some(pos),
-								Tile::Path => true,
-							}
-						}
-						else
+						let passage = |pos: &Position|
 						{
-							false
-						}
-					})
-					.filter_map(|npos|
-					{
-						let mut path = std::collections::BTreeSet::<Position>::from([pos, npos]);
-						std::iter::successors(Some(npos), |&pos|
-							{
-								path.insert(pos);
-								match *basic_grid.get(&pos)?
-								{
-									Tile::Slope(dir) => pos + dir,
-									Tile::Path =>
-									{
-										let mut next = Direction::slice().iter()
-											.filter_map(|&dir| pos + dir)
-											.filter(|npos| !path.contains(npos))
-											.filter(|npos| basic_grid.contains_key(npos));
-										let npos = next.next()?;
-										next.next().is_none().then_some(npos)
-									},
-								}
-							})
-							.enumerate()
-							.last()
-							.filter(|&(_, pos)| pos != entry)
-							.map(|(dist, pos)| (dist + 1, pos))
+							let stop = index.contains_key(pos);
+							neighbours(pos).filter(move |&npos| !stop && npos != from)
+						};
+						let path = bfs([first], passage, |pos| index.contains_key(pos))?;
+						let to = *path.states.last().unwrap();
+						trace!("passage from {:?} to {:?}: {:?}", from, to, path.states);
+						(to != entry).then(|| (path.cost + 1, index[&to]))
 					})
-					.collect();
-				trace!("trace from {:?}: {:?}", pos, v);
-				(pos, v)
+					.collect()
 			})
 			.collect();
 
-		trace!("grid: {:?}", grid);
+		trace!("graph: {:?}", graph);
 
-		let mut finished = Vec::new();
-		let mut paths = std::collections::VecDeque::from([(0, vec![entry])]);
-		while let Some((dist, path)) = paths.pop_front()
+		fn longest(graph: &[Vec<(usize, usize)>], node: usize, exit: usize, visited: u64) -> Option<usize>
 		{
-			let last = path.last().unwrap();
-			if let Some(next) = grid.get(last)
+			if node == exit
 			{
-				for &(ndist, npos) in next
-				{
-					if !path.contains(&npos)
-					{
-						let npath = path.iter().copied().chain([npos]).collect();
-						paths.push_back((dist + ndist, npath));
-					}
-				}
-			}
-			else
-			{
-				debug!("found path of length {} via {:?}", dist, path);
-				finished.push((dist, path));
+				return Some(0);
 			}
+			graph[node].iter()
+				.filter(|&&(_, next)| visited & (1 << next) == 0)
+				.filter_map(|&(dist, next)| Some(dist + longest(graph, next, exit, visited | (1 << next))?))
+				.max()
 		}
-		let result: usize = finished.into_iter().map(|(distance, _)| distance).max().unwrap();
+
+		let result = longest(&graph, index[&entry], index[&exit], 1 << index[&entry])
+			.ok_or(Error::AocNoSolution)?;
 
 		Ok(format!("{}", result))
 	}
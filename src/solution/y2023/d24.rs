@@ -6,7 +6,14 @@ use ::
 	{
 		convert::TryInto,
 		ops::RangeInclusive,
-	}
+	},
+	num::{ BigRational, ToPrimitive },
+};
+
+use super::super::common::
+{
+	geometry::Vec3,
+	linalg::Matrix,
 };
 
 /// # Examples
@@ -22,6 +29,10 @@ use ::
 ///     20, 19, 15 @  1, -5, -3";
 /// assert_eq!(Solution::part1((7..=27), input.to_string()).solve().unwrap(), "2");
 /// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "47");
+///
+/// // the rock would have hit the extra hailstone a nanosecond ago, or half a nanosecond from now
+/// assert!(Solution::part2(format!("{}\n27, 12, 8 @ 0, 0, 0", input)).solve().is_err());
+/// assert!(Solution::part2(format!("{}\n23, 13, 10 @ -1, 1, 2", input)).solve().is_err());
 /// ```
 pub struct Solution
 {
@@ -52,8 +63,8 @@ enum Part
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct Projectile
 {
-	pos: [i128; 3],
-	v: [i128; 3],
+	pos: Vec3<i128>,
+	v: Vec3<i128>,
 }
 
 #[allow(unused)]
@@ -111,17 +122,19 @@ impl std::str::FromStr for Projectile
 	fn from_str(input: &str) -> std::result::Result<Self, Error>
 	{
 		let (pos, v) = input.split_once(" @ ").ok_or_else(|| anyhow!("cannot split {:?} by \" @ \"", input))?;
-		let pos: [i128; 3] = pos.split(", ")
+		let pos: Vec3<i128> = pos.split(", ")
 			.map(str::trim)
 			.map(|s| s.parse::<i128>().with_context(|| anyhow!("cannot parse {:?} as number", s)))
 			.collect::<std::result::Result<Vec<_>, _>>()?
 			.try_into()
+			.map(<[i128; 3]>::into)
 			.map_err(|v| anyhow!("could not convert {:?} to array", v))?;
-		let v: [i128; 3] = v.split(", ")
+		let v: Vec3<i128> = v.split(", ")
 			.map(str::trim)
 			.map(|s| s.parse::<i128>().with_context(|| anyhow!("cannot parse {:?} as number", s)))
 			.collect::<std::result::Result<Vec<_>, _>>()?
 			.try_into()
+			.map(<[i128; 3]>::into)
 			.map_err(|v| anyhow!("could not convert {:?} to array", v))?;
 
 		Ok(Projectile
@@ -132,6 +145,69 @@ impl std::str::FromStr for Projectile
 	}
 }
 
+/// The position and velocity of a rock hitting every hailstone.
+///
+/// Rock and hailstone meet iff `(P - p) × (V - v) = 0`, which expands to the same non-linear `P × V`
+/// for every hailstone.
+/// Subtracting the equations of two hailstones `i` and `j` cancels it out and leaves three linear
+/// equations `P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i`, two pairs determine all six
+/// unknowns.
+/// The rock still has to meet every hailstone at a whole number of nanoseconds from now.
+fn rock(projectiles: &[Projectile]) -> Result<(Vec3<i128>, Vec3<i128>)>
+{
+	let first = projectiles.first().ok_or(Error::AocNoSolution)?;
+	let (rows, rhs): (Vec<Vec<i128>>, Vec<i128>) = projectiles.iter()
+		.skip(1)
+		.take(2)
+		.flat_map(|other|
+		{
+			let w = other.v - first.v;
+			let u = other.pos - first.pos;
+			let c = other.pos.cross(other.v) - first.pos.cross(first.v);
+			vec!
+			[
+				(vec![0, w.z, -w.y, 0, -u.z, u.y], c.x),
+				(vec![-w.z, 0, w.x, u.z, 0, -u.x], c.y),
+				(vec![w.y, -w.x, 0, -u.y, u.x, 0], c.z),
+			]
+		})
+		.unzip();
+	anyhow::ensure!(rows.len() == 6, "need at least three hailstones, got {}", projectiles.len());
+
+	let rhs = rhs.into_iter().map(|c| BigRational::from_integer(c.into())).collect::<Vec<_>>();
+	let solution = Matrix::from_integers(rows)?
+		.solve(&rhs)
+		.ok_or_else(|| anyhow!("the first three hailstones do not determine the rock"))?
+		.into_iter()
+		.map(|value| value.is_integer().then(|| value.to_integer().to_i128()).flatten().ok_or_else(|| anyhow!("rock coordinate {} is not an integer", value)))
+		.collect::<Result<Vec<_>>>()?;
+	let position = Vec3::new(solution[0], solution[1], solution[2]);
+	let velocity = Vec3::new(solution[3], solution[4], solution[5]);
+
+	if let Some(miss) = projectiles.iter().find(|p| hit_time(position, velocity, p).is_none())
+	{
+		bail!("rock thrown from {:?} with velocity {:?} misses {:?}", position, velocity, miss);
+	}
+
+	Ok((position, velocity))
+}
+
+/// The time the rock meets the hailstone, if they meet at all.
+fn hit_time(position: Vec3<i128>, velocity: Vec3<i128>, hailstone: &Projectile) -> Option<i128>
+{
+	// P + tV = p + tv, so the distance p - P is t times the closing velocity V - v
+	let distance = hailstone.pos - position;
+	let closing = velocity - hailstone.v;
+	let time = match (0..3).find(|&axis| closing[axis] != 0)
+	{
+		Some(axis) if distance[axis] % closing[axis] == 0 => distance[axis] / closing[axis],
+		Some(_) => return None,
+		// moving alongside, they only meet if they start at the same spot
+		None => 0,
+	};
+	(time >= 0 && closing * time == distance).then_some(time)
+}
+
 impl super::super::Solution for Solution
 {
 	fn solve(&self) -> Result<String>
@@ -180,74 +256,9 @@ impl super::super::Solution for Solution
 			},
 			Part::Two =>
 			{
-				let mut result = None;
-				for bits in 0..14
-				{
-					let low = 1i16 << bits;
-					let high = 1i16 << (bits + 1);
-					info!("run ({}) {}..{}", bits, low, high);
-					use rayon::prelude::*;
-					result = (low..high)
-						.into_par_iter()
-						.inspect(|max| debug!("max: {}", max))
-						.flat_map_iter(|max| (-max..=max).map(move |x| (max, x)))
-						.inspect(|(_, x)| trace!("x: {}", x))
-						.flat_map_iter(|(max, x)| (-max..=max).map(move |y| (max, x, y)))
-						.inspect(|(_, x, y)| trace!("x, y: {:?}", (x, y)))
-						.flat_map_iter(|(max, x, y)| (-max..=max).map(move |z| (max, x, y, z)))
-						.filter(|&(max, x, y, z)| x.abs() >= max || y.abs() >= max || z.abs() >= max)
-						.map(|(_, x, y, z)| [x.into(), y.into(), z.into()])
-						.flat_map_iter(|v|
-						{
-							let projectiles = &projectiles;
-							projectiles.iter()
-								.map(move |p|
-								{
-									let x = p.x(1) - v[0];
-									let y = p.y(1) - v[1];
-									let z = p.z(1) - v[2];
-									Projectile
-									{
-										pos: [x, y, z],
-										v,
-									}
-								})
-						})
-						.find_any(|p|
-						{
-							projectiles.iter()
-								.all(|h|
-								{
-									let (x, y) = match p.intersect2(0, 1, h)
-									{
-										Some(point) => point,
-										None => return false,
-									};
-									//trace!("projectiles intersect at {:?}", (x, y));
-									let times = [ p.rx(x), h.rx(x), p.ry(y), h.ry(y), ];
-									if times[0].is_none()
-									{
-										return false;
-									}
-									if !times.iter().all(|&x| x == times[0])
-									{
-										return false;
-									}
-									let time = times[0].unwrap();
-									if time <= 0
-									{
-										return false;
-									}
-									p.x(time) == h.x(time) && p.y(time) == h.y(time) && p.z(time) == h.z(time)
-								})
-						})
-						.map(|p| p.pos.iter().sum::<i128>() as usize);
-					if result.is_some()
-					{
-						break;
-					}
-				}
-				result.unwrap()
+				let (position, velocity) = rock(&projectiles)?;
+				debug!("rock thrown from {:?} with velocity {:?}", position, velocity);
+				position.sum() as usize
 			},
 		};
 
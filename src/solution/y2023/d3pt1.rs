@@ -38,6 +38,7 @@ struct Cell
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum CellContent
 {
 	Number(usize),
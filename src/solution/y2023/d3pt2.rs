@@ -39,6 +39,7 @@ struct Cell
 }
 
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 enum CellContent
 {
 	Number(usize),
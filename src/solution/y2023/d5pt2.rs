@@ -2,6 +2,8 @@ use crate::error::*;
 
 use std::ops::Range;
 
+use super::super::common::interval::IntervalSet;
+
 /// # Examples
 ///
 /// ```
@@ -48,69 +50,12 @@ pub struct Solution
 	input: String,
 }
 
-type Mapping = (Range<usize>, Range<usize>);
-
 #[derive(Clone, Debug)]
 pub struct Map
 {
 	name: String,
 	// mapped source range, first target value
-	map: Vec<Mapping>,
-}
-
-impl Map
-{
-	fn translate(&self, input: usize) -> usize
-	{
-		let result = self.map.iter()
-			.find(|(range, _)| range.contains(&input))
-			.map(|(range, destination)| input - range.start + destination.start)
-			.unwrap_or(input);
-		trace!("{} translate {} to {}", self.name, input, result);
-		result
-	}
-
-	fn rtranslate(&self, input: usize) -> usize
-	{
-		let result = self.map.iter()
-			.find(|(_, destination)| destination.contains(&input))
-			.map(|(range, destination)| input - destination.start + range.start)
-			.unwrap_or(input);
-		trace!("{} rtranslate {} to {}", self.name, input, result);
-		result
-	}
-
-	fn merge(&self, other: &Map) -> Map
-	{
-		let name = format!("{}-to-{}", self.name.split_once('-').unwrap().0, other.name.rsplit_once('-').unwrap().1);
-		debug!("merging {} and {} to {}", self.name, other.name, name);
-		trace!("merge:\n{:#?}\n{:#?}", self, other);
-
-		let points = other.map.iter()
-			.flat_map(|(range, _)| [range.start, range.end, self.rtranslate(range.start), self.rtranslate(range.end)])
-			.chain(self.map.iter()
-				.flat_map(|(range, _)| [range.start, range.end])
-			)
-			.collect::<std::collections::BTreeSet<_>>();
-
-		trace!("{} merge points: {:?}", name, points);
-
-		let map = points.into_iter().collect::<Vec<_>>().windows(2)
-			.map(|slice| slice[0]..slice[1])
-			.map(|range|
-			{
-				let start = other.translate(self.translate(range.start));
-				let end = other.translate(self.translate(range.end - 1));
-				(range, start..(end + 1))
-			})
-			.collect();
-
-		Map
-		{
-			name,
-			map,
-		}
-	}
+	map: Vec<(Range<usize>, usize)>,
 }
 
 impl std::str::FromStr for Map
@@ -128,7 +73,7 @@ impl std::str::FromStr for Map
 				let destination: usize = parts.next().ok_or(Error::AocParsing)?.parse()?;
 				let start: usize = parts.next().ok_or(Error::AocParsing)?.parse()?;
 				let length: usize = parts.next().ok_or(Error::AocParsing)?.parse()?;
-				Ok((start..(start + length), destination..(destination + length)))
+				Ok((start..(start + length), destination))
 			})
 			.collect::<std::result::Result<Vec<_>, Self::Err>>()?;
 
@@ -178,31 +123,17 @@ impl super::super::Solution for Solution
 			{
 				slice[0]..(slice[0] + slice[1])
 			})
-			.collect::<Vec<_>>();
-
-		let map = maps.iter()
-			.skip(1)
-			.fold(maps.first().unwrap().clone(), |a: Map, b| a.merge(b));
+			.collect::<IntervalSet<_>>();
 
-		debug!("maps compiled, {} entries", map.map.len());
-
-		let mut map_entries = map.map.iter()
-			.collect::<Vec<_>>();
-		map_entries.sort_by_key(|(_, dest)| dest.start);
-
-		let result = map_entries.into_iter()
-			.flat_map(|(range, _)|
+		let locations = maps.iter()
+			.fold(seeds, |values, map|
 			{
-				seeds.iter()
-					.filter(move |seed|
-					{
-						range.contains(&seed.start) || range.contains(&(seed.end - 1)) || seed.contains(&range.start) || seed.contains(&(range.end - 1))
-					})
-					.flat_map(move |seed| range.start.max(seed.start)..range.end.min(seed.end))
-			})
-			.next().unwrap();
+				let values = values.map_piecewise(&map.map);
+				debug!("{}: {} ranges", map.name, values.ranges().len());
+				values
+			});
 
-		Ok(format!("{}", map.translate(result)))
+		Ok(format!("{}", locations.min().ok_or(Error::AocNoSolution)?))
 	}
 }
 
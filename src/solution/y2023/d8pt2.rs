@@ -1,5 +1,7 @@
 use crate::error::*;
 
+use super::super::common::number::first_coincidence;
+
 /// # Examples
 ///
 /// ```
@@ -53,12 +55,12 @@ impl super::super::Solution for Solution
 			.filter(|s| s.ends_with('A'))
 			.collect::<Vec<_>>();
 
-		let result: usize = start.into_iter()
+		let cycles = start.into_iter()
 			.map(|start|
 			{
 				let mut steps = steps.iter().cycle();
 
-				std::iter::successors(Some(start), |current|
+				let mut ends = std::iter::successors(Some(start), |current|
 					{
 						let step = steps.next().unwrap();
 						let directions = map.get(current.as_str()).unwrap();
@@ -71,19 +73,27 @@ impl super::super::Solution for Solution
 						trace!("at {:?}: taking step {:?} to {:?}", current, step, next);
 						Some(next)
 					})
-					.position(|next| next.ends_with('Z'))
-					.unwrap()
+					.enumerate()
+					.filter(|(_, next)| next.ends_with('Z'))
+					.map(|(idx, _)| idx as i128);
+				let first = ends.next().unwrap();
+				let second = ends.next().unwrap();
+				debug!("{} reaches an end after {} and then every {} steps", start, first, second - first);
+				(first, second - first)
 			})
-			/*
-			 * Note: this only works because of several criteria none of which I've seen stated in the AoC text (but I'm a terrible reader):
-			 *   - every single start will only ever reach a single end, their paths will never cross
-			 *   - the distance between every start and its corresponding end is the same as from the end to the end again (i.e. looping with the same offset)
-			 *   - step distances sync up perfectly with the looping, therefore you won't have a miraculous disparity as soon as the LCM exceeds the give step count
-			 * Honestly I wouldn't have solved this if someone else didn't tell me that this works because I saw those three criterias as game breaking.
-			 * But hey.
-			 * So be it.
-			 */
-			.fold(1, num::integer::lcm);
+			.collect::<Vec<_>>();
+
+		/*
+		 * Note: this only works because of several criteria none of which I've seen stated in the AoC text (but I'm a terrible reader):
+		 *   - every single start will only ever reach a single end, their paths will never cross
+		 *   - the distance between every start and its corresponding end is the same as from the end to the end again (i.e. looping with the same offset)
+		 *   - step distances sync up perfectly with the looping, therefore you won't have a miraculous disparity as soon as the LCM exceeds the give step count
+		 * Honestly I wouldn't have solved this if someone else didn't tell me that this works because I saw those three criterias as game breaking.
+		 * But hey.
+		 * So be it.
+		 * The offset differing from the loop length is now handled by `first_coincidence`.
+		 */
+		let result = first_coincidence(cycles).ok_or(Error::AocNoSolution)?;
 
 		Ok(format!("{}", result))
 	}
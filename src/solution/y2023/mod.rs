@@ -42,6 +42,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 	enum InputType<'a>
 	{
 		UrlInput(Box<dyn Fn(Option<String>) -> Box<dyn super::Solution + 'static>>),
+		UrlInputArgs(Vec<clap::Arg>, Box<dyn Fn(Option<String>, &clap::ArgMatches) -> Box<dyn super::Solution + 'static>>),
 		Parser(Box<dyn super::InputParser<'a>>),
 	}
 
@@ -83,14 +84,14 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d17::Solution::with_min_max(4, 10, input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part2(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part1(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part2(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::part1(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(d19::Solution::args(), Box::new(|input, matches| Box::new(d19::Solution::part1(input.expect("empty input received")).range(matches.get_one::<std::ops::RangeInclusive<usize>>("range").unwrap().clone()).queries(matches.get_many::<String>("query").into_iter().flatten().cloned().collect()).boxes(matches.get_flag("boxes")))))),
+		Some(InputType::UrlInputArgs(d19::Solution::args(), Box::new(|input, matches| Box::new(d19::Solution::part2(input.expect("empty input received")).range(matches.get_one::<std::ops::RangeInclusive<usize>>("range").unwrap().clone()).queries(matches.get_many::<String>("query").into_iter().flatten().cloned().collect()).boxes(matches.get_flag("boxes")))))),
+		Some(InputType::UrlInputArgs(d20::Solution::args(), Box::new(|input, matches| Box::new(d20::Solution::part1(input.expect("empty input received")).dot(matches.get_flag("dot")))))),
+		Some(InputType::UrlInputArgs(d20::Solution::args(), Box::new(|input, matches| Box::new(d20::Solution::part2(input.expect("empty input received")).dot(matches.get_flag("dot")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::with_steps(64, input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::with_steps(26501365, input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part1(input.expect("empty input received")))))),
-		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInputArgs(d22::Solution::args(), Box::new(|input, matches| Box::new(d22::Solution::part1(input.expect("empty input received")).chain(matches.get_flag("chain")))))),
+		Some(InputType::UrlInputArgs(d22::Solution::args(), Box::new(|input, matches| Box::new(d22::Solution::part2(input.expect("empty input received")).chain(matches.get_flag("chain")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part1(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part2(input.expect("empty input received")))))),
 		Some(InputType::UrlInput(Box::new(|input| Box::new(d24::Solution::part1(200000000000000..=400000000000000, input.expect("empty input received")))))),
@@ -105,6 +106,7 @@ pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
 			match parser
 			{
 				InputType::UrlInput(parser) => Box::new((2023usize,idx/2+1,idx%2+1,parser)) as Box<dyn super::InputParser>,
+				InputType::UrlInputArgs(args, parser) => Box::new((2023usize,idx/2+1,idx%2+1,args,parser)) as Box<dyn super::InputParser>,
 				InputType::Parser(parser) => parser,
 			}
 		)
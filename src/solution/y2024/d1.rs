@@ -0,0 +1,88 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d1::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     3   4\n\
+///     4   3\n\
+///     2   5\n\
+///     1   3\n\
+///     3   9\n\
+///     3   3";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "11");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "31");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (mut left, mut right): (Vec<usize>, Vec<usize>) = self.input.lines()
+			.map(|line|
+			{
+				let mut split = line.split_whitespace();
+				let left = split.next().ok_or(Error::AocParsing).context("missing left location id")?.parse::<usize>()?;
+				let right = split.next().ok_or(Error::AocParsing).context("missing right location id")?.parse::<usize>()?;
+				Ok((left, right))
+			})
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.unzip();
+
+		let result: usize = match self.part
+		{
+			Part::One =>
+			{
+				left.sort_unstable();
+				right.sort_unstable();
+				left.into_iter()
+					.zip(right)
+					.map(|(left, right)| left.abs_diff(right))
+					.sum()
+			},
+			Part::Two =>
+			{
+				let mut counts = std::collections::BTreeMap::<usize, usize>::new();
+				for id in right
+				{
+					*counts.entry(id).or_default() += 1;
+				}
+				left.into_iter()
+					.map(|id| id * counts.get(&id).copied().unwrap_or(0))
+					.sum()
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
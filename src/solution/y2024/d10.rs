@@ -0,0 +1,95 @@
+use crate::error::*;
+
+use super::super::common::grid::{ Grid, Position };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d10::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     89010123\n\
+///     78121874\n\
+///     87430965\n\
+///     96549874\n\
+///     45678903\n\
+///     32019012\n\
+///     01329801\n\
+///     10456732";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "36");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "81");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let map = Grid::from_rows(self.input.lines()
+			.map(|line| line.chars().map(|ch| ch.to_digit(10).ok_or(Error::AocParsing).context("invalid height")).collect::<Result<Vec<_>>>())
+			.collect::<Result<Vec<_>>>()?)?;
+
+		// number of distinct trails from each position to every reachable peak
+		let mut trails: std::collections::BTreeMap<Position, std::collections::BTreeMap<Position, usize>> = Default::default();
+		for height in (0..=9).rev()
+		{
+			for (position, _) in map.iter().filter(|&(_, &h)| h == height)
+			{
+				let mut peaks = std::collections::BTreeMap::new();
+				if height == 9
+				{
+					peaks.insert(position, 1);
+				}
+				for (_, next) in map.neighbours(position).filter(|&(_, next)| map[next] == height + 1)
+				{
+					for (&peak, &count) in trails[&next].iter()
+					{
+						*peaks.entry(peak).or_insert(0) += count;
+					}
+				}
+				trails.insert(position, peaks);
+			}
+		}
+
+		let result: usize = map.iter()
+			.filter(|&(_, &height)| height == 0)
+			.map(|(position, _)|
+			{
+				let peaks = &trails[&position];
+				match self.part
+				{
+					Part::One => peaks.len(),
+					Part::Two => peaks.values().sum(),
+				}
+			})
+			.sum();
+
+		Ok(format!("{}", result))
+	}
+}
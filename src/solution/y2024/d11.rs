@@ -0,0 +1,74 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d11::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "125 17";
+/// assert_eq!(Solution::with_blinks(6, input.to_string()).solve().unwrap(), "22");
+/// assert_eq!(Solution::with_blinks(25, input.to_string()).solve().unwrap(), "55312");
+/// ```
+pub struct Solution
+{
+	input: String,
+	blinks: usize,
+}
+
+impl Solution
+{
+	pub fn with_blinks(blinks: usize, input: String) -> Self
+	{
+		Self { blinks, input, }
+	}
+}
+
+fn blink(stone: u64) -> Vec<u64>
+{
+	if stone == 0
+	{
+		return vec![1];
+	}
+	let digits = stone.ilog10() + 1;
+	if digits.is_multiple_of(2)
+	{
+		let magnitude = 10u64.pow(digits / 2);
+		vec![stone / magnitude, stone % magnitude]
+	}
+	else
+	{
+		vec![stone * 2024]
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		// the order of the stones never matters, only how many of each there are
+		let mut stones = std::collections::HashMap::<u64, usize>::new();
+		for stone in self.input.split_whitespace()
+		{
+			*stones.entry(stone.parse()?).or_default() += 1;
+		}
+
+		for _ in 0..self.blinks
+		{
+			let mut next = std::collections::HashMap::with_capacity(stones.len());
+			for (stone, count) in stones
+			{
+				for stone in blink(stone)
+				{
+					*next.entry(stone).or_default() += count;
+				}
+			}
+			stones = next;
+		}
+
+		let result: usize = stones.values().sum();
+
+		Ok(format!("{}", result))
+	}
+}
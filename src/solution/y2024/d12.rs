@@ -0,0 +1,136 @@
+use crate::error::*;
+
+use super::super::common::grid::{ Direction, Grid, Position };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d12::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     AAAA\n\
+///     BBCD\n\
+///     BBCC\n\
+///     EEEC";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "140");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "80");
+/// let input = "\
+///     OOOOO\n\
+///     OXOXO\n\
+///     OOOOO\n\
+///     OXOXO\n\
+///     OOOOO";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "772");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "436");
+/// let input = "\
+///     EEEEE\n\
+///     EXXXX\n\
+///     EEEEE\n\
+///     EXXXX\n\
+///     EEEEE";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "236");
+/// let input = "\
+///     AAAAAA\n\
+///     AAABBA\n\
+///     AAABBA\n\
+///     ABBAAA\n\
+///     ABBAAA\n\
+///     AAAAAA";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "368");
+/// let input = "\
+///     RRRRIICCFF\n\
+///     RRRRIICCCF\n\
+///     VVRRRCCFFF\n\
+///     VVRCCCJFFF\n\
+///     VVVVCJJCFE\n\
+///     VVIVCCJJEE\n\
+///     VVIIICJJEE\n\
+///     MIIIIIJJEE\n\
+///     MIIISIJEEE\n\
+///     MMMISSJEEE";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "1930");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "1206");
+/// ```
+pub struct Solution
+{
+	input: String,
+	bulk: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { bulk: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { bulk: true, input, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let map = self.input.parse::<Grid<char>>()?;
+
+		let mut seen = std::collections::BTreeSet::new();
+		let mut result = 0;
+		for (start, &plant) in map.iter()
+		{
+			if !seen.insert(start)
+			{
+				continue;
+			}
+
+			let mut region = std::collections::BTreeSet::new();
+			let mut queue = vec![start];
+			while let Some(position) = queue.pop()
+			{
+				region.insert(position);
+				for (_, next) in map.neighbours(position)
+				{
+					if map[next] == plant && seen.insert(next)
+					{
+						queue.push(next);
+					}
+				}
+			}
+
+			let inside = |position: Option<Position>| position.map(|position| region.contains(&position)).unwrap_or(false);
+			let fences: usize = if self.bulk
+			{
+				// a region has as many sides as it has corners
+				region.iter()
+					.map(|&position|
+					{
+						Direction::slice().iter()
+							.filter(|&&dir|
+							{
+								let (a, b) = (position + dir, position + dir.right());
+								let diagonal = a.and_then(|a| a + dir.right());
+								let (a, b, diagonal) = (inside(a), inside(b), inside(diagonal));
+								(!a && !b) || (a && b && !diagonal)
+							})
+							.count()
+					})
+					.sum()
+			}
+			else
+			{
+				region.iter()
+					.map(|&position| Direction::slice().iter().filter(|&&dir| !inside(position + dir)).count())
+					.sum()
+			};
+
+			trace!("region of {} with area {} and {} fences", plant, region.len(), fences);
+			result += region.len() * fences;
+		}
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,91 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d13::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     Button A: X+94, Y+34\n\
+///     Button B: X+22, Y+67\n\
+///     Prize: X=8400, Y=5400\n\
+///     \n\
+///     Button A: X+26, Y+66\n\
+///     Button B: X+67, Y+21\n\
+///     Prize: X=12748, Y=12176\n\
+///     \n\
+///     Button A: X+17, Y+86\n\
+///     Button B: X+84, Y+37\n\
+///     Prize: X=7870, Y=6450\n\
+///     \n\
+///     Button A: X+69, Y+23\n\
+///     Button B: X+27, Y+71\n\
+///     Prize: X=18641, Y=10279";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "480");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "875318608908");
+/// ```
+pub struct Solution
+{
+	input: String,
+	offset: i64,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { offset: 0, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { offset: 10000000000000, input, }
+	}
+}
+
+lazy_static::lazy_static!
+{
+	static ref MACHINE: regex::Regex = regex::Regex::new(r"Button A: X\+(\d+), Y\+(\d+)\s+Button B: X\+(\d+), Y\+(\d+)\s+Prize: X=(\d+), Y=(\d+)").unwrap();
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let machines = MACHINE.captures_iter(&self.input)
+			.map(|captures|
+			{
+				(1..=6)
+					.map(|idx| Ok(captures[idx].parse::<i64>()?))
+					.collect::<Result<Vec<_>>>()
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let result: i64 = machines.into_iter()
+			.filter_map(|machine|
+			{
+				let (ax, ay, bx, by) = (machine[0], machine[1], machine[2], machine[3]);
+				let (px, py) = (machine[4] + self.offset, machine[5] + self.offset);
+				// Cramer's rule, the button vectors are never collinear in the inputs
+				let determinant = ax * by - ay * bx;
+				if determinant == 0
+				{
+					warn!("collinear buttons in {:?}", machine);
+					return None;
+				}
+				let a = px * by - py * bx;
+				let b = ax * py - ay * px;
+				if a % determinant != 0 || b % determinant != 0
+				{
+					return None;
+				}
+				let (a, b) = (a / determinant, b / determinant);
+				(a >= 0 && b >= 0).then_some(3 * a + b)
+			})
+			.sum();
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,113 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d14::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     p=0,4 v=3,-3\n\
+///     p=6,3 v=-1,-3\n\
+///     p=10,3 v=-1,2\n\
+///     p=2,0 v=2,-1\n\
+///     p=0,0 v=1,3\n\
+///     p=3,0 v=-2,-2\n\
+///     p=7,6 v=-1,-3\n\
+///     p=3,0 v=-1,-2\n\
+///     p=9,3 v=2,3\n\
+///     p=7,3 v=-1,2\n\
+///     p=2,4 v=2,-3\n\
+///     p=9,5 v=-3,-3";
+/// assert_eq!(Solution::part1(11, 7, input.to_string()).solve().unwrap(), "12");
+/// ```
+pub struct Solution
+{
+	input: String,
+	width: i64,
+	height: i64,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(width: i64, height: i64, input: String) -> Self
+	{
+		Self { part: Part::One, width, height, input, }
+	}
+
+	pub fn part2(width: i64, height: i64, input: String) -> Self
+	{
+		Self { part: Part::Two, width, height, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+lazy_static::lazy_static!
+{
+	static ref ROBOT: regex::Regex = regex::Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
+}
+
+/// Variance of the coordinates (scaled by the cube of their count), the robots cluster together
+/// when forming the picture.
+fn variance<I: Iterator<Item=i64> + Clone>(values: I) -> i64
+{
+	let count = values.clone().count() as i64;
+	let sum: i64 = values.clone().sum();
+	values.map(|value| (value * count - sum).pow(2)).sum()
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let robots = self.input.lines()
+			.map(|line|
+			{
+				let captures = ROBOT.captures(line).ok_or(Error::AocParsing).with_context(|| format!("invalid robot {:?}", line))?;
+				Ok(((captures[1].parse::<i64>()?, captures[2].parse::<i64>()?), (captures[3].parse::<i64>()?, captures[4].parse::<i64>()?)))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let (width, height) = (self.width, self.height);
+		let x_at = |seconds: i64| robots.iter().map(move |((x, _), (dx, _))| (x + dx * seconds).rem_euclid(width));
+		let y_at = |seconds: i64| robots.iter().map(move |((_, y), (_, dy))| (y + dy * seconds).rem_euclid(height));
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				let mut quadrants = [0; 4];
+				for (x, y) in x_at(100).zip(y_at(100))
+				{
+					if x == width / 2 || y == height / 2
+					{
+						continue;
+					}
+					quadrants[(x > width / 2) as usize + 2 * (y > height / 2) as usize] += 1;
+				}
+				quadrants.iter().product::<i64>()
+			},
+			Part::Two =>
+			{
+				// horizontal and vertical positions repeat independently, find the tightest cluster of each
+				let x_offset = (0..width).min_by_key(|&seconds| variance(x_at(seconds))).ok_or(Error::AocNoSolution)?;
+				let y_offset = (0..height).min_by_key(|&seconds| variance(y_at(seconds))).ok_or(Error::AocNoSolution)?;
+				debug!("x clusters after {}, y clusters after {}", x_offset, y_offset);
+				(0..height)
+					.map(|n| x_offset + n * width)
+					.find(|seconds| seconds % height == y_offset)
+					.ok_or(Error::AocNoSolution)?
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,166 @@
+use crate::error::*;
+
+use super::super::common::grid::{ Direction, Grid, Position };
+
+use std::convert::TryFrom;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d15::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     #########\n\
+///     ##..O.O.#\n\
+///     ###@.O..#\n\
+///     ##...O..#\n\
+///     ##.#.O..#\n\
+///     ##...O..#\n\
+///     ##......#\n\
+///     #########\n\
+///     \n\
+///     <^^>>>vv<v>>v<<";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "2028");
+/// let input = "\
+///     ########\n\
+///     ##...#.#\n\
+///     ##.....#\n\
+///     ##..OO@#\n\
+///     ##..O..#\n\
+///     ##.....#\n\
+///     ########\n\
+///     \n\
+///     <vv<<^^<<^^";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "618");
+/// ```
+pub struct Solution
+{
+	input: String,
+	wide: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { wide: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { wide: true, input, }
+	}
+}
+
+struct Warehouse
+{
+	map: Grid<char>,
+	robot: Position,
+}
+
+impl Warehouse
+{
+	/// Moves the robot, pushing all boxes in the way if none of them is blocked by a wall.
+	fn push(&mut self, direction: Direction)
+	{
+		// the warehouse is surrounded by walls, so the robot never leaves it
+		let step = |position: Position| (position + direction).unwrap();
+		let mut moving = vec![self.robot];
+		let mut idx = 0;
+		while let Some(&position) = moving.get(idx)
+		{
+			idx += 1;
+			let next = step(position);
+			if moving.contains(&next)
+			{
+				continue;
+			}
+			match self.map[next]
+			{
+				'#' => return,
+				'O' => moving.push(next),
+				'[' =>
+				{
+					moving.push(next);
+					moving.push((next.0 + 1, next.1));
+				},
+				']' =>
+				{
+					moving.push(next);
+					moving.push((next.0 - 1, next.1));
+				},
+				_ => {},
+			}
+		}
+
+		let tiles = moving.iter().map(|&position| self.map[position]).collect::<Vec<_>>();
+		for &position in moving.iter()
+		{
+			self.map[position] = '.';
+		}
+		for (&position, tile) in moving.iter().zip(tiles)
+		{
+			self.map[step(position)] = tile;
+		}
+		self.robot = step(self.robot);
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (map, moves) = self.input.split_once("\n\n").ok_or(Error::AocParsing).context("cannot find moves")?;
+
+		let map = map.lines()
+			.map(|line|
+			{
+				if self.wide
+				{
+					line.chars()
+						.flat_map(|ch|
+						{
+							match ch
+							{
+								'O' => ['[', ']'],
+								'@' => ['@', '.'],
+								ch => [ch, ch],
+							}
+						})
+						.collect::<Vec<_>>()
+				}
+				else
+				{
+					line.chars().collect()
+				}
+			})
+			.collect::<Vec<_>>();
+		let map = Grid::from_rows(map).context(Error::AocParsing)?;
+
+		let robot = map.find(|&ch| ch == '@')
+			.ok_or(Error::AocParsing)
+			.context("no robot in warehouse")?;
+
+		let mut warehouse = Warehouse { map, robot, };
+		for ch in moves.chars().filter(|ch| !ch.is_whitespace())
+		{
+			let direction = match ch
+			{
+				'^' | 'v' | '<' | '>' => Direction::try_from(ch)?,
+				ch => bail!("unknown move {:?}", ch),
+			};
+			warehouse.push(direction);
+		}
+
+		trace!("final warehouse:\n{}", warehouse.map);
+
+		let result: usize = warehouse.map.iter()
+			.filter(|&(_, &ch)| ch == 'O' || ch == '[')
+			.map(|((x, y), _)| 100 * y + x)
+			.sum();
+
+		Ok(format!("{}", result))
+	}
+}
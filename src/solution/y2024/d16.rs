@@ -0,0 +1,134 @@
+use crate::error::*;
+
+use super::super::common::
+{
+	grid::{ Direction, Grid, Position },
+	search::{ dijkstra_all, Explored },
+};
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d16::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     ################\n\
+///     ##.......#....E#\n\
+///     ##.#.###.#.###.#\n\
+///     ##.....#.#...#.#\n\
+///     ##.###.#####.#.#\n\
+///     ##.#.#.......#.#\n\
+///     ##.#.#####.###.#\n\
+///     ##...........#.#\n\
+///     ####.#.#####.#.#\n\
+///     ##...#.....#.#.#\n\
+///     ##.#.#.###.#.#.#\n\
+///     ##.....#...#.#.#\n\
+///     ##.###.#.#.#.#.#\n\
+///     ##S..#.....#...#\n\
+///     ################";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "7036");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "45");
+/// let input = "\
+///     ##################\n\
+///     ##...#...#...#..E#\n\
+///     ##.#.#.#.#.#.#.#.#\n\
+///     ##.#.#.#...#...#.#\n\
+///     ##.#.#.#.###.#.#.#\n\
+///     ##...#.#.#.....#.#\n\
+///     ##.#.#.#.#.#####.#\n\
+///     ##.#...#.#.#.....#\n\
+///     ##.#.#####.#.###.#\n\
+///     ##.#.#.......#...#\n\
+///     ##.#.###.#####.###\n\
+///     ##.#.#...#.....#.#\n\
+///     ##.#.#.#####.###.#\n\
+///     ##.#.#.........#.#\n\
+///     ##.#.#.#########.#\n\
+///     ##S#.............#\n\
+///     ##################";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "11048");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "64");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+type State = (Position, Direction);
+
+fn dijkstra(maze: &Grid<char>, starts: &[State]) -> Explored<State>
+{
+	dijkstra_all(starts.iter().copied(), |&(position, direction): &State|
+	{
+		maze.step(position, direction)
+			.filter(|&forward| maze[forward] != '#')
+			.map(|forward| ((forward, direction), 1))
+			.into_iter()
+			.chain(vec![((position, direction.left()), 1000), ((position, direction.right()), 1000)])
+	})
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let maze: Grid<char> = self.input.parse()?;
+		if let Some(ch) = maze.iter().map(|(_, &ch)| ch).find(|ch| !"#.SE".contains(*ch))
+		{
+			bail!("unknown maze tile {:?}", ch);
+		}
+		let start = maze.find(|&ch| ch == 'S').ok_or(Error::AocParsing).context("no start tile")?;
+		let end = maze.find(|&ch| ch == 'E').ok_or(Error::AocParsing).context("no end tile")?;
+
+		let from_start = dijkstra(&maze, &[(start, Direction::Right)]);
+		let best = Direction::slice().iter()
+			.filter_map(|&direction| from_start.cost(&(end, direction)))
+			.min()
+			.ok_or(Error::AocNoSolution)?;
+
+		let result = match self.part
+		{
+			Part::One => best,
+			Part::Two =>
+			{
+				// walking backwards from the end, a state is on a best path if both halves add up
+				let ends = Direction::slice().iter().map(|&direction| (end, direction.invert())).collect::<Vec<_>>();
+				let from_end = dijkstra(&maze, &ends);
+				from_start.iter()
+					.filter(|&(&(position, direction), cost)|
+					{
+						from_end.cost(&(position, direction.invert())).map(|rest| cost + rest == best).unwrap_or(false)
+					})
+					.map(|(&(position, _), _)| position)
+					.collect::<std::collections::BTreeSet<_>>()
+					.len()
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,150 @@
+use crate::error::*;
+
+use std::convert::TryInto;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d17::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     Register A: 729\n\
+///     Register B: 0\n\
+///     Register C: 0\n\
+///     \n\
+///     Program: 0,1,5,4,3,0";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "4,6,3,5,6,3,5,2,1,0");
+/// let input = "\
+///     Register A: 2024\n\
+///     Register B: 0\n\
+///     Register C: 0\n\
+///     \n\
+///     Program: 0,3,5,4,3,0";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "117440");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+fn run(program: &[u64], [mut a, mut b, mut c]: [u64; 3]) -> Result<Vec<u64>>
+{
+	let mut output = Vec::new();
+	let mut ip = 0;
+	while let (Some(&opcode), Some(&operand)) = (program.get(ip), program.get(ip + 1))
+	{
+		let combo = match operand
+		{
+			0..=3 => Ok(operand),
+			4 => Ok(a),
+			5 => Ok(b),
+			6 => Ok(c),
+			_ => Err(anyhow!("invalid combo operand {} at {}", operand, ip)),
+		};
+		ip += 2;
+		match opcode
+		{
+			0 => a >>= combo?.min(63),
+			1 => b ^= operand,
+			2 => b = combo? % 8,
+			3 => if a != 0 { ip = operand as usize },
+			4 => b ^= c,
+			5 => output.push(combo? % 8),
+			6 => b = a >> combo?.min(63),
+			7 => c = a >> combo?.min(63),
+			_ => bail!("invalid opcode {}", opcode),
+		}
+	}
+	Ok(output)
+}
+
+/// Builds the initial value of register A three bits at a time, starting with the last output.
+///
+/// This relies on the program being a single loop which shifts A by three bits per iteration and
+/// derives each output from the bits of A only.
+fn search(program: &[u64], prefix: u64, digits: usize) -> Result<Option<u64>>
+{
+	if digits > program.len()
+	{
+		return Ok(Some(prefix));
+	}
+	for bits in 0..8
+	{
+		let a = prefix << 3 | bits;
+		if a == 0
+		{
+			continue;
+		}
+		if run(program, [a, 0, 0])? == program[program.len() - digits..]
+		{
+			if let Some(a) = search(program, a, digits + 1)?
+			{
+				return Ok(Some(a));
+			}
+		}
+	}
+	Ok(None)
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (registers, program) = self.input.split_once("\n\n").ok_or(Error::AocParsing).context("cannot find program")?;
+		let registers = registers.lines()
+			.map(|line|
+			{
+				let (_, value) = line.split_once(": ").ok_or(Error::AocParsing).context("invalid register")?;
+				Ok(value.parse::<u64>()?)
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let registers: [u64; 3] = registers.try_into().map_err(|_| Error::AocParsing).context("expected three registers")?;
+		let program = program.trim()
+			.strip_prefix("Program: ")
+			.ok_or(Error::AocParsing)
+			.context("cannot find program")?
+			.split(',')
+			.map(|s| Ok(s.parse::<u64>()?))
+			.collect::<Result<Vec<_>>>()?;
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				run(&program, registers)?.into_iter()
+					.map(|value| value.to_string())
+					.collect::<Vec<_>>()
+					.join(",")
+			},
+			Part::Two =>
+			{
+				search(&program, 0, 1)?.ok_or(Error::AocNoSolution)?.to_string()
+			},
+		};
+
+		Ok(result)
+	}
+}
@@ -0,0 +1,103 @@
+use crate::error::*;
+
+use super::super::common::
+{
+	grid::{ Grid, Position },
+	search::bfs,
+};
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d18::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n\
+///     5,5\n2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0";
+/// assert_eq!(Solution::part1(6, 12, input.to_string()).solve().unwrap(), "22");
+/// assert_eq!(Solution::part2(6, input.to_string()).solve().unwrap(), "6,1");
+/// ```
+pub struct Solution
+{
+	input: String,
+	size: usize,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(size: usize, bytes: usize, input: String) -> Self
+	{
+		Self { part: Part::One(bytes), size, input, }
+	}
+
+	pub fn part2(size: usize, input: String) -> Self
+	{
+		Self { part: Part::Two, size, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One(usize),
+	Two,
+}
+
+fn shortest(size: usize, corrupted: &std::collections::BTreeSet<Position>) -> Option<usize>
+{
+	let memory = Grid::from_fn(size + 1, size + 1, |position| corrupted.contains(&position));
+	let path = bfs([(0, 0)], |&position| memory.neighbours(position).map(|(_, next)| next).filter(|&next| !memory[next]), |&position| position == (size, size))?;
+	Some(path.cost)
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let bytes = self.input.lines()
+			.map(|line|
+			{
+				let (x, y) = line.split_once(',').ok_or(Error::AocParsing).context("invalid byte position")?;
+				Ok((x.parse::<usize>()?, y.parse::<usize>()?))
+			})
+			.collect::<Result<Vec<Position>>>()?;
+
+		let result = match self.part
+		{
+			Part::One(count) =>
+			{
+				let corrupted = bytes.iter().copied().take(count).collect();
+				shortest(self.size, &corrupted).ok_or(Error::AocNoSolution)?.to_string()
+			},
+			Part::Two =>
+			{
+				// binary search for the first number of fallen bytes blocking the exit
+				let blocked = |count: usize| shortest(self.size, &bytes.iter().copied().take(count).collect()).is_none();
+				let (mut low, mut high) = (0, bytes.len());
+				if !blocked(high)
+				{
+					bail!(Error::AocNoSolution);
+				}
+				while low + 1 < high
+				{
+					let mid = (low + high) / 2;
+					if blocked(mid)
+					{
+						high = mid;
+					}
+					else
+					{
+						low = mid;
+					}
+				}
+				let (x, y) = bytes[high - 1];
+				format!("{},{}", x, y)
+			},
+		};
+
+		Ok(result)
+	}
+}
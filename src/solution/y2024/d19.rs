@@ -0,0 +1,92 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d19::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     r, wr, b, g, bwu, rb, gb, br\n\
+///     \n\
+///     brwrr\n\
+///     bggr\n\
+///     gbbr\n\
+///     rrbgbr\n\
+///     ubwu\n\
+///     bwurrg\n\
+///     brgr\n\
+///     bbrgwb";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "6");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "16");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+/// Number of ways to build the design from the towels.
+fn arrangements(design: &str, towels: &[&str]) -> usize
+{
+	// ways[idx] counts the arrangements of the first idx stripes
+	let mut ways = vec![0; design.len() + 1];
+	ways[0] = 1;
+	for idx in 0..design.len()
+	{
+		if ways[idx] == 0
+		{
+			continue;
+		}
+		for towel in towels.iter()
+		{
+			if design[idx..].starts_with(towel)
+			{
+				ways[idx + towel.len()] += ways[idx];
+			}
+		}
+	}
+	ways[design.len()]
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (towels, designs) = self.input.split_once("\n\n").ok_or(Error::AocParsing).context("cannot find designs")?;
+		let towels = towels.split(", ").map(str::trim).collect::<Vec<_>>();
+
+		let counts = designs.lines()
+			.map(|design| arrangements(design, &towels))
+			.inspect(|count| trace!("arrangements: {}", count));
+
+		let result: usize = match self.part
+		{
+			Part::One => counts.filter(|&count| count > 0).count(),
+			Part::Two => counts.sum(),
+		};
+
+		Ok(format!("{}", result))
+	}
+}
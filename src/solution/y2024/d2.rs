@@ -0,0 +1,79 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d2::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     7 6 4 2 1\n\
+///     1 2 7 8 9\n\
+///     9 7 6 2 1\n\
+///     1 3 2 4 5\n\
+///     8 6 4 4 1\n\
+///     1 3 6 7 9";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "2");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "4");
+/// ```
+pub struct Solution
+{
+	input: String,
+	dampener: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { dampener: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { dampener: true, input, }
+	}
+}
+
+fn is_safe(report: &[isize]) -> bool
+{
+	let increasing = report.windows(2).all(|w| (1..=3).contains(&(w[1] - w[0])));
+	let decreasing = report.windows(2).all(|w| (1..=3).contains(&(w[0] - w[1])));
+	increasing || decreasing
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let reports = self.input.lines()
+			.map(|line|
+			{
+				line.split_whitespace()
+					.map(|s| Ok(s.parse::<isize>()?))
+					.collect::<Result<Vec<isize>>>()
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let result = reports.iter()
+			.filter(|report|
+			{
+				if is_safe(report)
+				{
+					return true;
+				}
+				self.dampener && (0..report.len())
+					.any(|skip|
+					{
+						let mut report = report.to_vec();
+						report.remove(skip);
+						is_safe(&report)
+					})
+			})
+			.inspect(|report| trace!("safe: {:?}", report))
+			.count();
+
+		Ok(format!("{}", result))
+	}
+}
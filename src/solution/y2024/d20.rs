@@ -0,0 +1,100 @@
+use crate::error::*;
+
+use super::super::common::grid::{ offset, Grid };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d20::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     ################\n\
+///     ##...#...#.....#\n\
+///     ##.#.#.#.#.###.#\n\
+///     ##S#...#.#.#...#\n\
+///     ########.#.#.###\n\
+///     ########.#.#...#\n\
+///     ########.#.###.#\n\
+///     ####..E#...#...#\n\
+///     ####.#######.###\n\
+///     ##...###...#...#\n\
+///     ##.#####.#.###.#\n\
+///     ##.#...#.#.#...#\n\
+///     ##.#.#.#.#.#.###\n\
+///     ##...#...#...###\n\
+///     ################";
+/// assert_eq!(Solution::with_cheat(2, 64, input.to_string()).solve().unwrap(), "1");
+/// assert_eq!(Solution::with_cheat(2, 40, input.to_string()).solve().unwrap(), "2");
+/// assert_eq!(Solution::with_cheat(2, 20, input.to_string()).solve().unwrap(), "5");
+/// assert_eq!(Solution::with_cheat(20, 76, input.to_string()).solve().unwrap(), "3");
+/// assert_eq!(Solution::with_cheat(20, 74, input.to_string()).solve().unwrap(), "7");
+/// assert_eq!(Solution::with_cheat(20, 72, input.to_string()).solve().unwrap(), "29");
+/// ```
+pub struct Solution
+{
+	input: String,
+	cheat: usize,
+	threshold: usize,
+}
+
+impl Solution
+{
+	pub fn with_cheat(cheat: usize, threshold: usize, input: String) -> Self
+	{
+		Self { cheat, threshold, input, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let map = self.input.parse::<Grid<char>>()?;
+		if let Some((_, ch)) = map.iter().find(|(_, ch)| !"#SE.".contains(**ch))
+		{
+			bail!("unknown track tile {:?}", ch);
+		}
+		let start = map.find(|&ch| ch == 'S').ok_or(Error::AocParsing).context("no start tile")?;
+
+		// distance of every track position from the start
+		let mut distances = std::collections::BTreeMap::new();
+		let mut queue = std::collections::VecDeque::new();
+		distances.insert(start, 0);
+		queue.push_back(start);
+		while let Some(position) = queue.pop_front()
+		{
+			let distance = distances[&position];
+			for (_, next) in map.neighbours(position)
+			{
+				if map[next] != '#' && !distances.contains_key(&next)
+				{
+					distances.insert(next, distance + 1);
+					queue.push_back(next);
+				}
+			}
+		}
+
+		let cheat = self.cheat as isize;
+		let result = distances.iter()
+			.map(|(&position, &from)|
+			{
+				(-cheat..=cheat)
+					.flat_map(|dy| (-cheat..=cheat).map(move |dx| (dx, dy)))
+					.filter(|(dx, dy)| dx.abs() + dy.abs() <= cheat)
+					.filter(|&(dx, dy)|
+					{
+						let length = (dx.abs() + dy.abs()) as usize;
+						offset(position, (dx, dy))
+							.and_then(|to| distances.get(&to))
+							.map(|&to| to >= from + length + self.threshold)
+							.unwrap_or(false)
+					})
+					.count()
+			})
+			.sum::<usize>();
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,141 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d21::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     029A\n\
+///     980A\n\
+///     179A\n\
+///     456A\n\
+///     379A";
+/// assert_eq!(Solution::with_robots(2, input.to_string()).solve().unwrap(), "126384");
+/// assert_eq!(Solution::with_robots(25, input.to_string()).solve().unwrap(), "154115708116294");
+/// ```
+pub struct Solution
+{
+	input: String,
+	robots: usize,
+}
+
+impl Solution
+{
+	pub fn with_robots(robots: usize, input: String) -> Self
+	{
+		Self { robots, input, }
+	}
+}
+
+type Position = (isize, isize);
+
+const NUMERIC: [(char, Position); 11] =
+[
+	('7', (0, 0)), ('8', (1, 0)), ('9', (2, 0)),
+	('4', (0, 1)), ('5', (1, 1)), ('6', (2, 1)),
+	('1', (0, 2)), ('2', (1, 2)), ('3', (2, 2)),
+	('0', (1, 3)), ('A', (2, 3)),
+];
+const NUMERIC_GAP: Position = (0, 3);
+
+const DIRECTIONAL: [(char, Position); 5] =
+[
+	('^', (1, 0)), ('A', (2, 0)),
+	('<', (0, 1)), ('v', (1, 1)), ('>', (2, 1)),
+];
+const DIRECTIONAL_GAP: Position = (0, 0);
+
+fn find(keypad: &[(char, Position)], key: char) -> Result<Position>
+{
+	keypad.iter()
+		.find(|&&(k, _)| k == key)
+		.map(|&(_, position)| position)
+		.ok_or_else(|| anyhow!("key {:?} not on keypad", key))
+}
+
+/// The sequences worth considering for moving between two keys and pressing the second one.
+///
+/// Mixing horizontal and vertical moves is never shorter, so only both orders are tried.
+fn paths(from: Position, to: Position, gap: Position) -> Vec<String>
+{
+	let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+	let horizontal = if dx < 0 { "<" } else { ">" }.repeat(dx.unsigned_abs());
+	let vertical = if dy < 0 { "^" } else { "v" }.repeat(dy.unsigned_abs());
+
+	let mut paths = Vec::new();
+	if (to.0, from.1) != gap
+	{
+		paths.push(format!("{}{}A", horizontal, vertical));
+	}
+	if (from.0, to.1) != gap
+	{
+		paths.push(format!("{}{}A", vertical, horizontal));
+	}
+	paths.dedup();
+	paths
+}
+
+struct Keypads
+{
+	memo: std::collections::HashMap<(char, char, usize), usize>,
+}
+
+impl Keypads
+{
+	/// Presses needed on the outermost keypad to type the sequence on a directional keypad
+	/// operated through `depth` further robots.
+	fn cost(&mut self, sequence: &str, depth: usize) -> Result<usize>
+	{
+		if depth == 0
+		{
+			return Ok(sequence.len());
+		}
+		std::iter::once('A').chain(sequence.chars())
+			.zip(sequence.chars())
+			.map(|(from, to)|
+			{
+				if let Some(&cost) = self.memo.get(&(from, to, depth))
+				{
+					return Ok(cost);
+				}
+				let mut best = usize::MAX;
+				for path in paths(find(&DIRECTIONAL, from)?, find(&DIRECTIONAL, to)?, DIRECTIONAL_GAP)
+				{
+					best = best.min(self.cost(&path, depth - 1)?);
+				}
+				self.memo.insert((from, to, depth), best);
+				Ok(best)
+			})
+			.sum()
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut keypads = Keypads { memo: Default::default(), };
+		let mut result = 0;
+		for code in self.input.lines()
+		{
+			let mut presses = 0;
+			for (from, to) in std::iter::once('A').chain(code.chars()).zip(code.chars())
+			{
+				let mut best = usize::MAX;
+				for path in paths(find(&NUMERIC, from)?, find(&NUMERIC, to)?, NUMERIC_GAP)
+				{
+					best = best.min(keypads.cost(&path, self.robots)?);
+				}
+				presses += best;
+			}
+			let value = code.trim_end_matches('A').parse::<usize>().context(Error::AocParsing)?;
+			debug!("code {} needs {} presses", code, presses);
+			result += presses * value;
+		}
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,99 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d22::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     1\n\
+///     10\n\
+///     100\n\
+///     2024";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "37327623");
+/// let input = "\
+///     1\n\
+///     2\n\
+///     3\n\
+///     2024";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "23");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+fn next(mut secret: u64) -> u64
+{
+	const PRUNE: u64 = 16777216;
+	secret = ((secret * 64) ^ secret) % PRUNE;
+	secret = ((secret / 32) ^ secret) % PRUNE;
+	((secret * 2048) ^ secret) % PRUNE
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let buyers = self.input.lines()
+			.map(|line| Ok(line.parse::<u64>()?))
+			.collect::<Result<Vec<_>>>()?;
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				buyers.into_iter()
+					.map(|secret| std::iter::successors(Some(secret), |&secret| Some(next(secret))).nth(2000).unwrap())
+					.sum::<u64>()
+			},
+			Part::Two =>
+			{
+				// bananas per sequence of four changes, only the first occurrence per buyer counts
+				let mut bananas = std::collections::HashMap::<[i64; 4], u64>::new();
+				for secret in buyers
+				{
+					let prices = std::iter::successors(Some(secret), |&secret| Some(next(secret)))
+						.take(2001)
+						.map(|secret| (secret % 10) as i64)
+						.collect::<Vec<_>>();
+					let mut seen = std::collections::HashSet::new();
+					for window in prices.windows(5)
+					{
+						let changes = [window[1] - window[0], window[2] - window[1], window[3] - window[2], window[4] - window[3]];
+						if seen.insert(changes)
+						{
+							*bananas.entry(changes).or_default() += window[4] as u64;
+						}
+					}
+				}
+				bananas.into_values().max().ok_or(Error::AocNoSolution)?
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
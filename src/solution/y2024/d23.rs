@@ -0,0 +1,122 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d23::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nvc-aq\n\
+///     tb-ka\nwh-tc\nyn-cg\nkh-ub\nta-co\nde-co\ntc-td\ntb-wq\n\
+///     wh-td\nta-ka\ntd-qp\naq-cg\nwq-ub\nub-vc\nde-ta\nwq-aq\n\
+///     wq-vc\nwh-yn\nka-de\nkh-ta\nco-tc\nwh-qp\ntb-vc\ntd-yn";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "7");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "co,de,ka,ta");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+type Graph<'a> = std::collections::BTreeMap<&'a str, std::collections::BTreeSet<&'a str>>;
+
+/// Bron–Kerbosch with pivoting, keeping the largest clique found.
+fn largest_clique<'a>(graph: &Graph<'a>, clique: Vec<&'a str>, mut candidates: std::collections::BTreeSet<&'a str>, mut excluded: std::collections::BTreeSet<&'a str>, best: &mut Vec<&'a str>)
+{
+	if candidates.is_empty() && excluded.is_empty()
+	{
+		if clique.len() > best.len()
+		{
+			*best = clique;
+		}
+		return;
+	}
+	let pivot = candidates.iter().chain(excluded.iter())
+		.max_by_key(|node| graph[*node].intersection(&candidates).count())
+		.copied()
+		.unwrap();
+	let remaining = candidates.difference(&graph[pivot]).copied().collect::<Vec<_>>();
+	for node in remaining
+	{
+		let mut next = clique.clone();
+		next.push(node);
+		largest_clique
+		(
+			graph,
+			next,
+			candidates.intersection(&graph[node]).copied().collect(),
+			excluded.intersection(&graph[node]).copied().collect(),
+			best,
+		);
+		candidates.remove(node);
+		excluded.insert(node);
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut graph = Graph::new();
+		for line in self.input.lines()
+		{
+			let (a, b) = line.split_once('-').ok_or(Error::AocParsing).with_context(|| format!("invalid connection {:?}", line))?;
+			graph.entry(a).or_default().insert(b);
+			graph.entry(b).or_default().insert(a);
+		}
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				let mut count = 0;
+				for (&a, neighbours) in graph.iter()
+				{
+					for &b in neighbours.range(a..).skip_while(|&&b| b == a)
+					{
+						for &c in graph[b].range(b..).skip_while(|&&c| c == b)
+						{
+							if neighbours.contains(c) && [a, b, c].iter().any(|name| name.starts_with('t'))
+							{
+								count += 1;
+							}
+						}
+					}
+				}
+				count.to_string()
+			},
+			Part::Two =>
+			{
+				let mut best = Vec::new();
+				largest_clique(&graph, Vec::new(), graph.keys().copied().collect(), Default::default(), &mut best);
+				best.sort_unstable();
+				best.join(",")
+			},
+		};
+
+		Ok(result)
+	}
+}
@@ -0,0 +1,204 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d24::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     x00: 1\n\
+///     x01: 1\n\
+///     x02: 1\n\
+///     y00: 0\n\
+///     y01: 1\n\
+///     y02: 0\n\
+///     \n\
+///     x00 AND y00 -> z00\n\
+///     x01 XOR y01 -> z01\n\
+///     x02 OR y02 -> z02";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "4");
+/// ```
+///
+/// Part 2 on a three bit ripple-carry adder with two pairs of swapped outputs:
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d24::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     x00: 1\n\
+///     x01: 1\n\
+///     x02: 1\n\
+///     y00: 0\n\
+///     y01: 1\n\
+///     y02: 0\n\
+///     \n\
+///     x00 XOR y00 -> z00\n\
+///     x00 AND y00 -> c00\n\
+///     x01 XOR y01 -> s01\n\
+///     s01 XOR c00 -> a01\n\
+///     x01 AND y01 -> z01\n\
+///     s01 AND c00 -> b01\n\
+///     a01 OR b01 -> c01\n\
+///     x02 XOR y02 -> a02\n\
+///     s02 XOR c01 -> z02\n\
+///     x02 AND y02 -> s02\n\
+///     s02 AND c01 -> b02\n\
+///     a02 OR b02 -> z03";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "a01,a02,s02,z01");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation
+{
+	And,
+	Or,
+	Xor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Gate<'a>
+{
+	inputs: [&'a str; 2],
+	operation: Operation,
+	output: &'a str,
+}
+
+impl<'a> Gate<'a>
+{
+	fn has_xy_inputs(&self) -> bool
+	{
+		self.inputs.iter().all(|input| input.starts_with('x') || input.starts_with('y'))
+	}
+
+	fn is_first_bit(&self) -> bool
+	{
+		self.inputs.iter().all(|input| input.ends_with("00"))
+	}
+}
+
+fn evaluate<'a>(wire: &'a str, gates: &std::collections::BTreeMap<&'a str, Gate<'a>>, values: &mut std::collections::BTreeMap<&'a str, bool>) -> Result<bool>
+{
+	if let Some(&value) = values.get(wire)
+	{
+		return Ok(value);
+	}
+	let gate = gates.get(wire).ok_or_else(|| anyhow!("wire {} has no value", wire))?;
+	// mark the wire while evaluating to bail out on loops
+	values.insert(wire, false);
+	let (a, b) = (evaluate(gate.inputs[0], gates, values)?, evaluate(gate.inputs[1], gates, values)?);
+	let value = match gate.operation
+	{
+		Operation::And => a && b,
+		Operation::Or => a || b,
+		Operation::Xor => a ^ b,
+	};
+	values.insert(wire, value);
+	Ok(value)
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (initial, gates) = self.input.split_once("\n\n").ok_or(Error::AocParsing).context("cannot find gates")?;
+		let mut values = initial.lines()
+			.map(|line|
+			{
+				let (wire, value) = line.split_once(": ").ok_or(Error::AocParsing).context("invalid initial value")?;
+				Ok((wire, value == "1"))
+			})
+			.collect::<Result<std::collections::BTreeMap<_, _>>>()?;
+		let gates = gates.lines()
+			.map(|line|
+			{
+				let parts = line.split_whitespace().collect::<Vec<_>>();
+				let (a, operation, b, output) = match parts.as_slice()
+				{
+					&[a, operation, b, "->", output] => (a, operation, b, output),
+					_ => bail!("invalid gate {:?}", line),
+				};
+				let operation = match operation
+				{
+					"AND" => Operation::And,
+					"OR" => Operation::Or,
+					"XOR" => Operation::Xor,
+					operation => bail!("unknown operation {:?}", operation),
+				};
+				Ok((output, Gate { inputs: [a, b], operation, output, }))
+			})
+			.collect::<Result<std::collections::BTreeMap<_, _>>>()?;
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				let mut result = 0u64;
+				for &wire in gates.keys().filter(|wire| wire.starts_with('z')).rev()
+				{
+					result = result << 1 | evaluate(wire, &gates, &mut values)? as u64;
+				}
+				result.to_string()
+			},
+			Part::Two =>
+			{
+				// the circuit has to be a ripple-carry adder, every gate not fitting that pattern has a swapped output
+				let last = gates.keys().filter(|wire| wire.starts_with('z')).max().copied().ok_or(Error::AocParsing)?;
+				let feeds = |wire: &str, operation: Operation|
+				{
+					gates.values().any(|gate| gate.operation == operation && gate.inputs.contains(&wire))
+				};
+				let mut swapped = gates.values()
+					.filter(|gate|
+					{
+						let wrong = match gate.operation
+						{
+							_ if gate.output.starts_with('z') && gate.output != last => gate.operation != Operation::Xor,
+							_ if gate.output == last => gate.operation != Operation::Or,
+							Operation::Xor if !gate.has_xy_inputs() => true,
+							Operation::Xor => !gate.is_first_bit() && !feeds(gate.output, Operation::Xor),
+							Operation::And => !gate.is_first_bit() && !feeds(gate.output, Operation::Or),
+							Operation::Or => false,
+						};
+						if wrong
+						{
+							debug!("gate does not fit a ripple-carry adder: {:?}", gate);
+						}
+						wrong
+					})
+					.map(|gate| gate.output)
+					.collect::<Vec<_>>();
+				swapped.sort_unstable();
+				swapped.join(",")
+			},
+		};
+
+		Ok(result)
+	}
+}
@@ -0,0 +1,98 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d25::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     ######\n\
+///     .####\n\
+///     .####\n\
+///     .####\n\
+///     .#.#.\n\
+///     .#...\n\
+///     .....\n\
+///     \n\
+///     ######\n\
+///     ###.##\n\
+///     .#.##\n\
+///     ...##\n\
+///     ...#.\n\
+///     ...#.\n\
+///     .....\n\
+///     \n\
+///     .....\n\
+///     ##....\n\
+///     ##....\n\
+///     ##...#\n\
+///     ##.#.#\n\
+///     ##.###\n\
+///     ######\n\
+///     \n\
+///     .....\n\
+///     .....\n\
+///     ##.#..\n\
+///     ####..\n\
+///     ####.#\n\
+///     ####.#\n\
+///     ######\n\
+///     \n\
+///     .....\n\
+///     .....\n\
+///     .....\n\
+///     ##....\n\
+///     ##.#..\n\
+///     ##.#.#\n\
+///     ######";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "3");
+/// ```
+pub struct Solution
+{
+	input: String,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { input, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut locks = Vec::new();
+		let mut keys = Vec::new();
+		for schematic in self.input.split("\n\n")
+		{
+			let rows = schematic.lines().collect::<Vec<_>>();
+			let first = rows.first().ok_or(Error::AocParsing).context("empty schematic")?;
+			let width = first.len();
+			// column heights including the solid row, overlaps are easier to check that way
+			let heights = (0..width)
+				.map(|x| rows.iter().filter(|row| row.as_bytes().get(x) == Some(&b'#')).count())
+				.collect::<Vec<_>>();
+			if first.chars().all(|ch| ch == '#')
+			{
+				locks.push(heights);
+			}
+			else
+			{
+				keys.push(heights);
+			}
+		}
+		let height = self.input.lines().take_while(|line| !line.is_empty()).count();
+
+		let result = locks.iter()
+			.flat_map(|lock| keys.iter().map(move |key| (lock, key)))
+			.filter(|(lock, key)| lock.iter().zip(key.iter()).all(|(a, b)| a + b <= height))
+			.count();
+
+		Ok(format!("{}", result))
+	}
+}
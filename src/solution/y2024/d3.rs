@@ -0,0 +1,66 @@
+use crate::error::*;
+
+use regex::Regex;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d3::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "161");
+/// let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "48");
+/// ```
+pub struct Solution
+{
+	input: String,
+	conditionals: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { conditionals: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { conditionals: true, input, }
+	}
+}
+
+lazy_static::lazy_static!
+{
+	static ref INSTRUCTION: Regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\)").unwrap();
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let mut enabled = true;
+		let mut result = 0usize;
+		for captures in INSTRUCTION.captures_iter(&self.input)
+		{
+			match &captures[0]
+			{
+				"do()" => enabled = true,
+				"don't()" => enabled = !self.conditionals,
+				instruction =>
+				{
+					trace!("{} (enabled: {})", instruction, enabled);
+					if enabled
+					{
+						result += captures[1].parse::<usize>()? * captures[2].parse::<usize>()?;
+					}
+				},
+			}
+		}
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,89 @@
+use crate::error::*;
+
+use super::super::common::grid::{ Grid, Position, Vector, NEIGHBOURS8 };
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d4::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     MMMSXXMASM\n\
+///     MSAMXMSMSA\n\
+///     AMXSXMAAMM\n\
+///     MSAMASMSMX\n\
+///     XMASAMXAMM\n\
+///     XXAMMXXAMA\n\
+///     SMSMSASXSS\n\
+///     SAXAMASAAA\n\
+///     MAMMMXMMMM\n\
+///     MXMXAXMASX";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "18");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "9");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let grid = self.input.parse::<Grid<char>>()?;
+		let get = |(x, y): Position, (dx, dy): Vector| grid.get_signed((x as isize + dx, y as isize + dy)).copied();
+
+		let result = match self.part
+		{
+			Part::One =>
+			{
+				grid.positions()
+					.flat_map(|pos| NEIGHBOURS8.iter().map(move |&dir| (pos, dir)))
+					.filter(|&(pos, (dx, dy))|
+					{
+						"XMAS".chars()
+							.enumerate()
+							.all(|(idx, ch)| get(pos, (dx * idx as isize, dy * idx as isize)) == Some(ch))
+					})
+					.count()
+			},
+			Part::Two =>
+			{
+				grid.positions()
+					.filter(|&pos|
+					{
+						let diagonal = |a, b| matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')));
+						grid[pos] == 'A'
+							&& diagonal(get(pos, (-1, -1)), get(pos, (1, 1)))
+							&& diagonal(get(pos, (-1, 1)), get(pos, (1, -1)))
+					})
+					.count()
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,127 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d5::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     47|53\n\
+///     97|13\n\
+///     97|61\n\
+///     97|47\n\
+///     75|29\n\
+///     61|13\n\
+///     75|53\n\
+///     29|13\n\
+///     97|29\n\
+///     53|29\n\
+///     61|53\n\
+///     97|53\n\
+///     61|29\n\
+///     47|13\n\
+///     75|47\n\
+///     97|75\n\
+///     47|61\n\
+///     75|61\n\
+///     47|29\n\
+///     75|13\n\
+///     53|13\n\
+///     \n\
+///     75,47,61,53,29\n\
+///     97,61,53,29,13\n\
+///     75,29,13\n\
+///     75,97,47,61,53\n\
+///     61,13,29\n\
+///     97,13,75,29,47";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "143");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "123");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let (rules, updates) = self.input.split_once("\n\n").ok_or(Error::AocParsing).context("cannot find updates")?;
+
+		let rules = rules.lines()
+			.map(|line|
+			{
+				let (before, after) = line.split_once('|').ok_or(Error::AocParsing).context("invalid ordering rule")?;
+				Ok((before.parse::<usize>()?, after.parse::<usize>()?))
+			})
+			.collect::<Result<std::collections::BTreeSet<_>>>()?;
+
+		let updates = updates.lines()
+			.map(|line|
+			{
+				line.split(',')
+					.map(|s| Ok(s.parse::<usize>()?))
+					.collect::<Result<Vec<_>>>()
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let compare = |a: &usize, b: &usize|
+		{
+			if rules.contains(&(*a, *b))
+			{
+				std::cmp::Ordering::Less
+			}
+			else if rules.contains(&(*b, *a))
+			{
+				std::cmp::Ordering::Greater
+			}
+			else
+			{
+				std::cmp::Ordering::Equal
+			}
+		};
+
+		let result: usize = updates.into_iter()
+			.filter_map(|mut update|
+			{
+				let ordered = update.is_sorted_by(|a, b| compare(a, b) != std::cmp::Ordering::Greater);
+				match (self.part, ordered)
+				{
+					(Part::One, true) => Some(update[update.len() / 2]),
+					(Part::Two, false) =>
+					{
+						update.sort_by(compare);
+						trace!("reordered update: {:?}", update);
+						Some(update[update.len() / 2])
+					},
+					_ => None,
+				}
+			})
+			.sum();
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,130 @@
+use crate::error::*;
+
+use super::super::common::grid::{ Direction, Grid, Position };
+
+use std::convert::TryFrom;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d6::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     ....#.....\n\
+///     .........#\n\
+///     ..........\n\
+///     ..#.......\n\
+///     .......#..\n\
+///     ..........\n\
+///     .#..^.....\n\
+///     ........#.\n\
+///     ##.........\n\
+///     ......#...";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "41");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "6");
+/// ```
+pub struct Solution
+{
+	input: String,
+	part: Part,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { part: Part::One, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { part: Part::Two, input, }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part
+{
+	One,
+	Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile
+{
+	Floor,
+	Obstacle,
+	Guard,
+}
+
+impl TryFrom<char> for Tile
+{
+	type Error = Error;
+	fn try_from(ch: char) -> std::result::Result<Self, Error>
+	{
+		Ok(match ch
+		{
+			'.' => Tile::Floor,
+			'#' => Tile::Obstacle,
+			'^' => Tile::Guard,
+			ch => Err(anyhow!("unknown map tile {:?}", ch))?,
+		})
+	}
+}
+
+/// Walks the guard, returning the visited positions or `None` if the guard loops.
+fn walk(lab: &Grid<Tile>, mut position: Position, extra: Option<Position>) -> Option<std::collections::BTreeSet<Position>>
+{
+	let mut direction = Direction::Up;
+	let mut seen = std::collections::BTreeSet::new();
+	loop
+	{
+		if !seen.insert((position, direction))
+		{
+			return None;
+		}
+		let next = match lab.step(position, direction)
+		{
+			Some(next) => next,
+			None => break,
+		};
+		if lab[next] == Tile::Obstacle || Some(next) == extra
+		{
+			direction = direction.right();
+		}
+		else
+		{
+			position = next;
+		}
+	}
+	Some(seen.into_iter().map(|(position, _)| position).collect())
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let lab: Grid<Tile> = self.input.parse()?;
+		let start = lab.find(|&tile| tile == Tile::Guard).ok_or(Error::AocParsing).context("no guard on the map")?;
+
+		let visited = walk(&lab, start, None).ok_or_else(|| anyhow!("guard loops without any added obstacle"))?;
+
+		let result = match self.part
+		{
+			Part::One => visited.len(),
+			Part::Two =>
+			{
+				// only positions on the original path can change anything
+				use rayon::prelude::*;
+				visited.into_par_iter()
+					.filter(|&position| position != start)
+					.filter(|&position| walk(&lab, start, Some(position)).is_none())
+					.count()
+			},
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,96 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d7::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     190: 10 19\n\
+///     3267: 81 40 27\n\
+///     83: 17 5\n\
+///     156: 15 6\n\
+///     7290: 6 8 6 15\n\
+///     161011: 16 10 13\n\
+///     192: 17 8 14\n\
+///     21037: 9 7 18 13\n\
+///     292: 11 6 16 20";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "3749");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "11387");
+/// ```
+pub struct Solution
+{
+	input: String,
+	concatenation: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { concatenation: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { concatenation: true, input, }
+	}
+}
+
+/// Works backwards from the target, undoing the operators from the right.
+fn solvable(target: u64, numbers: &[u64], concatenation: bool) -> bool
+{
+	let (&last, rest) = match numbers.split_last()
+	{
+		Some(split) => split,
+		None => return false,
+	};
+	if rest.is_empty()
+	{
+		return target == last;
+	}
+	if target >= last && solvable(target - last, rest, concatenation)
+	{
+		return true;
+	}
+	if last != 0 && target.is_multiple_of(last) && solvable(target / last, rest, concatenation)
+	{
+		return true;
+	}
+	if concatenation
+	{
+		let magnitude = 10u64.pow(last.checked_ilog10().unwrap_or(0) + 1);
+		if target % magnitude == last && solvable(target / magnitude, rest, concatenation)
+		{
+			return true;
+		}
+	}
+	false
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let equations = self.input.lines()
+			.map(|line|
+			{
+				let (target, numbers) = line.split_once(": ").ok_or(Error::AocParsing).context("missing test value")?;
+				let numbers = numbers.split_whitespace()
+					.map(|s| Ok(s.parse::<u64>()?))
+					.collect::<Result<Vec<_>>>()?;
+				Ok((target.parse::<u64>()?, numbers))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let result: u64 = equations.into_iter()
+			.filter(|(target, numbers)| solvable(*target, numbers, self.concatenation))
+			.inspect(|equation| trace!("solvable: {:?}", equation))
+			.map(|(target, _)| target)
+			.sum();
+
+		Ok(format!("{}", result))
+	}
+}
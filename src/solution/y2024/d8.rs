@@ -0,0 +1,96 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d8::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "\
+///     ............\n\
+///     ........0...\n\
+///     .....0......\n\
+///     .......0....\n\
+///     ....0.......\n\
+///     ......A.....\n\
+///     ............\n\
+///     ............\n\
+///     ........A...\n\
+///     .........A..\n\
+///     ............\n\
+///     ............";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "14");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "34");
+/// ```
+pub struct Solution
+{
+	input: String,
+	harmonics: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { harmonics: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { harmonics: true, input, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let height = self.input.lines().count() as isize;
+		let width = self.input.lines().next().map(str::len).unwrap_or(0) as isize;
+		let contains = |(x, y): (isize, isize)| (0..width).contains(&x) && (0..height).contains(&y);
+
+		let mut antennas = std::collections::BTreeMap::<char, Vec<(isize, isize)>>::new();
+		for (y, line) in self.input.lines().enumerate()
+		{
+			for (x, ch) in line.chars().enumerate()
+			{
+				if ch != '.'
+				{
+					antennas.entry(ch).or_default().push((x as isize, y as isize));
+				}
+			}
+		}
+
+		let mut antinodes = std::collections::BTreeSet::new();
+		for positions in antennas.values()
+		{
+			for &a in positions.iter()
+			{
+				for &b in positions.iter().filter(|&&b| b != a)
+				{
+					let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+					if self.harmonics
+					{
+						let mut position = b;
+						while contains(position)
+						{
+							antinodes.insert(position);
+							position = (position.0 + dx, position.1 + dy);
+						}
+					}
+					else
+					{
+						let position = (b.0 + dx, b.1 + dy);
+						if contains(position)
+						{
+							antinodes.insert(position);
+						}
+					}
+				}
+			}
+		}
+
+		Ok(format!("{}", antinodes.len()))
+	}
+}
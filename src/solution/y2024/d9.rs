@@ -0,0 +1,108 @@
+use crate::error::*;
+
+/// # Examples
+///
+/// ```
+/// # use adventofcode::solution::{ y2024::d9::Solution, Solution as S };
+/// # env_logger::init();
+/// let input = "2333133121414131402";
+/// assert_eq!(Solution::part1(input.to_string()).solve().unwrap(), "1928");
+/// assert_eq!(Solution::part2(input.to_string()).solve().unwrap(), "2858");
+/// ```
+pub struct Solution
+{
+	input: String,
+	whole_files: bool,
+}
+
+impl Solution
+{
+	pub fn part1(input: String) -> Self
+	{
+		Self { whole_files: false, input, }
+	}
+
+	pub fn part2(input: String) -> Self
+	{
+		Self { whole_files: true, input, }
+	}
+}
+
+impl super::super::Solution for Solution
+{
+	fn solve(&self) -> Result<String>
+	{
+		debug!("called with input: {}", self.input);
+
+		let lengths = self.input.trim()
+			.chars()
+			.map(|ch| ch.to_digit(10).map(|d| d as usize).ok_or(Error::AocParsing).with_context(|| format!("invalid length {:?}", ch)))
+			.collect::<Result<Vec<_>>>()?;
+
+		// (start, length) of every file by id and every gap in order
+		let mut files = Vec::new();
+		let mut gaps = Vec::new();
+		let mut position = 0;
+		for (idx, &length) in lengths.iter().enumerate()
+		{
+			if idx % 2 == 0
+			{
+				files.push((position, length));
+			}
+			else
+			{
+				gaps.push((position, length));
+			}
+			position += length;
+		}
+
+		let result: usize = if self.whole_files
+		{
+			for file in files.iter_mut().rev()
+			{
+				if let Some(gap) = gaps.iter_mut()
+					.take_while(|(start, _)| *start < file.0)
+					.find(|(_, length)| *length >= file.1)
+				{
+					file.0 = gap.0;
+					gap.0 += file.1;
+					gap.1 -= file.1;
+				}
+			}
+			files.iter()
+				.enumerate()
+				.map(|(id, &(start, length))| (start..start + length).sum::<usize>() * id)
+				.sum()
+		}
+		else
+		{
+			let mut blocks = lengths.iter()
+				.enumerate()
+				.flat_map(|(idx, &length)| std::iter::repeat_n((idx % 2 == 0).then_some(idx / 2), length))
+				.collect::<Vec<_>>();
+			let (mut left, mut right) = (0, blocks.len());
+			loop
+			{
+				while left < right && blocks[left].is_some()
+				{
+					left += 1;
+				}
+				while left < right && blocks[right - 1].is_none()
+				{
+					right -= 1;
+				}
+				if left + 1 >= right
+				{
+					break;
+				}
+				blocks.swap(left, right - 1);
+			}
+			blocks.iter()
+				.enumerate()
+				.filter_map(|(position, id)| id.map(|id| position * id))
+				.sum()
+		};
+
+		Ok(format!("{}", result))
+	}
+}
@@ -0,0 +1,100 @@
+pub mod d1;
+pub mod d2;
+pub mod d3;
+pub mod d4;
+pub mod d5;
+pub mod d6;
+pub mod d7;
+pub mod d8;
+pub mod d9;
+pub mod d10;
+pub mod d11;
+pub mod d12;
+pub mod d13;
+pub mod d14;
+pub mod d15;
+pub mod d16;
+pub mod d17;
+pub mod d18;
+pub mod d19;
+pub mod d20;
+pub mod d21;
+pub mod d22;
+pub mod d23;
+pub mod d24;
+pub mod d25;
+
+pub fn parsers<'a>() -> Vec<Box<dyn super::InputParser<'a>>>
+{
+	#[allow(unused)]
+	enum InputType<'a>
+	{
+		UrlInput(Box<dyn Fn(Option<String>) -> Box<dyn super::Solution + 'static>>),
+		Parser(Box<dyn super::InputParser<'a>>),
+	}
+
+	let days: Vec<Option<InputType>> = vec!
+	[
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d1::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d1::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d2::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d2::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d3::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d3::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d4::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d4::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d5::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d5::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d6::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d6::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d7::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d7::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d8::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d8::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d9::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d9::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d10::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d10::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d11::Solution::with_blinks(25, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d11::Solution::with_blinks(75, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d12::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d12::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d13::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d13::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d14::Solution::part1(101, 103, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d14::Solution::part2(101, 103, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d15::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d16::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d16::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d17::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d17::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part1(70, 1024, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d18::Solution::part2(70, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d19::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::with_cheat(2, 100, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d20::Solution::with_cheat(20, 100, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::with_robots(2, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d21::Solution::with_robots(25, input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d22::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d23::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d24::Solution::part1(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d24::Solution::part2(input.expect("empty input received")))))),
+		Some(InputType::UrlInput(Box::new(|input| Box::new(d25::Solution::part1(input.expect("empty input received")))))),
+	];
+
+	days.into_iter()
+		.enumerate()
+		.filter_map(|(idx,opt)| opt.map(|parser| (idx,parser)))
+		.map(|(idx,parser)|
+			match parser
+			{
+				InputType::UrlInput(parser) => Box::new((2024usize,idx/2+1,idx%2+1,parser)) as Box<dyn super::InputParser>,
+				InputType::Parser(parser) => parser,
+			}
+		)
+		.collect()
+}